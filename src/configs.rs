@@ -19,18 +19,358 @@
 //!        println!("{:?}", cfg);
 //! }
 //! ```
+//!
+//! # Configuration File
+//!
+//! The configuration file (YAML) defines the data sources to analyze, the processors and model options of the algorithm,
+//! and the output settings of the generated data. All the sections are optional.
+//!
+//! ```yaml
+//! ---
+//! data-algorithm-creator:
+//!   data-source:
+//!     # a list of sample values for a single field ...
+//!     delimited-string:
+//!       field: "greeting"
+//!       delimiter: ","
+//!       value: "Hello,World,from,Test,Data,Generation"
+//!     # ... or a csv file of sample data
+//!     # csv-file:
+//!     #   path: "./tests/samples/sample-01.csv"
+//!     #   delimiter: ","
+//!   processors: 4
+//!   model:
+//!     seed: 1234
+//!     markov-order: 2
+//!     correlation: true
+//!     date-shift: 30
+//!     # analyze the numbers of 9 or 10 digits (e.g.: 1483525800) as epoch seconds
+//!     epoch-seconds: false
+//!     # custom classes of chars with their own pattern symbols, (checked in the order of their priority)
+//!     char-classes:
+//!       - symbol: "h"
+//!         chars: "0123456789abcdef"
+//!         priority: 0
+//!       - symbol: "G"
+//!         regex: "\\p{Greek}"
+//!         priority: 1
+//!     novelty: 0.2
+//!     # the fields that only have unique values
+//!     unique:
+//!       - "id"
+//!     # the tokens that represent a null value, (default "NULL", "N/A", "#N/A" and "\\N")
+//!     null-sentinels:
+//!       - "NULL"
+//!       - "-"
+//!     # don't generate the analyzed values (nor values within this Levenshtein distance of them)
+//!     privacy-min-distance: 2
+//!     # the secret key (hexadecimal) of the hashes of the privacy mode, (keep the file outside of the exported data sample parser)
+//!     privacy-key: "0x5eed"
+//!     # export the profiles with noisy counts and without the patterns that occurred fewer than 5 times
+//!     differential-privacy:
+//!       epsilon: 1.0
+//!       k-threshold: 5
+//! data-generator:
+//!   output:
+//!     path: "./tests/samples/generated-01.csv"
+//!     delimiter: ","
+//!     rows: 100
+//! ```
 
 //use std::path::Path;
+use crate::engine::char_class::CharClass;
+use crate::engine::markov::{MAX_ORDER, MIN_ORDER};
+use crate::engine::noise::DifferentialPrivacy;
+use crate::errors::{TdgError, TdgResult};
 use serde_json;
 use std::fs::File;
 use std::io::prelude::*;
-use yaml_rust::YamlLoader;
 
-#[derive(Serialize, Deserialize, Debug)]
+/// The name of the field of a delimited-string data source that doesn't specify one
+pub const DEFAULT_FIELD: &str = "value";
+/// The number of rows to generate if the output settings don't specify it
+pub const DEFAULT_ROWS: u32 = 100;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+/// Represents the settings of a configuration file
+pub struct Settings {
+    /// the settings used to analyze the data sample (create the algorithm)
+    #[serde(default)]
+    pub data_algorithm_creator: AlgorithmCreatorSettings,
+    /// the settings used to generate the test data
+    #[serde(default)]
+    pub data_generator: GeneratorSettings,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+/// Represents the settings used to analyze the data sample
+pub struct AlgorithmCreatorSettings {
+    /// the data sample to analyze when the DataSampleParser is created
+    #[serde(default, with = "serde_yaml::with::singleton_map")]
+    pub data_source: Option<DataSource>,
+    /// the number of processors of the Profiles
+    pub processors: Option<u8>,
+    /// the options of the model used to generate data
+    #[serde(default)]
+    pub model: ModelSettings,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+/// Represents a data sample to analyze
+pub enum DataSource {
+    /// a list of sample values for a single field, separated by a delimiter
+    DelimitedString {
+        /// the name of the field, (default "value")
+        field: Option<String>,
+        /// the char that separates the values, (default ",")
+        delimiter: Option<String>,
+        /// the sample values
+        value: String,
+    },
+    /// a csv file of sample data with a header row
+    CsvFile {
+        /// the path of the csv file
+        path: String,
+        /// the char that separates the fields, (default ",")
+        delimiter: Option<String>,
+    },
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+/// Represents the options of the model used to generate data
+pub struct ModelSettings {
+    /// the seed of the random number generators so that the generated data can be reproduced
+    pub seed: Option<u64>,
+    /// the order of the n-gram (Markov chain) model, (1 to 4)
+    pub markov_order: Option<u8>,
+    /// generate records with combinations of patterns of the fields that occurred together in the data sample, (default false)
+    pub correlation: Option<bool>,
+    /// the number of days to shift the range of the generated dates by, (negative for earlier dates)
+    pub date_shift: Option<i64>,
+    /// analyze the numbers of 9 or 10 digits as epoch seconds instead of as numbers, (default false)
+    pub epoch_seconds: Option<bool>,
+    /// the custom classes of chars with their own pattern symbols, (see engine::char_class::CharClass)
+    pub char_classes: Option<Vec<CharClass>>,
+    /// the chance (0.0 to 1.0) that a value is generated from a synthesized pattern instead of an analyzed pattern, (default 0.0)
+    pub novelty: Option<f64>,
+    /// the fields that only have unique values, (e.g.: primary keys and usernames)
+    pub unique: Option<Vec<String>>,
+    /// the tokens that represent a null value, which are generated at the same rate instead of as patterns, (see engine::missing::NULL_SENTINELS)
+    pub null_sentinels: Option<Vec<String>>,
+    /// don't generate the analyzed values, nor values within this Levenshtein distance of them, (0 only rejects the analyzed values)
+    pub privacy_min_distance: Option<usize>,
+    /// the secret key (hexadecimal) of the hashes of the privacy mode, so that the exported data sample parser can generate data again, (see to_privacy_key())
+    pub privacy_key: Option<String>,
+    /// add noise to the statistics of the exported Profiles and suppress the rare patterns, (see engine::noise::DifferentialPrivacy)
+    pub differential_privacy: Option<DifferentialPrivacy>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+/// Represents the settings used to generate the test data
+pub struct GeneratorSettings {
+    /// the csv file to generate
+    pub output: Option<OutputSettings>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+/// Represents the csv file to generate
+pub struct OutputSettings {
+    /// the path of the csv file
+    pub path: String,
+    /// the char that separates the fields, (default ",")
+    pub delimiter: Option<String>,
+    /// the number of rows to generate, (default 100)
+    pub rows: Option<u32>,
+}
+
+impl Settings {
+    /// This function checks that the values of the settings are supported, returning an InvalidSetting error for the first one that isn't.
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::configs::Settings;
+    ///
+    /// fn main() {
+    ///        let mut settings = Settings::default();
+    ///        assert!(settings.validate().is_ok());
+    ///
+    ///        settings.data_algorithm_creator.model.markov_order = Some(9);
+    ///        assert!(settings.validate().is_err());
+    /// }
+    /// ```
+    pub fn validate(&self) -> TdgResult<()> {
+        let creator = &self.data_algorithm_creator;
+
+        match &creator.data_source {
+            Some(DataSource::DelimitedString { delimiter, .. }) => {
+                to_delimiter(
+                    "data-algorithm-creator.data-source.delimited-string.delimiter",
+                    delimiter,
+                )?;
+            }
+            Some(DataSource::CsvFile { delimiter, .. }) => {
+                to_delimiter(
+                    "data-algorithm-creator.data-source.csv-file.delimiter",
+                    delimiter,
+                )?;
+            }
+            None => {}
+        }
+
+        if creator.processors == Some(0) {
+            return Err(invalid(
+                "data-algorithm-creator.processors",
+                "there must be at least 1 processor".to_string(),
+            ));
+        }
+
+        if let Some(order) = creator.model.markov_order {
+            if !(MIN_ORDER..=MAX_ORDER).contains(&order) {
+                return Err(invalid(
+                    "data-algorithm-creator.model.markov-order",
+                    format!(
+                        "the order must be between {} and {}, but was {}",
+                        MIN_ORDER, MAX_ORDER, order
+                    ),
+                ));
+            }
+        }
+
+        for class in creator.model.char_classes.iter().flatten() {
+            if let Err(TdgError::InvalidSetting { message, .. }) = class.validate() {
+                return Err(invalid(
+                    "data-algorithm-creator.model.char-classes",
+                    message,
+                ));
+            }
+        }
+
+        if let Some(novelty) = creator.model.novelty {
+            if !(0.0..=1.0).contains(&novelty) {
+                return Err(invalid(
+                    "data-algorithm-creator.model.novelty",
+                    format!("the novelty must be between 0 and 1, but was {}", novelty),
+                ));
+            }
+        }
+
+        to_privacy_key(
+            "data-algorithm-creator.model.privacy-key",
+            &creator.model.privacy_key,
+        )?;
+
+        if let Some(dp) = &creator.model.differential_privacy {
+            if let Err(TdgError::InvalidSetting { message, .. }) = dp.validate() {
+                return Err(invalid(
+                    "data-algorithm-creator.model.differential-privacy",
+                    message,
+                ));
+            }
+        }
+
+        if let Some(output) = &self.data_generator.output {
+            to_delimiter("data-generator.output.delimiter", &output.delimiter)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// This function converts the delimiter of a setting to a byte, returning an error if it isn't a single (ascii) char
+///
+/// # Arguments
+///
+/// * `setting: &str` - The name of the setting (used in the error).</br>
+/// * `delimiter: &Option<String>` - The delimiter, if the setting was specified.</br>
+///
+/// #Example
+///
+/// ```
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::configs::to_delimiter;
+///
+/// fn main() {
+///        assert_eq!(to_delimiter("delimiter", &Some("|".to_string())).unwrap(), Some(b'|'));
+///        assert!(to_delimiter("delimiter", &Some("||".to_string())).is_err());
+/// }
+/// ```
+pub fn to_delimiter(setting: &str, delimiter: &Option<String>) -> TdgResult<Option<u8>> {
+    match delimiter {
+        None => Ok(None),
+        Some(d) if d.len() == 1 => Ok(Some(d.as_bytes()[0])),
+        Some(d) => Err(invalid(
+            setting,
+            format!(
+                "the delimiter must be a single (ascii) char, but was {:?}",
+                d
+            ),
+        )),
+    }
+}
+
+/// This function converts the secret key of the privacy mode of a setting from hexadecimal (with or without the 0x prefix)
+/// to a number, returning an error if it isn't a hexadecimal number of up to 32 digits
+///
+/// # Arguments
+///
+/// * `setting: &str` - The name of the setting (used in the error).</br>
+/// * `key: &Option<String>` - The key, if the setting was specified.</br>
+///
+/// #Example
+///
+/// ```
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::configs::to_privacy_key;
+///
+/// fn main() {
+///        assert_eq!(to_privacy_key("privacy-key", &Some("0x5eed".to_string())).unwrap(), Some(0x5eed));
+///        assert!(to_privacy_key("privacy-key", &Some("seed".to_string())).is_err());
+/// }
+/// ```
+pub fn to_privacy_key(setting: &str, key: &Option<String>) -> TdgResult<Option<u128>> {
+    match key {
+        None => Ok(None),
+        Some(k) => u128::from_str_radix(k.trim_start_matches("0x"), 16)
+            .map(Some)
+            .map_err(|e| {
+                invalid(
+                    setting,
+                    format!(
+                        "the key must be a hexadecimal number of up to 32 digits, ({})",
+                        e
+                    ),
+                )
+            }),
+    }
+}
+
+#[inline]
+fn invalid(setting: &str, message: String) -> TdgError {
+    TdgError::InvalidSetting {
+        setting: setting.to_string(),
+        message,
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
 // Represents a Configs object that can be set by an implementation of the test data generation library
 pub struct Configs {
     /// the file path of the test data generation library configuration file
     file: String,
+    /// the settings that have been loaded from the configuration file (not archived, since the file can be loaded again)
+    #[serde(skip)]
+    settings: Option<Settings>,
 }
 
 impl Configs {
@@ -58,7 +398,10 @@ impl Configs {
     /// ```
     pub fn new(path: &String) -> Configs {
         let pth = path.to_string().to_owned();
-        Configs { file: pth }
+        Configs {
+            file: pth,
+            settings: None,
+        }
     }
 
     /// Constructs a new Configs object from a serialized (JSON) string. This is used when restoring from "archive"
@@ -78,7 +421,25 @@ impl Configs {
     /// }
     /// ```
     pub fn from_serialized(serialized: &str) -> Configs {
-        serde_json::from_str(serialized).unwrap()
+        Self::try_from_serialized(serialized).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Constructs a new Configs object from a serialized (JSON) string, returning an error instead of panicking
+    /// if the string isn't a valid Configs object.
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::configs::Configs;
+    ///
+    /// fn main() {
+    ///        assert!(Configs::try_from_serialized("{\"path\":1}").is_err());
+    /// }
+    /// ```
+    pub fn try_from_serialized(serialized: &str) -> TdgResult<Configs> {
+        Ok(serde_json::from_str(serialized)?)
     }
 
     /// Loads the configuration file using the path that was provided during calling a new Configs object
@@ -121,17 +482,75 @@ impl Configs {
     /// }
     /// ```
     pub fn load_config_file(&mut self) {
-        let mut f = File::open(&self.file).unwrap_or_else(|_| {
-            panic!(
-                "Error: Configuration file not found at {}",
-                &self.file.to_string()
-            )
-        });
+        match self.try_load_config_file() {
+            Ok(_) => {}
+            Err(TdgError::FileOpen { path, .. }) => {
+                panic!("Error: Configuration file not found at {}", path)
+            }
+            Err(e) => panic!("Error: {}", e),
+        }
+    }
+
+    /// Loads the configuration file using the path that was provided during calling a new Configs object,
+    /// returning an error if the file couldn't be read, isn't valid YAML or has settings that aren't supported.
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::configs::Configs;
+    ///
+    /// fn main() {
+    ///        let mut cfg = Configs::new(&String::from("./badpath/tdg.yaml"));
+    ///
+    ///        assert!(cfg.try_load_config_file().is_err());
+    /// }
+    /// ```
+    pub fn try_load_config_file(&mut self) -> TdgResult<()> {
+        let mut f = File::open(&self.file).map_err(|e| TdgError::FileOpen {
+            path: self.file.to_string(),
+            source: e,
+        })?;
         let mut contents = String::new();
         f.read_to_string(&mut contents)
-            .expect("Something went wrong reading file");
-        let _cfg_yaml = &YamlLoader::load_from_str(&contents).expect("failed to load YAML file")[0];
-        //println!("{:?}", cfg);
+            .map_err(|e| TdgError::FileRead {
+                path: self.file.to_string(),
+                source: e,
+            })?;
+        let settings: Settings = if contents.trim().is_empty() {
+            Settings::default()
+        } else {
+            serde_yaml::from_str(&contents).map_err(|e| TdgError::Yaml {
+                path: self.file.to_string(),
+                source: e,
+            })?
+        };
+        settings.validate()?;
+        self.settings = Some(settings);
+
+        Ok(())
+    }
+
+    /// This function returns the settings that have been loaded from the configuration file, (see try_load_config_file()).
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::configs::Configs;
+    ///
+    /// fn main() {
+    ///        let mut cfg = Configs::new(&String::from("./tests/config/tdg.yaml"));
+    ///        assert!(cfg.get_settings().is_none());
+    ///
+    ///        cfg.load_config_file();
+    ///        assert!(cfg.get_settings().unwrap().data_algorithm_creator.data_source.is_some());
+    /// }
+    /// ```
+    pub fn get_settings(&self) -> Option<&Settings> {
+        self.settings.as_ref()
     }
 
     /// This function converts the Configs object to a serialize JSON string.
@@ -178,6 +597,141 @@ mod tests {
         cfg.load_config_file();
     }
 
+    #[test]
+    // ensure Configs returns an error when reading an invalid configuration file
+    fn try_load_config_bad_cfg_file() {
+        let mut cfg = Configs::new(&String::from("./badpath/tdg.yaml"));
+
+        match cfg.try_load_config_file() {
+            Err(TdgError::FileOpen { path, .. }) => assert_eq!(path, "./badpath/tdg.yaml"),
+            other => panic!("Expected a FileOpen error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    // ensure the settings of the configuration file are loaded
+    fn load_config_settings() {
+        let mut cfg = Configs::new(&String::from("./tests/config/tdg.yaml"));
+        cfg.try_load_config_file().unwrap();
+
+        assert_eq!(
+            cfg.get_settings()
+                .unwrap()
+                .data_algorithm_creator
+                .data_source,
+            Some(DataSource::DelimitedString {
+                field: None,
+                delimiter: Some(",".to_string()),
+                value: "Hello,World,from,Test,Data,Generation".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    // ensure settings that aren't supported are rejected
+    fn settings_from_yaml_invalid() {
+        let bad_delimiter =
+            "data-generator:\n  output:\n    path: out.csv\n    delimiter: \"||\"\n";
+        let settings: Settings = serde_yaml::from_str(bad_delimiter).unwrap();
+
+        match settings.validate() {
+            Err(TdgError::InvalidSetting { setting, .. }) => {
+                assert_eq!(setting, "data-generator.output.delimiter")
+            }
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+
+        let built_in_symbol = "data-algorithm-creator:\n  model:\n    char-classes:\n      - symbol: \"#\"\n        chars: abc\n";
+        let settings: Settings = serde_yaml::from_str(built_in_symbol).unwrap();
+        match settings.validate() {
+            Err(TdgError::InvalidSetting { setting, .. }) => {
+                assert_eq!(setting, "data-algorithm-creator.model.char-classes")
+            }
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+
+        let novelty = "data-algorithm-creator:\n  model:\n    novelty: 1.5\n";
+        let settings: Settings = serde_yaml::from_str(novelty).unwrap();
+        match settings.validate() {
+            Err(TdgError::InvalidSetting { setting, .. }) => {
+                assert_eq!(setting, "data-algorithm-creator.model.novelty")
+            }
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+
+        let epsilon =
+            "data-algorithm-creator:\n  model:\n    differential-privacy:\n      epsilon: 0\n";
+        let settings: Settings = serde_yaml::from_str(epsilon).unwrap();
+        match settings.validate() {
+            Err(TdgError::InvalidSetting { setting, .. }) => {
+                assert_eq!(setting, "data-algorithm-creator.model.differential-privacy")
+            }
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+
+        let privacy_key = "data-algorithm-creator:\n  model:\n    privacy-key: secret\n";
+        let settings: Settings = serde_yaml::from_str(privacy_key).unwrap();
+        match settings.validate() {
+            Err(TdgError::InvalidSetting { setting, .. }) => {
+                assert_eq!(setting, "data-algorithm-creator.model.privacy-key")
+            }
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+
+        let unknown_field = "data-algorithm-creator:\n  threads: 4\n";
+        assert!(serde_yaml::from_str::<Settings>(unknown_field).is_err());
+    }
+
+    #[test]
+    // ensure all the settings can be read from YAML
+    fn settings_from_yaml() {
+        let yaml = "data-algorithm-creator:\n  data-source:\n    csv-file:\n      path: ./tests/samples/sample-01.csv\n  processors: 2\n  model:\n    seed: 7\n    markov-order: 3\n    correlation: true\n    date-shift: -7\n    epoch-seconds: true\n    char-classes:\n      - symbol: h\n        chars: 0123456789abcdef\n      - symbol: G\n        regex: \\p{Greek}\n        priority: 1\n    novelty: 0.25\n    unique: [id]\n    null-sentinels: [NULL, \"-\"]\n    privacy-min-distance: 2\n    privacy-key: \"0x5eed\"\n    differential-privacy:\n      epsilon: 0.5\n      k-threshold: 10\ndata-generator:\n  output:\n    path: ./out.csv\n    rows: 5\n";
+        let settings: Settings = serde_yaml::from_str(yaml).unwrap();
+
+        assert!(settings.validate().is_ok());
+        assert_eq!(settings.data_algorithm_creator.processors, Some(2));
+        assert_eq!(settings.data_algorithm_creator.model.seed, Some(7));
+        assert_eq!(settings.data_algorithm_creator.model.markov_order, Some(3));
+        assert_eq!(
+            settings.data_algorithm_creator.model.correlation,
+            Some(true)
+        );
+        assert_eq!(settings.data_algorithm_creator.model.date_shift, Some(-7));
+        assert_eq!(
+            settings.data_algorithm_creator.model.epoch_seconds,
+            Some(true)
+        );
+        assert_eq!(settings.data_algorithm_creator.model.novelty, Some(0.25));
+        assert_eq!(
+            settings.data_algorithm_creator.model.unique,
+            Some(vec!["id".to_string()])
+        );
+        assert_eq!(
+            settings.data_algorithm_creator.model.null_sentinels,
+            Some(vec!["NULL".to_string(), "-".to_string()])
+        );
+        assert_eq!(
+            settings.data_algorithm_creator.model.privacy_min_distance,
+            Some(2)
+        );
+        assert_eq!(
+            settings.data_algorithm_creator.model.privacy_key,
+            Some("0x5eed".to_string())
+        );
+        assert_eq!(
+            settings.data_algorithm_creator.model.differential_privacy,
+            Some(DifferentialPrivacy::new(0.5, 10).unwrap())
+        );
+        assert_eq!(
+            settings.data_algorithm_creator.model.char_classes,
+            Some(vec![
+                CharClass::chars('h', "0123456789abcdef", 0).unwrap(),
+                CharClass::regex('G', r"\p{Greek}", 1).unwrap()
+            ])
+        );
+        assert_eq!(settings.data_generator.output.unwrap().rows, Some(5));
+    }
+
     #[test]
     fn new_fact_from_serialized() {
         let serialized = "{\"file\":\"./tests/config/tdg.yaml\"}";
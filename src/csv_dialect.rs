@@ -0,0 +1,412 @@
+//! The `csv_dialect` module provides the properties of the csv formatted data that is analyzed and generated
+//! (e.g.: the delimiter, the quote char, whether there is a header row), and a sniffer that guesses them from a sample of the data.
+//!
+//! When the data has no header row, the fields are named after their position (e.g.: `column_1`, `column_2`).
+//!
+//! # Examples
+//!
+//!
+//! Analyze a legacy extract that has no header row and uses `~` quoting ...
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::csv_dialect::CsvDialect;
+//! use test_data_generation::data_sample_parser::DataSampleParser;
+//!
+//! fn main() {
+//!     let data = "~Aaron~|~Aaberg~|32\n~Beth~|~Aaby~|41\n~Carla~|~Aadland~|27\n";
+//!
+//!     let dialect = CsvDialect::sniff(data.as_bytes(), None);
+//!     assert_eq!(dialect.delimiter, b'|');
+//!     assert_eq!(dialect.quote, b'~');
+//!     assert!(!dialect.has_headers);
+//!
+//!     // the dialect is sniffed from the data, since it isn't specified
+//!     let mut dsp = DataSampleParser::new();
+//!     dsp.analyze_csv_data(data, None).unwrap();
+//!     assert_eq!(dsp.extract_headers(), vec!["column_1", "column_2", "column_3"]);
+//! }
+//! ```
+
+use crate::engine::data_type::DataType;
+
+/// The maximum number of lines of the sample that are used to sniff the dialect
+pub const SNIFF_LINES: usize = 50;
+/// The delimiters that the sniffer can detect, (in order of preference)
+const DELIMITERS: [u8; 6] = [b',', b'\t', b';', b'|', b'~', b':'];
+/// The quote chars that the sniffer can detect, (in order of preference)
+const QUOTES: [u8; 3] = [b'"', b'\'', b'~'];
+/// The char that starts a comment line, (if the sniffer detects comments)
+const COMMENT: u8 = b'#';
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+/// Represents the properties of csv formatted data
+pub struct CsvDialect {
+    /// the char that separates the fields, (default comma)
+    pub delimiter: u8,
+    /// true if the first record is a header row, otherwise the fields are named after their position, (default true)
+    pub has_headers: bool,
+    /// the char that wraps the text of a field, (default double quote)
+    pub quote: u8,
+    /// the char that escapes a quote char in a quoted field, (default None, i.e.: quotes are escaped by doubling them)
+    pub escape: Option<u8>,
+    /// the char that starts a line that is ignored, (default None)
+    pub comment: Option<u8>,
+    /// true to remove the leading and trailing whitespace of the fields, (default false)
+    pub trim: bool,
+    /// true if the records can have a different number of fields, (default false)
+    pub flexible: bool,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        CsvDialect {
+            delimiter: b',',
+            has_headers: true,
+            quote: b'"',
+            escape: None,
+            comment: None,
+            trim: false,
+            flexible: false,
+        }
+    }
+}
+
+impl CsvDialect {
+    /// Constructs a new CsvDialect that uses the default properties, (i.e.: the csv properties of analyze_csv_data())
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::csv_dialect::CsvDialect;
+    ///
+    /// fn main() {
+    ///     let mut dialect = CsvDialect::new();
+    ///     dialect.has_headers = false;
+    ///     dialect.quote = b'~';
+    ///
+    ///     assert_eq!(dialect.delimiter, b',');
+    /// }
+    /// ```
+    pub fn new() -> CsvDialect {
+        CsvDialect::default()
+    }
+
+    /// This function guesses the dialect of csv formatted data from a sample of the data (e.g.: the first few kilobytes of a file).
+    /// The properties that can't be guessed have their default value.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample: &[u8]` - The first bytes of the csv formatted data.</br>
+    /// * `delimiter: Option<u8>` - The delimiter of the data, otherwise it is guessed too.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::csv_dialect::CsvDialect;
+    ///
+    /// fn main() {
+    ///     let dialect = CsvDialect::sniff(b"id;name\n1;Aaron\n2;Beth\n", None);
+    ///
+    ///     assert_eq!(dialect.delimiter, b';');
+    ///     assert!(dialect.has_headers);
+    /// }
+    /// ```
+    pub fn sniff(sample: &[u8], delimiter: Option<u8>) -> CsvDialect {
+        let text = String::from_utf8_lossy(sample);
+        let mut lines: Vec<&str> = text
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .take(SNIFF_LINES + 1)
+            .collect();
+        // the last line can be cut off at the end of the sample
+        if lines.len() > 1 && !text.ends_with('\n') {
+            lines.pop();
+        }
+        lines.truncate(SNIFF_LINES);
+
+        let mut dialect = CsvDialect::default();
+
+        // a header can start with the comment char, but records can't
+        if lines.iter().skip(1).any(|l| l.as_bytes()[0] == COMMENT) {
+            dialect.comment = Some(COMMENT);
+            lines.retain(|l| l.as_bytes()[0] != COMMENT);
+        }
+
+        let delimiters = match delimiter {
+            Some(d) => vec![d],
+            None => DELIMITERS.to_vec(),
+        };
+        match Self::sniff_quote(&lines, &delimiters) {
+            Some((quote, d)) => {
+                dialect.quote = quote;
+                dialect.delimiter = d;
+            }
+            None => {
+                if let Some(d) = Self::sniff_delimiter(&lines, &delimiters) {
+                    dialect.delimiter = d;
+                }
+            }
+        }
+
+        if text.contains(&format!("\\{}", dialect.quote as char)) {
+            dialect.escape = Some(b'\\');
+        }
+
+        let joined = lines.join("\n");
+        let records: Vec<csv::StringRecord> = dialect
+            .reader_builder()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(joined.as_bytes())
+            .records()
+            .map_while(Result::ok)
+            .collect();
+
+        if let Some(first) = records.first() {
+            dialect.flexible = records.iter().any(|r| r.len() != first.len());
+            dialect.trim = Self::sniff_trim(&records);
+            dialect.has_headers = Self::sniff_headers(first, &records[1..]);
+        }
+
+        dialect
+    }
+
+    /// This function returns a csv reader builder that reads data of the dialect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::csv_dialect::CsvDialect;
+    ///
+    /// fn main() {
+    ///     let mut dialect = CsvDialect::new();
+    ///     dialect.delimiter = b'|';
+    ///
+    ///     let mut rdr = dialect.reader_builder().from_reader("name|age\nAaron|32\n".as_bytes());
+    ///     assert_eq!(rdr.records().next().unwrap().unwrap().get(1), Some("32"));
+    /// }
+    /// ```
+    pub fn reader_builder(&self) -> csv::ReaderBuilder {
+        let mut builder = csv::ReaderBuilder::new();
+        builder
+            .has_headers(self.has_headers)
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .double_quote(self.escape.is_none())
+            .escape(self.escape)
+            .comment(self.comment)
+            .flexible(self.flexible)
+            .trim(match self.trim {
+                true => csv::Trim::All,
+                false => csv::Trim::None,
+            });
+        builder
+    }
+
+    /// This function returns a csv writer builder that writes data of the dialect.
+    /// _NOTE:_ The header row is written by the caller (if the dialect has headers).
+    pub fn writer_builder(&self) -> csv::WriterBuilder {
+        let mut builder = csv::WriterBuilder::new();
+        builder
+            .has_headers(self.has_headers)
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .double_quote(self.escape.is_none())
+            .escape(self.escape.unwrap_or(b'\\'))
+            .flexible(self.flexible);
+        builder
+    }
+
+    /// This function returns the name of a field of data that has no header row, (e.g.: `column_1` is the first field).
+    ///
+    /// # Arguments
+    ///
+    /// * `idx: usize` - The position of the field, starting at 0.</br>
+    pub fn column_name(idx: usize) -> String {
+        format!("column_{}", idx + 1)
+    }
+
+    /// This function returns the quote char and the delimiter that occur next to each other the most, (e.g.: `","`).
+    fn sniff_quote(lines: &[&str], delimiters: &[u8]) -> Option<(u8, u8)> {
+        let mut best = None;
+        let mut best_count = 0;
+
+        for quote in QUOTES.iter() {
+            for delimiter in delimiters.iter().filter(|d| *d != quote) {
+                let count: usize = lines
+                    .iter()
+                    .map(|l| Self::count_adjacent(l.as_bytes(), *quote, *delimiter))
+                    .sum();
+                if count > best_count {
+                    best_count = count;
+                    best = Some((*quote, *delimiter));
+                }
+            }
+        }
+
+        best
+    }
+
+    /// This function counts how many times the quote char and the delimiter are next to each other, (ignoring spaces in between).
+    fn count_adjacent(line: &[u8], quote: u8, delimiter: u8) -> usize {
+        let next_is = |start: usize, c: u8| {
+            line[start..]
+                .iter()
+                .find(|b| **b != b' ')
+                .is_some_and(|b| *b == c)
+        };
+
+        line.iter()
+            .enumerate()
+            .filter(|(idx, b)| {
+                (**b == delimiter && next_is(idx + 1, quote))
+                    || (**b == quote && next_is(idx + 1, delimiter))
+            })
+            .count()
+    }
+
+    /// This function returns the delimiter that occurs the same (non-zero) number of times in the most lines.
+    fn sniff_delimiter(lines: &[&str], delimiters: &[u8]) -> Option<u8> {
+        let mut best = None;
+        let mut best_lines = 0;
+
+        for delimiter in delimiters.iter() {
+            let mut frequencies = std::collections::BTreeMap::new();
+            for line in lines.iter() {
+                let count = line.bytes().filter(|b| b == delimiter).count();
+                if count > 0 {
+                    *frequencies.entry(count).or_insert(0) += 1;
+                }
+            }
+
+            if let Some(consistent) = frequencies.values().max() {
+                if *consistent > best_lines {
+                    best_lines = *consistent;
+                    best = Some(*delimiter);
+                }
+            }
+        }
+
+        best
+    }
+
+    /// This function returns true if at least half of the (non-empty) fields have leading or trailing whitespace.
+    fn sniff_trim(records: &[csv::StringRecord]) -> bool {
+        let (padded, total) = records
+            .iter()
+            .flat_map(|r| r.iter())
+            .filter(|f| !f.is_empty())
+            .fold((0, 0), |(padded, total), f| {
+                (padded + (f.trim() != f) as usize, total + 1)
+            });
+
+        total > 0 && padded * 2 >= total
+    }
+
+    /// This function returns true if the first record looks like a header row, (e.g.: a text field above a column of numbers).
+    /// Without any evidence, the first record is a header row.
+    fn sniff_headers(first: &csv::StringRecord, rest: &[csv::StringRecord]) -> bool {
+        let is_number = |v: &str| matches!(DataType::of(v), DataType::Integer | DataType::Decimal);
+        let mut votes = 0;
+
+        // fixed width fields are padded to the same length
+        for (idx, header) in first.iter().map(str::trim).enumerate() {
+            let values: Vec<&str> = rest
+                .iter()
+                .filter_map(|r| r.get(idx))
+                .map(str::trim)
+                .collect();
+            if values.is_empty() {
+                continue;
+            }
+
+            let length = values[0].chars().count();
+            if values.iter().all(|v| is_number(v)) {
+                votes += if is_number(header) { -1 } else { 1 };
+            } else if values.iter().all(|v| v.chars().count() == length) {
+                votes += if header.chars().count() == length {
+                    -1
+                } else {
+                    1
+                };
+            }
+            // a header isn't repeated in its column
+            if values.contains(&header) {
+                votes -= 1;
+            }
+        }
+
+        votes >= 0
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_default() {
+        let dialect = CsvDialect::sniff(b"firstname,lastname\nAaron,Aaberg\nAbbey,Aadland\n", None);
+        assert_eq!(dialect, CsvDialect::default());
+        assert_eq!(CsvDialect::sniff(b"", None), CsvDialect::default());
+    }
+
+    #[test]
+    fn test_sniff_quoted_tabs() {
+        let dialect = CsvDialect::sniff(b"'id'\t'name'\n'1'\t'O\\'Brian'\n'2'\t'Aaby'", None);
+
+        assert_eq!(dialect.delimiter, b'\t');
+        assert_eq!(dialect.quote, b'\'');
+        assert_eq!(dialect.escape, Some(b'\\'));
+        assert!(dialect.has_headers);
+    }
+
+    #[test]
+    fn test_sniff_comments_trim_flexible() {
+        let dialect = CsvDialect::sniff(
+            b"Aaron    |Aaberg   |32\n# exported 2019-01-01\nBeth     |Aaby     \nCarla    |Aadland  |27\n",
+            None,
+        );
+
+        assert_eq!(dialect.delimiter, b'|');
+        assert_eq!(dialect.comment, Some(b'#'));
+        assert!(dialect.trim);
+        assert!(dialect.flexible);
+        assert!(!dialect.has_headers);
+    }
+
+    #[test]
+    fn test_sniff_headers_repeated_value() {
+        let dialect = CsvDialect::sniff(b"OK;A\nOK;B\nOK;C\n", Some(b';'));
+        assert_eq!(dialect.delimiter, b';');
+        assert!(!dialect.has_headers);
+    }
+
+    #[test]
+    fn test_reader_writer_round_trip() {
+        let mut dialect = CsvDialect::new();
+        dialect.delimiter = b'|';
+        dialect.quote = b'~';
+        dialect.escape = Some(b'\\');
+
+        let mut wtr = dialect.writer_builder().from_writer(vec![]);
+        wtr.write_record(["a|b", "c~d"]).unwrap();
+        let data = wtr.into_inner().unwrap();
+        assert_eq!(String::from_utf8(data.clone()).unwrap(), "~a|b~|~c\\~d~\n");
+
+        dialect.has_headers = false;
+        let mut rdr = dialect.reader_builder().from_reader(data.as_slice());
+        let record = rdr.records().next().unwrap().unwrap();
+        assert_eq!(record.iter().collect::<Vec<&str>>(), vec!["a|b", "c~d"]);
+    }
+}
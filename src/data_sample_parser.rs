@@ -76,31 +76,54 @@
 //!
 
 // use std::collections::BTreeMap;
-use crate::configs::Configs;
-use crate::engine::{Engine, EngineContainer};
+use crate::configs::{
+    to_delimiter, to_privacy_key, Configs, DataSource, Settings, DEFAULT_FIELD, DEFAULT_ROWS,
+};
+use crate::csv_dialect::CsvDialect;
+use crate::engine::char_class::{self, CharClass};
+use crate::engine::correlation::CorrelationModel;
+use crate::engine::data_type::DataType;
+use crate::engine::json_shape::JsonShape;
+use crate::engine::missing::{MissingValues, JSON_NULL};
+use crate::engine::noise::DifferentialPrivacy;
+use crate::engine::{Engine, EngineContainer, PatternDefinition};
+use crate::errors::{TdgError, TdgResult};
 use crate::shared::CsvManipulator;
+use crate::sql::{SqlOptions, SqlStatement};
 use crate::Profile;
 use csv;
 use indexmap::IndexMap;
+use std::collections::HashSet;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
 use std::io::Write;
 use std::result::Result;
 //use csv::StringRecord;
-use csv::WriterBuilder;
 use serde_json;
 use serde_json::Value;
 use std::error::Error;
 
-use std::sync::mpsc;
-use std::sync::mpsc::{Receiver, Sender};
-use std::thread;
-
-const DELIMITER: u8 = b',';
+/// The number of records that are read into memory and analyzed at a time when streaming csv data
+pub const STREAM_CHUNK_ROWS: usize = 1000;
+/// The size (in bytes) of the buffer used to read csv data from a stream
+pub const STREAM_BUFFER_CAPACITY: usize = 64 * 1024;
 
 type ProfilesMap = IndexMap<String, Profile>;
 
+/// An iterator that lazily generates records of test data using a DataSampleParser, (see DataSampleParser::generate_records())
+pub struct GeneratedRecords<'a> {
+    dsp: &'a mut DataSampleParser,
+}
+
+impl Iterator for GeneratedRecords<'_> {
+    type Item = TdgResult<Vec<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.dsp.try_generate_record())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 /// Represents the Parser for sample data to be used
 pub struct DataSampleParser {
@@ -111,6 +134,57 @@ pub struct DataSampleParser {
     /// List of Profiles objects identified by a unique profile name LinkedHashMap<String, Profile>
     #[serde(with = "indexmap::map::serde_seq")]
     profiles: ProfilesMap,
+    /// The seed used to derive the seeds of the Profiles so that the generated data can be reproduced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    /// The order of the n-gram (Markov chain) model the Profiles use to generate data (if enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    markov_order: Option<u8>,
+    /// The joint model of the patterns of the fields that is used to generate records with combinations of patterns that occurred together (if enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    correlation: Option<CorrelationModel>,
+    /// The number of days the Profiles shift the range of the generated dates by (if set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date_shift: Option<i64>,
+    /// Indicates if the Profiles analyze numbers of 9 or 10 digits as epoch seconds, (see set_epoch_seconds())
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    epoch_seconds: bool,
+    /// The custom classes of chars that the Profiles analyze the values with, (see add_char_class())
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    char_classes: Vec<CharClass>,
+    /// The chance that the Profiles generate a value from a synthesized pattern, (see set_novelty())
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    novelty: Option<f64>,
+    /// The fields whose Profiles only generate unique values, (see set_unique())
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    unique: Vec<String>,
+    /// The tokens that represent a null value in the values the Profiles analyze, (see set_null_sentinels())
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    null_sentinels: Option<Vec<String>>,
+    /// The minimum Levenshtein distance between the values the Profiles generate and the analyzed values in privacy mode, (see set_privacy())
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    privacy: Option<usize>,
+    /// The secret key of the hashes of the privacy mode of the Profiles, (it isn't exported, see set_privacy_key())
+    #[serde(skip)]
+    privacy_key: Option<u128>,
+    /// The noise that is added to the statistics of the Profiles when the DataSampleParser is exported, (see set_export_privacy())
+    #[serde(skip)]
+    export_privacy: Option<DifferentialPrivacy>,
+    /// the properties of the analyzed and generated csv data, (see set_csv_dialect())
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    csv_dialect: Option<CsvDialect>,
+    /// the nested shape of the analyzed JSON records, (see analyze_json())
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    json_shape: Option<JsonShape>,
+    /// The random number generator that chooses the combination of patterns of a generated record
+    #[serde(skip)]
+    rng: Option<fastrand::Rng>,
+}
+
+impl Default for DataSampleParser {
+    fn default() -> Self {
+        DataSampleParser::new()
+    }
 }
 
 impl CsvManipulator for DataSampleParser {}
@@ -136,10 +210,27 @@ impl DataSampleParser {
             issues: false,
             cfg: None,
             profiles: ProfilesMap::new(),
+            seed: None,
+            markov_order: None,
+            correlation: None,
+            date_shift: None,
+            epoch_seconds: false,
+            char_classes: Vec::new(),
+            novelty: None,
+            unique: Vec::new(),
+            null_sentinels: None,
+            privacy: None,
+            privacy_key: None,
+            export_privacy: None,
+            csv_dialect: None,
+            json_shape: None,
+            rng: None,
         }
     }
 
-    /// Constructs a new DataSampleParser
+    /// Constructs a new DataSampleParser that uses the settings of a configuration file, (see try_new_with()).
+    /// If the configuration file couldn't be loaded or applied, the error is logged and the DataSampleParser is running with issues,
+    /// (see running_with_issues()).
     ///
     /// # Arguments
     ///
@@ -155,22 +246,34 @@ impl DataSampleParser {
     /// fn main() {
     ///        // initalize a new DataSampelParser
     ///        // param: the path to the configuration  file
-    ///        let dsp = DataSampleParser::new_with(&String::from("./config/tdg.yaml"));
+    ///        let mut dsp = DataSampleParser::new_with(&String::from("./tests/config/tdg.yaml"));
+    ///
+    ///        // the delimited-string data source of the configuration file has been analyzed
+    ///        assert_eq!(dsp.extract_headers(), vec!["value"]);
+    ///        assert_eq!(dsp.running_with_issues(), &false);
     /// }
     /// ```
+    #[allow(clippy::ptr_arg)]
     pub fn new_with(path: &String) -> DataSampleParser {
-        DataSampleParser {
-            issues: false,
-            cfg: Some(Configs::new(path)),
-            profiles: ProfilesMap::new(),
+        match Self::try_new_with(path) {
+            Ok(dsp) => dsp,
+            Err(e) => {
+                error!("Could not apply the configuration file {}: {}", path, e);
+                let mut dsp = DataSampleParser::new();
+                dsp.cfg = Some(Configs::new(path));
+                dsp.issues = true;
+                dsp
+            }
         }
     }
 
-    /// Constructs a new DataSampleParser from an exported JSON file. This is used when restoring from "archive"
+    /// Constructs a new DataSampleParser that uses the settings of a configuration file, returning an error
+    /// if the configuration file couldn't be loaded or its data source couldn't be analyzed.
+    /// The model options (seed, markov-order) and processors are applied to the Profiles, and the data source (if any) is analyzed.
     ///
     /// # Arguments
     ///
-    /// * `path: &String` - The full path name of the json formatted Data Sample Parser archive file.</br>
+    /// * `path: &str - The full path name (including the file name and extension) to the configuration file.</br>
     ///
     /// #Example
     ///
@@ -180,264 +283,172 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///        let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-00-dsp"));
+    ///        let mut dsp = DataSampleParser::try_new_with("./tests/config/tdg.yaml").unwrap();
     ///
-    ///        assert_eq!(dsp.generate_record()[0], "OK".to_string());
+    ///        // the delimited-string data source of the configuration file has been analyzed
+    ///        println!("Generated data: {}", dsp.generate_by_field_name("value".to_string()));
+    ///        assert!(DataSampleParser::try_new_with("./badpath/tdg.yaml").is_err());
     /// }
     /// ```
-    pub fn from_file(path: &String) -> DataSampleParser {
-        // open the archive file
-        let mut file = match File::open(format!("{}.json", &path)) {
-            Err(_e) => {
-                error!("Could not open file {:?}", &path.to_string());
-                panic!("Could not open file {:?}", &path.to_string());
-            }
-            Ok(f) => {
-                info!("Successfully opened file {:?}", &path.to_string());
-                f
-            }
-        };
+    pub fn try_new_with(path: &str) -> TdgResult<DataSampleParser> {
+        let mut cfg = Configs::new(&path.to_string());
+        cfg.try_load_config_file()?;
+        let settings = cfg.get_settings().cloned().unwrap_or_default();
 
-        //read the archive file
-        let mut serialized = String::new();
-        match file.read_to_string(&mut serialized) {
-            Err(e) => {
-                error!(
-                    "Could not read file {:?} because of {:?}",
-                    &path.to_string(),
-                    e.to_string()
-                );
-                panic!(
-                    "Could not read file {:?} because of {:?}",
-                    &path.to_string(),
-                    e.to_string()
-                );
-            }
-            Ok(s) => {
-                info!("Successfully read file {:?}", &path.to_string());
-                s
-            }
-        };
+        let mut dsp = DataSampleParser::new();
+        dsp.cfg = Some(cfg);
+        dsp.apply_settings(&settings)?;
 
-        // Support backwards compatibility for DSP saved using prior versions
-        let dsp: Value = serde_json::from_str(&serialized).unwrap();
-        let prfils = dsp.get("profiles").unwrap();
+        Ok(dsp)
+    }
 
-        match prfils.is_array() {
-            true => {
-                debug!("Version 0.3.0 detected. Using latest version");
-                serde_json::from_str(&serialized).unwrap()
-            }
-            false => {
-                info!("Prior version 0.2.1 detected. Trying to upgrade to latest version");
+    /// This function applies the settings of a configuration file and analyzes its data source (if any).
+    fn apply_settings(&mut self, settings: &Settings) -> TdgResult<()> {
+        let creator = &settings.data_algorithm_creator;
 
-                Self::upgrade_to_latest_version(serialized)
-            }
+        if let Some(seed) = creator.model.seed {
+            self.set_seed(seed);
         }
-    }
 
-    fn upgrade_to_latest_version(serialized: String) -> DataSampleParser {
-        let dsp: Value = serde_json::from_str(&serialized).unwrap();
-        let prfils = dsp.get("profiles").unwrap();
-        let mut pm: ProfilesMap = ProfilesMap::new();
-        let issues = dsp.get("issues").unwrap().as_bool().unwrap();
+        if let Some(order) = creator.model.markov_order {
+            self.set_markov_order(order)?;
+        }
 
-        for prf in prfils.as_object().iter() {
-            for attr in prf.keys() {
-                let id = prf
-                    .get(attr)
-                    .unwrap()
-                    .as_object()
-                    .unwrap()
-                    .get("id")
-                    .unwrap()
-                    .as_str()
-                    .unwrap()
-                    .to_string();
-                let serl = &serde_json::to_string(prf.get(attr).unwrap()).unwrap();
-                println!("{:?} : {:?}", id, serl);
-                pm.insert(id, Profile::from_serialized(serl));
-            }
+        if let Some(enabled) = creator.model.correlation {
+            self.set_correlation(enabled);
         }
 
-        let mut rtn = match dsp.get("cfg").unwrap() {
-            serde_json::Value::Null => DataSampleParser::new(),
-            _ => DataSampleParser::new_with(
-                &dsp.get("cfg")
-                    .unwrap()
-                    .as_object()
-                    .unwrap()
-                    .get("file")
-                    .unwrap()
-                    .as_str()
-                    .unwrap()
-                    .to_string(),
-            ),
-        };
+        if let Some(days) = creator.model.date_shift {
+            self.set_date_shift(days);
+        }
 
-        rtn.issues = issues;
-        rtn.profiles = pm;
-        rtn
-    }
+        if let Some(enabled) = creator.model.epoch_seconds {
+            self.set_epoch_seconds(enabled);
+        }
 
-    #[inline]
-    fn analyze_columns(&mut self, profile_keys: Vec<String>, columns: Vec<Vec<String>>) {
-        let col_cnt = columns.len();
-        let (tx, rx): (
-            Sender<Result<Profile, String>>,
-            Receiver<Result<Profile, String>>,
-        ) = mpsc::channel();
-        let mut jobs = Vec::new();
-
-        //iterate through all the columns
-        for (idx, column) in columns.iter().enumerate() {
-            let thread_tx = tx.clone();
-            let container = EngineContainer {
-                profile: self.profiles.get(&profile_keys[idx]).unwrap().clone(),
-                entities: column.to_vec(),
-            };
+        for class in creator.model.char_classes.iter().flatten() {
+            self.add_char_class(class.clone())?;
+        }
 
-            let job = thread::spawn(move || {
-                let result = Self::profile_entities_with_container(container);
-                thread_tx.send(result).unwrap();
-            });
+        if let Some(novelty) = creator.model.novelty {
+            self.set_novelty(novelty)?;
+        }
 
-            jobs.push(job);
+        for field in creator.model.unique.iter().flatten() {
+            self.set_unique(field, true);
         }
 
-        let mut results = Vec::with_capacity(col_cnt);
-        for _ in 0..col_cnt {
-            results.push(rx.recv());
+        if let Some(sentinels) = creator.model.null_sentinels.as_ref() {
+            self.set_null_sentinels(Some(sentinels.clone()));
         }
 
-        for job in jobs {
-            job.join().expect("Error: Could not run the job");
+        if let Some(distance) = creator.model.privacy_min_distance {
+            self.set_privacy(Some(distance));
         }
 
-        for result in results {
-            match result {
-                Ok(msg) => {
-                    //received from sender
-                    match msg {
-                        Ok(p) => {
-                            let id = p.clone().id.unwrap();
-                            debug!("Profile {} has finished analyzing the entities.", id);
-                            self.profiles.insert(id, p);
-                        }
-                        Err(e) => {
-                            error!(
-                                "Profile wasn't able to analyzing the entities. Error: {}",
-                                e
-                            );
-                        }
-                    }
-                }
-                Err(e) => {
-                    // could not receive from sender
-                    error!("Receiver wasn't able to receive message from sender which was analyzing entities for the profile. Error: {}", e);
-                    panic!("Receiver wasn't able to receive message from sender which was analyzing entities for the profile. Error: {}", e);
-                }
+        if let Some(key) = to_privacy_key("privacy-key", &creator.model.privacy_key)? {
+            self.set_privacy_key(key)?;
+        }
+
+        if let Some(dp) = creator.model.differential_privacy.as_ref() {
+            self.set_export_privacy(Some(dp.clone()))?;
+        }
+
+        match &creator.data_source {
+            Some(DataSource::DelimitedString {
+                field,
+                delimiter,
+                value,
+            }) => {
+                let delimiter = to_delimiter("delimiter", delimiter)?.map_or(',', |d| d as char);
+                let values = value.split(delimiter).map(|v| v.to_string()).collect();
+
+                self.analyze_field(field.as_deref().unwrap_or(DEFAULT_FIELD), values)
             }
+            Some(DataSource::CsvFile { path, delimiter }) => {
+                self.try_analyze_csv_file(path, to_delimiter("delimiter", delimiter)?)
+            }
+            None => Ok(()),
         }
-        // Multi-Threading END
     }
 
-    /// This function analyzes sample data that is a csv formatted string and returns a boolean if successful.
-    /// _NOTE:_ The csv properties are as follows:
-    ///       + headers are included as first line
-    ///       + double quote wrap text
-    ///       + double quote escapes is enabled
-    ///       + delimiter is a comma
-    ///
-    ///
-    /// # Arguments
-    ///
-    /// * `data: &String` - The textual content of a csv formatted sample data file.</br>
-    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the default.</br>
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// extern crate test_data_generation;
-    ///
-    /// use test_data_generation::data_sample_parser::DataSampleParser;
-    ///
-    /// fn main() {
-    ///        // initalize a new DataSampelParser
-    ///        let mut dsp = DataSampleParser::new();
-    ///        let mut data = String::from("");
-    ///        data.push_str("\"firstname\",\"lastname\"\n");
-    ///        data.push_str("\"Aaron\",\"Aaberg\"\n");
-    ///        data.push_str("\"Aaron\",\"Aaby\"\n");
-    ///        data.push_str("\"Abbey\",\"Aadland\"\n");
-    ///        data.push_str("\"Abbie\",\"Aagaard\"\n");
-    ///        data.push_str("\"Abby\",\"Aakre\"");
-    ///
-    ///     // Use the default delimiter (comma)
-    ///     assert_eq!(dsp.analyze_csv_data(&data, None).unwrap(),1);
-    /// }
-    /// ```
-    pub fn analyze_csv_data(
-        &mut self,
-        data: &String,
-        delimiter: Option<u8>,
-    ) -> Result<i32, String> {
-        debug!("Starting to analyzed the csv data {}", data);
-
-        let mut rdr = csv::ReaderBuilder::new()
-            .has_headers(true)
-            .quote(b'"')
-            .double_quote(true)
-            .delimiter(Self::else_default_delimiter(delimiter))
-            .from_reader(data.as_bytes());
+    /// This function returns the settings of the configuration file (if one has been loaded).
+    fn settings(&self) -> Option<&Settings> {
+        self.cfg.as_ref().and_then(|cfg| cfg.get_settings())
+    }
 
-        //iterate through the headers
-        for headers in rdr.headers() {
-            for header in headers.iter() {
-                //add a Profile to the list of profiles to represent the field (indexed using the header label)
-                let p = Profile::new_with_id(header.to_string());
-                self.profiles.insert(header.to_string(), p);
+    /// This function creates a new Profile for the field using the model options of the DataSampleParser.
+    fn new_profile(&self, field: &str) -> TdgResult<Profile> {
+        let mut profile = match self
+            .settings()
+            .and_then(|s| s.data_algorithm_creator.processors)
+        {
+            Some(processors) => {
+                let mut p = Profile::new_with_processors(processors);
+                p.id = Some(field.to_string());
+                p
             }
+            None => Profile::new_with_id(field.to_string()),
+        };
+
+        if let Some(order) = self.markov_order {
+            profile.set_markov_order(order)?;
+        }
+
+        if let Some(days) = self.date_shift {
+            profile.set_date_shift(days);
         }
 
-        //create a Vec from all the keys (headers) in the profiles list
-        let profile_keys: Vec<_> = self.profiles.keys().cloned().collect();
+        profile.set_epoch_seconds(self.epoch_seconds);
 
-        debug!("CSV headers: {:?}", profile_keys);
+        for class in self.char_classes.iter() {
+            profile.add_char_class(class.clone())?;
+        }
 
-        // Multi-Threading START
-        let columns = Self::read_as_columns(rdr);
-        //let col_cnt = columns.len();
-        let rec_cnt = columns[0].len();
-        self.analyze_columns(profile_keys, columns);
+        if let Some(novelty) = self.novelty {
+            profile.set_novelty(novelty)?;
+        }
 
-        debug!("Successfully analyzed the csv data");
-        debug!(
-            "Analyzed {} records, {} fields",
-            rec_cnt,
-            self.profiles.len()
-        );
+        if self.unique.iter().any(|f| f == field) {
+            profile.set_unique(true);
+        }
+
+        if self.null_sentinels.is_some() {
+            profile.set_null_sentinels(self.null_sentinels.clone());
+        }
+
+        if self.privacy.is_some() {
+            profile.set_privacy(self.privacy);
+
+            if let Some(key) = self.privacy_key {
+                profile.set_privacy_key(key)?;
+            }
+        }
+
+        Ok(profile)
+    }
+
+    /// This function analyzes a list of sample values for a single field.
+    fn analyze_field(&mut self, field: &str, values: Vec<String>) -> TdgResult<()> {
+        let profile = self.new_profile(field)?;
+        self.profiles.insert(field.to_string(), profile);
+        self.analyze_columns(vec![field.to_string()], vec![values])?;
 
         //prepare the profiles for data generation
         self.profiles.iter_mut().for_each(|p| p.1.pre_generate());
+        self.seed_profiles();
 
-        Ok(1)
+        Ok(())
     }
 
-    /// This function analyzes sample data that is a csv formatted file and returns a boolean if successful.
-    /// _NOTE:_ The csv properties are as follows:
-    ///       + headers are included as first line
-    ///       + double quote wrap text
-    ///       + double quote escapes is enabled
-    ///       + delimiter is a comma
-    ///
+    /// Constructs a new DataSampleParser that uses a seeded random number generator.
+    /// Two parsers with the same seed that have analyzed the same data sample will generate the same test data.
     ///
     /// # Arguments
     ///
-    /// * `path: &String` - The full path name of the csv formatted sample data file.</br>
-    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the default.</br>
+    /// * `seed: u64` - The seed for the random number generators of the Profiles.</br>
     ///
-    /// # Example
+    /// #Example
     ///
     /// ```
     /// extern crate test_data_generation;
@@ -445,39 +456,30 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///        // initalize a new DataSampelParser
-    ///        let mut dsp = DataSampleParser::new();
+    ///        let mut dsp_a = DataSampleParser::new_with_seed(1234);
+    ///        let mut dsp_b = DataSampleParser::new_with_seed(1234);
     ///
-    ///     // Use the default delimiter (comma)
-    ///     assert_eq!(dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap(),1);
+    ///     dsp_a.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     dsp_b.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///
+    ///        assert_eq!(dsp_a.generate_record(), dsp_b.generate_record());
     /// }
     /// ```
-    pub fn analyze_csv_file(
-        &mut self,
-        path: &String,
-        delimiter: Option<u8>,
-    ) -> Result<i32, String> {
-        info!("Starting to analyzed the csv file {}", path);
-
-        let mut file = (File::open(path).map_err(|e| {
-            error!("csv file {} couldn't be opened!", path);
-            e.to_string()
-        }))?;
-
-        let mut data = String::new();
-        file.read_to_string(&mut data)
-            .map_err(|e| {
-                error!("csv file {} couldn't be read!", path);
-                e.to_string()
-            })
-            .unwrap();
-
-        self.analyze_csv_data(&data, delimiter)
+    pub fn new_with_seed(seed: u64) -> DataSampleParser {
+        let mut dsp = DataSampleParser::new();
+        dsp.set_seed(seed);
+        dsp
     }
 
-    /// This function generates date as strings using the a `demo` profile
+    /// This function (re)seeds the random number generators of all the Profiles.
+    /// Each Profile is given its own seed that is derived from the specified seed and the position of the field,
+    /// so the generated data doesn't depend on the order in which the fields were analyzed.
     ///
-    /// # Example
+    /// # Arguments
+    ///
+    /// * `seed: u64` - The seed for the random number generators of the Profiles.</br>
+    ///
+    /// #Example
     ///
     /// ```
     /// extern crate test_data_generation;
@@ -485,47 +487,30 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///        // initalize a new DataSampelParser
-    ///        let dsp = DataSampleParser::new();
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
     ///
-    ///        // generate some test data using the demo functions
-    ///        println!("generate date:{}", dsp.demo_date());
+    ///     dsp.set_seed(1234);
+    ///     let first = dsp.generate_record();
+    ///
+    ///     dsp.set_seed(1234);
+    ///        assert_eq!(dsp.generate_record(), first);
     /// }
     /// ```
-    pub fn demo_date(&self) -> String {
-        let mut profil = Profile::new();
-
-        profil.analyze("01/04/2017");
-        profil.analyze("02/09/2017");
-        profil.analyze("03/13/2017");
-        profil.analyze("04/17/2017");
-        profil.analyze("05/22/2017");
-        profil.analyze("07/26/2017");
-        profil.analyze("08/30/2017");
-        profil.analyze("09/07/2017");
-        profil.analyze("10/11/2017");
-        profil.analyze("11/15/2017");
-        profil.analyze("12/21/2017");
-        profil.analyze("01/14/2016");
-        profil.analyze("02/19/2016");
-        profil.analyze("03/23/2016");
-        profil.analyze("04/27/2016");
-        profil.analyze("05/02/2016");
-        profil.analyze("07/16/2015");
-        profil.analyze("08/20/2015");
-        profil.analyze("09/17/2015");
-        profil.analyze("10/01/2014");
-        profil.analyze("11/25/2014");
-        profil.analyze("12/31/2018");
-
-        profil.pre_generate();
-        //profil.apply_facts("##p##p####".to_string())
-        profil.generate()
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+        self.seed_profiles();
     }
 
-    /// This function generates people's names as strings using the a `demo` profile
+    /// This function enables the n-gram (Markov chain) model of all the Profiles, (see Profile::set_markov_order()).
+    /// The Profiles of fields that are analyzed afterwards will also use the model.
+    /// Returns an InvalidSetting error if the order is higher than the order the Profiles analyzed their values with.
     ///
-    /// # Example
+    /// # Arguments
+    ///
+    /// * `order: u8` - The number of previous chars to use when choosing the next char (1 to 4).</br>
+    ///
+    /// #Example
     ///
     /// ```
     /// extern crate test_data_generation;
@@ -533,37 +518,38 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///        // initalize a new DataSampelParser
-    ///        let dsp = DataSampleParser::new();
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.set_markov_order(2).unwrap();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
     ///
-    ///        // generate some test data using the demo functions
-    ///        println!("generate date:{}", dsp.demo_person_name());
+    ///     println!("Generated data record: {:?}", dsp.generate_record());
     /// }
-    pub fn demo_person_name(&self) -> String {
-        let mut profil = Profile::new();
-
-        profil.analyze("Smith, John");
-        profil.analyze("O'Brien, Henny");
-        profil.analyze("Dale, Danny");
-        profil.analyze("Rickets, Ronnae");
-        profil.analyze("Richard, Richie");
-        profil.analyze("Roberts, Blake");
-        profil.analyze("Conways, Sephen");
+    /// ```
+    pub fn set_markov_order(&mut self, order: u8) -> TdgResult<()> {
+        for profile in self.profiles.values_mut() {
+            profile.set_markov_order(order)?;
+        }
 
-        profil.pre_generate();
-        profil.generate()
-    }
+        // validates the order even when there aren't any profiles yet
+        Profile::new().set_markov_order(order)?;
+        self.markov_order = Some(order);
 
-    fn else_default_delimiter(delimiter: Option<u8>) -> u8 {
-        match delimiter {
-            Some(d) => d,
-            None => DELIMITER,
-        }
+        Ok(())
     }
 
-    /// This function returns a vector of header names
+    /// This function enables (or disables) the joint model of the patterns of the fields.
+    /// When enabled, the combination of patterns (one per field) of every analyzed csv record is learned,
+    /// and each generated record uses a combination of patterns that occurred together in the data sample,
+    /// (e.g.: a city is generated with the pattern of zip code it was analyzed with), instead of generating each field independently.
+    /// Only the most frequent combinations are kept (see engine::correlation::MAX_COMBINATIONS), and the records of the other
+    /// combinations are generated field by field.
+    /// NOTE: Enable the model before analyzing, since the combinations can't be recovered from the Profiles. Disabling the model discards it.
     ///
-    /// # Example
+    /// # Arguments
+    ///
+    /// * `enabled: bool` - true to learn and use the combinations of patterns, false to generate each field independently.</br>
+    ///
+    /// #Example
     ///
     /// ```
     /// extern crate test_data_generation;
@@ -571,31 +557,38 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///        // initalize a new DataSampelParser
     ///        let mut dsp = DataSampleParser::new();
-    ///
+    ///     dsp.set_correlation(true);
     ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
-    ///     let headers = dsp.extract_headers();
     ///
-    ///        assert_eq!(headers.len(), 2);
+    ///     assert_eq!(dsp.get_correlation().unwrap().fields, vec!["firstname", "lastname"]);
+    ///     println!("Generated data record: {:?}", dsp.generate_record());
     /// }
-    pub fn extract_headers(&mut self) -> Vec<String> {
-        let mut headers = vec![];
-
-        for profile in self.profiles.iter_mut() {
-            headers.push(profile.0.to_string());
+    /// ```
+    pub fn set_correlation(&mut self, enabled: bool) {
+        match enabled {
+            true => {
+                if self.correlation.is_none() {
+                    self.correlation = Some(CorrelationModel::default());
+                }
+            }
+            false => self.correlation = None,
         }
+    }
 
-        headers
+    /// This function returns the joint model of the patterns of the fields (if enabled), (see set_correlation()).
+    pub fn get_correlation(&self) -> Option<&CorrelationModel> {
+        self.correlation.as_ref()
     }
 
-    /// This function generates test data for the specified field name.
+    /// This function shifts the range of the dates generated by all the Profiles by a number of days, (see Profile::set_date_shift()).
+    /// The Profiles of fields that are analyzed afterwards will also shift their dates.
     ///
     /// # Arguments
     ///
-    /// * `field: String` - The name of the field (e.g.: firstname) the represents the profile to use when generating the test data.</br>
+    /// * `days: i64` - The number of days to shift the range by, (negative to shift it to earlier dates).</br>
     ///
-    /// # Example
+    /// #Example
     ///
     /// ```
     /// extern crate test_data_generation;
@@ -603,24 +596,31 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///        // initalize a new DataSampelParser
     ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.set_date_shift(365);
+    ///     dsp.analyze_csv_data(&String::from("name,signup\nAaron,2019-03-14\nBeth,2019-06-30\n"), None).unwrap();
     ///
-    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
-    ///     println!("Generated data for first name {}",dsp.generate_by_field_name("firstname".to_string()));
+    ///     let record = dsp.generate_record();
+    ///     assert_eq!(&record[1][0..4], "2020");
     /// }
     /// ```
-    pub fn generate_by_field_name(&mut self, field: String) -> String {
-        self.profiles
-            .get_mut(&field)
-            .unwrap()
-            .generate()
-            .to_string()
+    pub fn set_date_shift(&mut self, days: i64) {
+        for profile in self.profiles.values_mut() {
+            profile.set_date_shift(days);
+        }
+
+        self.date_shift = Some(days);
     }
 
-    /// This function Vec of generates test data fields.
+    /// This function enables (or disables) the detection of epoch seconds of all the Profiles, (see Profile::set_epoch_seconds()).
+    /// The Profiles of fields that are analyzed afterwards will also detect epoch seconds.
+    /// NOTE: Enable the detection before analyzing, since the values that were already analyzed aren't analyzed again.
     ///
-    /// # Example
+    /// # Arguments
+    ///
+    /// * `enabled: bool` - true to detect numbers of 9 or 10 digits as epoch seconds, false to analyze them as numbers.</br>
+    ///
+    /// #Example
     ///
     /// ```
     /// extern crate test_data_generation;
@@ -628,97 +628,97 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///        // initalize a new DataSampelParser
     ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.set_epoch_seconds(true);
+    ///     dsp.analyze_csv_data("id,created\n1,1483488000\n2,1483574399\n", None).unwrap();
     ///
-    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
-    ///     println!("Generated data record: {:?}",dsp.generate_record());
+    ///     assert!(dsp.get_profile("created").unwrap().date_time.is_some());
     /// }
     /// ```
-    pub fn generate_record(&mut self) -> Vec<String> {
-        let mut record = Vec::new();
-
-        for profile in self.profiles.iter_mut() {
-            record.push(profile.1.generate().to_string());
+    pub fn set_epoch_seconds(&mut self, enabled: bool) {
+        for profile in self.profiles.values_mut() {
+            profile.set_epoch_seconds(enabled);
         }
 
-        record
+        self.epoch_seconds = enabled;
     }
 
-    /// This function creates a csv file of generated test data.
-    /// Prior to calling this funciton, you need to call the analyze_csv_file() function.
-    /// _NOTE:_ The csv properties are as follows:
-    ///       + headers are included as first line
-    ///       + double quotes wrap text
-    ///       + double quote escapes is enabled
-    ///       + delimiter is a comma
-    ///
+    /// This function adds a custom class of chars with its own pattern symbol to all the Profiles, (see Profile::add_char_class()).
+    /// The Profiles of fields that are analyzed afterwards will also use the class. Returns an InvalidSetting error if the class isn't valid.
     ///
     /// # Arguments
     ///
-    /// * `row_count: u32` - The number of rows to generate.</br>
-    /// * `path: &String` - The full path name where to save the csv file.</br>
-    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the default.</br>
+    /// * `class: CharClass` - The custom class to add, (it replaces the custom class with the same symbol).</br>
     ///
-    /// # Example
+    /// #Example
     ///
     /// ```
     /// extern crate test_data_generation;
     ///
     /// use test_data_generation::data_sample_parser::DataSampleParser;
+    /// use test_data_generation::engine::char_class::CharClass;
     ///
     /// fn main() {
-    ///        // initalize a new DataSampelParser
     ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.add_char_class(CharClass::regex('G', r"\p{Greek}", 0).unwrap()).unwrap();
+    ///     dsp.analyze_csv_data("word\nλόγος\nάλφα\n", None).unwrap();
     ///
-    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
-    ///     dsp.generate_csv(100, &String::from("./tests/samples/generated-01.csv"), None).unwrap();
+    ///     let record = dsp.generate_record();
+    ///     assert!(record[0].chars().all(|c| c >= 'Ά' && c <= 'ώ'));
     /// }
     /// ```
-    pub fn generate_csv(
-        &mut self,
-        row_count: u32,
-        path: &String,
-        delimiter: Option<u8>,
-    ) -> Result<(), Box<dyn Error>> {
-        info!("generating csv file {}", path);
-
-        let mut wtr = (WriterBuilder::new()
-            .has_headers(true)
-            .quote(b'"')
-            .double_quote(true)
-            .delimiter(Self::else_default_delimiter(delimiter))
-            .from_path(path)
-            .map_err(|e| {
-                error!("csv file {} couldn't be created!", path);
-                e.to_string()
-            }))?;
-
-        let headers = self.extract_headers();
-        wtr.write_record(&headers)?;
-
-        for _r in 0..row_count {
-            let mut record = Vec::new();
+    pub fn add_char_class(&mut self, class: CharClass) -> TdgResult<()> {
+        for profile in self.profiles.values_mut() {
+            profile.add_char_class(class.clone())?;
+        }
 
-            for profile in self.profiles.iter_mut() {
-                record.push(profile.1.generate());
-            }
+        char_class::add_class(&mut self.char_classes, class)
+    }
 
-            wtr.write_record(&record)?;
+    /// This function sets the novelty of the data generated by all the Profiles, (see Profile::set_novelty()).
+    /// The Profiles of fields that are analyzed afterwards will also use the novelty. Returns an InvalidSetting error if the novelty isn't between 0.0 and 1.0.
+    /// NOTE: Records that are generated from the joint model of the patterns (see set_correlation()) only use the analyzed combinations of patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `novelty: f64` - The chance (0.0 to 1.0) that a value is generated from a synthesized pattern.</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.set_novelty(0.3).unwrap();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///
+    ///     println!("Generated data record: {:?}", dsp.generate_record());
+    ///     assert!(dsp.set_novelty(2.0).is_err());
+    /// }
+    /// ```
+    pub fn set_novelty(&mut self, novelty: f64) -> TdgResult<()> {
+        for profile in self.profiles.values_mut() {
+            profile.set_novelty(novelty)?;
         }
 
-        wtr.flush()?;
+        // validates the novelty even when there aren't any profiles yet
+        Profile::new().set_novelty(novelty)?;
+        self.novelty = Some(novelty);
 
         Ok(())
     }
 
-    /// This function calculates the levenshtein distance between 2 strings.
-    /// See: https://crates.io/crates/levenshtein
+    /// This function enables (or disables) the uniqueness mode of the Profile of a field, (e.g.: for primary keys and usernames),
+    /// so that the field only has values that weren't already generated, (see Profile::set_unique()).
+    /// If the field hasn't been analyzed yet, its Profile uses the uniqueness mode once it is analyzed.
     ///
     /// # Arguments
     ///
-    /// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
-    /// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the distance.</br>
+    /// * `field: &str` - The name of the field (e.g.: id).</br>
+    /// * `enabled: bool` - true to only generate values that weren't already generated, false to allow repeated values.</br>
     ///
     /// #Example
     ///
@@ -728,23 +728,34 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///     // analyze the dataset
-    ///        let mut dsp =  DataSampleParser::new();
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.set_unique("id", true);
+    ///     dsp.analyze_csv_data("id,name\n11,Aaron\n22,Beth\n", None).unwrap();
     ///
-    ///     assert_eq!(dsp.levenshtein_distance(&"kitten".to_string(), &"sitting".to_string()), 3 as usize);
+    ///     // there are only 2 ids, (11 and 22)
+    ///     assert_eq!(dsp.get_profile("id").unwrap().capacity(), 2.0);
+    ///     assert!(dsp.try_generate_csv_writer(2, Vec::new(), None).is_ok());
+    ///     assert!(dsp.try_generate_csv_writer(1, Vec::new(), None).is_err());
     /// }
-    ///
-    pub fn levenshtein_distance(&mut self, control: &str, experiment: &str) -> usize {
-        // https://docs.rs/levenshtein/1.0.3/levenshtein/fn.levenshtein.html
-        levenshtein_distance!(control, experiment)
+    /// ```
+    pub fn set_unique(&mut self, field: &str, enabled: bool) {
+        if let Some(profile) = self.profiles.get_mut(field) {
+            profile.set_unique(enabled);
+        }
+
+        self.unique.retain(|f| f != field);
+        if enabled {
+            self.unique.push(field.to_string());
+        }
     }
 
-    /// This function calculates the percent difference between 2 strings.
+    /// This function sets the tokens that represent a null value in the values of all the Profiles, (see Profile::set_null_sentinels()).
+    /// The Profiles of fields that are analyzed afterwards will also use the tokens.
+    /// NOTE: Set the tokens before analyzing, since the values that were already analyzed aren't analyzed again.
     ///
     /// # Arguments
     ///
-    /// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
-    /// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the percent difference.</br>
+    /// * `sentinels: Option<Vec<String>>` - The tokens that represent a null value, or None for the default tokens (see engine::missing::NULL_SENTINELS).</br>
     ///
     /// #Example
     ///
@@ -754,23 +765,30 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///     // analyze the dataset
-    ///        let mut dsp =  DataSampleParser::new();
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.set_null_sentinels(Some(vec!["-".to_string(), "NULL".to_string()]));
+    ///     dsp.analyze_csv_data("surname\nNone\n-\nNil\nNULL\n", None).unwrap();
     ///
-    ///     assert_eq!(dsp.realistic_test(&"kitten".to_string(), &"sitting".to_string()), 76.92307692307692 as f64);
+    ///     assert_eq!(dsp.get_profile("surname").unwrap().missing_rate(), 0.5);
     /// }
-    ///
-    pub fn realistic_test(&mut self, control: &String, experiment: &String) -> f64 {
-        //https://docs.rs/GSL/0.4.31/rgsl/statistics/fn.correlation.html
-        //http://www.statisticshowto.com/probability-and-statistics/correlation-coefficient-formula/
-        // pearson's chi square test
-        // cosine similarity - http://blog.christianperone.com/2013/09/machine-learning-cosine-similarity-for-vector-space-models-part-iii/
-        realistic_test!(control, experiment)
+    /// ```
+    pub fn set_null_sentinels(&mut self, sentinels: Option<Vec<String>>) {
+        for profile in self.profiles.values_mut() {
+            profile.set_null_sentinels(sentinels.clone());
+        }
+
+        self.null_sentinels = sentinels;
     }
 
-    /// This function returns a boolean that indicates if the data sample parsing had issues
+    /// This function enables (or disables) the no-verbatim privacy mode of the Profiles of the fields that are analyzed afterwards,
+    /// (see Profile::set_privacy()), so that the generated data doesn't contain the analyzed values, (e.g.: for samples of personal data).
+    /// NOTE: Enable the mode before analyzing, since the values that were already analyzed can't be recovered from the Profiles.
     ///
-    /// # Example
+    /// # Arguments
+    ///
+    /// * `min_distance: Option<usize>` - The minimum Levenshtein distance from every analyzed value, (0 to only reject the analyzed values), or None to disable the mode.</br>
+    ///
+    /// #Example
     ///
     /// ```
     /// extern crate test_data_generation;
@@ -778,27 +796,41 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///        // initalize a new DataSampelParser
-    ///        // param: the path to the configuration file is wrong
-    ///        let dsp = DataSampleParser::new_with(&String::from("./target/debug/config/tdg.yaml"));
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.set_privacy(Some(1));
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
     ///
-    ///        // generate some test data using the demo functions
-    ///        assert_eq!(dsp.running_with_issues(), &false);
+    ///     assert!(dsp.get_profile("lastname").unwrap().privacy.is_some());
+    ///     println!("Generated data record: {:?}", dsp.generate_record());
     /// }
-    pub fn running_with_issues(&self) -> &bool {
-        &self.issues
+    /// ```
+    pub fn set_privacy(&mut self, min_distance: Option<usize>) {
+        // the profiles that were already analyzed would keep the hashes of no values
+        if min_distance.is_none() {
+            for profile in self.profiles.values_mut() {
+                profile.set_privacy(None);
+            }
+        }
+
+        self.privacy = min_distance;
     }
 
-    /// This function saves (exports) the DataSampleParser to a JSON file.
-    /// This is useful when you wish to reuse the algorithm to generate more test data later.
+    /// This function returns the secret key of the hashes of the privacy mode of the Profiles, or None if it hasn't been supplied,
+    /// (see set_privacy_key()). Without a supplied key, each Profile in privacy mode hashes its values with its own random key,
+    /// so an exported DataSampleParser can't generate their values again.
+    pub fn privacy_key(&self) -> Option<u128> {
+        self.privacy_key
+    }
+
+    /// This function supplies the secret key of the hashes of the privacy mode (see set_privacy()) of the Profiles, so that the
+    /// Profiles of the fields that are analyzed afterwards use it, and the Profiles of a restored DataSampleParser accept it again,
+    /// (the key isn't exported, and a Profile in privacy mode doesn't generate values until its key is supplied).
+    /// Returns an InvalidSetting error if a Profile analyzed its values with a different key.
+    /// NOTE: Keep the key outside of the exported DataSampleParser, (e.g.: in a secret store).
     ///
     /// # Arguments
     ///
-    /// * `field: &String` - The full path of the export file , excluding the file extension, (e.g.: "./test/data/custom-names").</br>
-    ///
-    /// #Errors
-    /// If this function encounters any form of I/O or other error, an error variant will be returned.
-    /// Otherwise, the function returns Ok(true).</br>
+    /// * `key: u128` - The secret key of the hashes of the analyzed values.</br>
     ///
     /// #Example
     ///
@@ -808,294 +840,3339 @@ impl DataSampleParser {
     /// use test_data_generation::data_sample_parser::DataSampleParser;
     ///
     /// fn main() {
-    ///     // analyze the dataset
-    ///        let mut dsp =  DataSampleParser::new();
-    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-00.csv"), None).unwrap();
+    ///     let mut dsp = DataSampleParser::new();
+    ///     dsp.set_privacy(Some(0));
+    ///     dsp.set_privacy_key(0x5eed).unwrap();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
     ///
-    ///     assert_eq!(dsp.save(&String::from("./tests/samples/sample-00-dsp")).unwrap(), true);
+    ///     assert_eq!(dsp.get_profile("lastname").unwrap().privacy_key(), Some(0x5eed));
     /// }
-    ///
-    pub fn save(&mut self, path: &String) -> Result<bool, io::Error> {
-        let dsp_json = serde_json::to_string(&self).unwrap();
-
-        // Create the archive file
-        let mut file = match File::create(format!("{}.json", &path)) {
-            Err(e) => {
-                error!("Could not create file {:?}", &path.to_string());
-                return Err(e);
-            }
-            Ok(f) => {
-                info!("Successfully exported to {:?}", &path.to_string());
-                f
+    /// ```
+    pub fn set_privacy_key(&mut self, key: u128) -> TdgResult<()> {
+        for profile in self.profiles.values_mut() {
+            if profile.privacy.is_some() {
+                profile.set_privacy_key(key)?;
             }
-        };
+        }
 
-        // Write the json string to file, returns io::Result<()>
-        match file.write_all(dsp_json.as_bytes()) {
-            Err(e) => {
-                error!("Could not write to file {}", &path.to_string());
-                return Err(e);
-            }
-            Ok(_) => {
-                info!("Successfully exported to {}", &path.to_string());
-            }
-        };
+        self.privacy_key = Some(key);
+        Ok(())
+    }
+
+    /// This function enables (or disables) the differentially private export mode, so that the DataSampleParser is exported
+    /// (see try_save()) with noise added to the statistics of its Profiles, (see privatize()), with (epsilon, delta)-differential
+    /// privacy, (see engine::noise). The DataSampleParser itself keeps generating data from the analyzed statistics.
+    /// Returns an InvalidSetting error if epsilon isn't a positive number.
+    ///
+    /// # Arguments
+    ///
+    /// * `dp: Option<DifferentialPrivacy>` - The privacy budget and the k-threshold, or None to export the analyzed statistics.</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    /// use test_data_generation::engine::noise::DifferentialPrivacy;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     dsp.set_export_privacy(Some(DifferentialPrivacy::new(1.0, 5).unwrap())).unwrap();
+    ///
+    ///     assert!(dsp.try_save("./tests/samples/sample-01-private-dsp").is_ok());
+    /// }
+    /// ```
+    pub fn set_export_privacy(&mut self, dp: Option<DifferentialPrivacy>) -> TdgResult<()> {
+        if let Some(dp) = dp.as_ref() {
+            dp.validate()?;
+        }
 
-        Ok(true)
+        self.export_privacy = dp;
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs::File;
-    use std::io::BufReader;
+    /// This function returns a copy of the DataSampleParser whose Profiles are privatized (see Profile::privatize()), and whose
+    /// combinations of correlated patterns are released with noise, (the combinations below the k-threshold, or with a pattern
+    /// that was suppressed, are suppressed as well). Since a record changes the statistics of every field (and the combinations),
+    /// the budget is split equally between the Profiles and the correlation model, so that the values of a record are protected
+    /// by the whole budget.
+    /// Returns an InvalidSetting error if epsilon isn't a positive number.
+    ///
+    /// # Arguments
+    ///
+    /// * `dp: &DifferentialPrivacy` - The privacy budget and the k-threshold, (see engine::noise::DifferentialPrivacy).</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    /// use test_data_generation::engine::noise::DifferentialPrivacy;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///
+    ///     let mut shared = dsp.privatize(&DifferentialPrivacy::new(1.0, 5).unwrap()).unwrap();
+    ///     assert_eq!(shared.extract_headers(), dsp.extract_headers());
+    /// }
+    /// ```
+    pub fn privatize(&self, dp: &DifferentialPrivacy) -> TdgResult<DataSampleParser> {
+        dp.validate()?;
 
-    #[test]
-    // ensure a new Data Sample Parser can be created
-    fn test_new() {
-        let _dsp = DataSampleParser::new();
+        // the share of the budget of each Profile and of the correlation model
+        let shares = self.profiles.len() + self.correlation.is_some() as usize;
+        let dp = &DifferentialPrivacy {
+            epsilon: dp.epsilon / shares.max(1) as f64,
+            k_threshold: dp.k_threshold,
+        };
 
-        assert!(true);
-    }
+        let mut profiles = ProfilesMap::new();
+        for (field, profile) in self.profiles.iter() {
+            profiles.insert(field.clone(), profile.privatize(dp)?);
+        }
 
-    #[test]
-    // ensure a new Data Sample Parser can be created with configurations
-    fn test_new_with() {
-        let _dsp = DataSampleParser::new_with(&String::from("./config/tdg.yaml"));
+        let mut rng = fastrand::Rng::new();
+        let correlation = self.correlation.as_ref().map(|model| CorrelationModel {
+            fields: model.fields.clone(),
+            combinations: dp
+                .release(model.combinations.clone(), 1, dp.k_threshold, &mut rng)
+                .into_iter()
+                .filter(|(patterns, _)| {
+                    // a missing value has no pattern, (see Profile::generate_missing())
+                    model.fields.iter().zip(patterns).all(|(field, pattern)| {
+                        pattern.is_empty()
+                            || profiles
+                                .get(field)
+                                .is_some_and(|p| p.patterns.contains_key(pattern))
+                    })
+                })
+                .collect(),
+            uncorrelated: dp.noisy_count(model.uncorrelated, 1, &mut rng),
+        });
 
-        assert!(true);
+        Ok(DataSampleParser {
+            issues: self.issues,
+            cfg: self.cfg.clone(),
+            profiles,
+            seed: self.seed,
+            markov_order: self.markov_order,
+            correlation,
+            date_shift: self.date_shift,
+            epoch_seconds: self.epoch_seconds,
+            char_classes: self.char_classes.clone(),
+            novelty: self.novelty,
+            unique: self.unique.clone(),
+            null_sentinels: self.null_sentinels.clone(),
+            privacy: self.privacy,
+            privacy_key: None,
+            export_privacy: None,
+            csv_dialect: self.csv_dialect.clone(),
+            json_shape: self.json_shape.clone(),
+            rng: None,
+        })
     }
 
-    #[test]
-    // ensure the Data Sample Parser can be restored from archived file
-    fn test_from_file() {
-        let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-00-dsp"));
-        println!("Sample data is [{:?}]", dsp.generate_record()[0]);
+    /// This function checks that the Profiles of the fields in uniqueness mode can generate a number of records,
+    /// (based on the estimated capacity of their value space, the values they have already generated and their rate of missing values),
+    /// returning a UniqueExhausted error for the first field that can't, and that the key of the Profiles in privacy mode has been
+    /// supplied (see set_privacy_key()), returning an InvalidSetting error if it hasn't. It is called before the data is generated
+    /// (and before the header is written) by the functions that generate a number of records (e.g.: try_generate_csv_writer()).
+    ///
+    /// # Arguments
+    ///
+    /// * `row_count: u32` - The number of records to generate.</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_csv_data("id,name\n11,Aaron\n22,Beth\n", None).unwrap();
+    ///     dsp.set_unique("id", true);
+    ///
+    ///     assert!(dsp.check_capacity(2).is_ok());
+    ///     assert!(dsp.check_capacity(3).is_err());
+    /// }
+    /// ```
+    pub fn check_capacity(&self, row_count: u32) -> TdgResult<()> {
+        for profile in self.profiles.values() {
+            profile.check_privacy_key()?;
 
-        assert_eq!(dsp.generate_record()[0], "OK".to_string());
-    }
+            let generated = match profile.unique.as_ref() {
+                Some(unique) => unique.len(),
+                None => continue,
+            };
+            let capacity = profile.capacity();
+            let required = (row_count as f64 * (1.0 - profile.missing_rate())).floor();
 
-    #[test]
-    // ensure the Data Sample Parser can be restored from archived file that
-    // was saved using version 0.2.1 using a configuration
-    fn test_from_file_v021_with_cfg() {
-        let mut dsp =
-            DataSampleParser::from_file(&String::from("./tests/samples/sample-0.2.1-dsp"));
-        println!("Sample data is [{:?}]", dsp.generate_record()[0]);
+            if capacity - (generated as f64) < required {
+                return Err(TdgError::UniqueExhausted {
+                    field: profile.id.clone(),
+                    generated,
+                    capacity,
+                });
+            }
+        }
 
-        assert_eq!(dsp.generate_record()[0], "OK".to_string());
+        Ok(())
     }
 
-    #[test]
-    // ensure the Data Sample Parser can be restored from archived file that
+    /// This function sets the properties of the csv data that is analyzed and generated (e.g.: no header row, `~` quoting).
+    /// If the dialect isn't set, the properties of the analyzed data are sniffed from the data (see CsvDialect::sniff()),
+    /// and the generated data uses the default properties. The delimiter argument of the csv functions overrides the delimiter of the dialect.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect: Option<CsvDialect>` - The properties of the csv data, or None to sniff them.</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::csv_dialect::CsvDialect;
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     let mut dialect = CsvDialect::new();
+    ///     dialect.has_headers = false;
+    ///     dialect.delimiter = b'|';
+    ///     dsp.set_csv_dialect(Some(dialect));
+    ///
+    ///     dsp.analyze_csv_data("Aaron|Aaberg\nBeth|Aaby\n", None).unwrap();
+    ///     assert_eq!(dsp.extract_headers(), vec!["column_1", "column_2"]);
+    /// }
+    /// ```
+    pub fn set_csv_dialect(&mut self, dialect: Option<CsvDialect>) {
+        self.csv_dialect = dialect;
+    }
+
+    /// This function returns the properties of the csv data (if set), (see set_csv_dialect()).
+    pub fn get_csv_dialect(&self) -> Option<&CsvDialect> {
+        self.csv_dialect.as_ref()
+    }
+
+    /// This function derives a seed for a field based on its position (splitmix64), so that each field has its own sequence.
+    #[inline]
+    fn derive_seed(seed: u64, idx: usize) -> u64 {
+        let mut z = seed.wrapping_add((idx as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// This function seeds the Profiles using the seed of the DataSampleParser (if any)
+    fn seed_profiles(&mut self) {
+        if let Some(seed) = self.seed {
+            self.rng = Some(fastrand::Rng::with_seed(seed));
+            for (idx, profile) in self.profiles.values_mut().enumerate() {
+                profile.set_seed(Self::derive_seed(seed, idx));
+            }
+        }
+    }
+
+    /// Constructs a new DataSampleParser from an exported JSON file. This is used when restoring from "archive"
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The full path name of the json formatted Data Sample Parser archive file.</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-00-dsp"));
+    ///
+    ///        assert_eq!(dsp.generate_record()[0], "OK".to_string());
+    /// }
+    /// ```
+    pub fn from_file(path: &str) -> DataSampleParser {
+        Self::try_from_file(path).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Constructs a new DataSampleParser from an exported JSON file, returning an error instead of panicking
+    /// if the file can't be opened, read or parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The full path name of the json formatted Data Sample Parser archive file.</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        assert!(DataSampleParser::try_from_file("./tests/samples/sample-00-dsp").is_ok());
+    ///        assert!(DataSampleParser::try_from_file("./tests/samples/bad-path").is_err());
+    /// }
+    /// ```
+    pub fn try_from_file(path: &str) -> TdgResult<DataSampleParser> {
+        let file_name = format!("{}.json", &path);
+
+        // open the archive file
+        let mut file = match File::open(&file_name) {
+            Err(e) => {
+                error!("Could not open file {:?}", &path.to_string());
+                return Err(TdgError::FileOpen {
+                    path: path.to_string(),
+                    source: e,
+                });
+            }
+            Ok(f) => {
+                info!("Successfully opened file {:?}", &path.to_string());
+                f
+            }
+        };
+
+        //read the archive file
+        let mut serialized = String::new();
+        match file.read_to_string(&mut serialized) {
+            Err(e) => {
+                error!(
+                    "Could not read file {:?} because of {:?}",
+                    &path.to_string(),
+                    e.to_string()
+                );
+                return Err(TdgError::FileRead {
+                    path: path.to_string(),
+                    source: e,
+                });
+            }
+            Ok(s) => {
+                info!("Successfully read file {:?}", &path.to_string());
+                s
+            }
+        };
+
+        Self::try_from_serialized(&serialized).map_err(|e| match e {
+            TdgError::Json { source, .. } => TdgError::Json {
+                path: Some(file_name),
+                source,
+            },
+            other => other,
+        })
+    }
+
+    /// Constructs a new DataSampleParser from a serialized (JSON) string of the DataSampleParser object.
+    /// Archives saved using prior versions (0.2.1) are upgraded to the latest version.
+    ///
+    /// # Arguments
+    ///
+    /// * `serialized: &str` - The JSON string that represents the archived DataSampleParser object.</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        assert!(DataSampleParser::try_from_serialized("{\"issues\":false}").is_err());
+    /// }
+    /// ```
+    pub fn try_from_serialized(serialized: &str) -> TdgResult<DataSampleParser> {
+        // Support backwards compatibility for DSP saved using prior versions
+        let dsp: Value = serde_json::from_str(serialized)?;
+        let prfils = dsp.get("profiles").ok_or_else(|| TdgError::MissingField {
+            field: "profiles".to_string(),
+        })?;
+
+        match prfils.is_array() {
+            true => {
+                debug!("Version 0.3.0 detected. Using latest version");
+                Ok(serde_json::from_value(dsp)?)
+            }
+            false => {
+                info!("Prior version 0.2.1 detected. Trying to upgrade to latest version");
+
+                Self::try_upgrade_to_latest_version(dsp)
+            }
+        }
+    }
+
+    fn try_upgrade_to_latest_version(dsp: Value) -> TdgResult<DataSampleParser> {
+        let missing = |field: &str| TdgError::MissingField {
+            field: field.to_string(),
+        };
+        let prfils = dsp.get("profiles").ok_or_else(|| missing("profiles"))?;
+        let mut pm: ProfilesMap = ProfilesMap::new();
+        let issues = dsp
+            .get("issues")
+            .and_then(|i| i.as_bool())
+            .ok_or_else(|| missing("issues"))?;
+
+        for prf in prfils.as_object().iter() {
+            for (attr, value) in prf.iter() {
+                let id = value
+                    .get("id")
+                    .and_then(|id| id.as_str())
+                    .ok_or_else(|| missing(&format!("profiles.{}.id", attr)))?
+                    .to_string();
+                let profile: Profile = serde_json::from_value(value.clone())?;
+                debug!("Upgraded profile {:?}", id);
+                pm.insert(id, profile);
+            }
+        }
+
+        let mut rtn = match dsp.get("cfg") {
+            None | Some(serde_json::Value::Null) => DataSampleParser::new(),
+            // the configuration file was applied when the archive was created, so it isn't loaded again
+            Some(cfg) => {
+                let mut rtn = DataSampleParser::new();
+                rtn.cfg = Some(Configs::new(
+                    &cfg.get("file")
+                        .and_then(|f| f.as_str())
+                        .ok_or_else(|| missing("cfg.file"))?
+                        .to_string(),
+                ));
+                rtn
+            }
+        };
+
+        rtn.issues = issues;
+        rtn.profiles = pm;
+        Ok(rtn)
+    }
+
+    // the columns are analyzed one after another, and the entities of each column are analyzed by a pool of as many workers
+    // as the number of processors of its Profile, so the number of threads is bounded regardless of the number of rows and columns
+    #[inline]
+    fn analyze_columns(
+        &mut self,
+        profile_keys: Vec<String>,
+        columns: Vec<Vec<String>>,
+    ) -> TdgResult<()> {
+        for (key, column) in profile_keys.iter().zip(columns) {
+            // the Profile is moved out of the map while it analyzes the column, (instead of copying its statistics for every chunk)
+            let container = EngineContainer {
+                profile: std::mem::take(self.profiles.get_mut(key).unwrap()),
+                entities: column,
+            };
+
+            match Self::profile_entities_with_container(container) {
+                Ok(p) => {
+                    debug!("Profile {} has finished analyzing the entities.", key);
+                    self.profiles.insert(key.clone(), p);
+                }
+                Err(e) => {
+                    error!(
+                        "Profile wasn't able to analyzing the entities. Error: {}",
+                        e
+                    );
+                    return Err(TdgError::Analyze { message: e });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// This function analyzes sample data that is a csv formatted string and returns a boolean if successful.
+    /// _NOTE:_ The csv properties are the dialect of set_csv_dialect(), otherwise they are sniffed from the data, (see CsvDialect::sniff()).
+    ///       The default csv properties are as follows:
+    ///       + headers are included as first line
+    ///       + double quote wrap text
+    ///       + double quote escapes is enabled
+    ///       + delimiter is a comma
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `data: &str` - The textual content of a csv formatted sample data file.</br>
+    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the delimiter of the dialect.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        // initalize a new DataSampelParser
+    ///        let mut dsp = DataSampleParser::new();
+    ///        let mut data = String::from("");
+    ///        data.push_str("\"firstname\",\"lastname\"\n");
+    ///        data.push_str("\"Aaron\",\"Aaberg\"\n");
+    ///        data.push_str("\"Aaron\",\"Aaby\"\n");
+    ///        data.push_str("\"Abbey\",\"Aadland\"\n");
+    ///        data.push_str("\"Abbie\",\"Aagaard\"\n");
+    ///        data.push_str("\"Abby\",\"Aakre\"");
+    ///
+    ///     // Use the default delimiter (comma)
+    ///     assert_eq!(dsp.analyze_csv_data(&data, None).unwrap(),1);
+    /// }
+    /// ```
+    pub fn analyze_csv_data(&mut self, data: &str, delimiter: Option<u8>) -> Result<i32, String> {
+        self.try_analyze_csv_data(data, delimiter)
+            .map(|_| 1)
+            .map_err(|e| e.to_string())
+    }
+
+    /// This function analyzes sample data that is a csv formatted string, returning an error if the data couldn't be read or analyzed.
+    ///
+    /// # Arguments
+    ///
+    /// * `data: &str` - The textual content of a csv formatted sample data file.</br>
+    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the delimiter of the dialect.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     assert!(dsp.try_analyze_csv_data("firstname,lastname\nAaron,Aaberg\n", None).is_ok());
+    /// }
+    /// ```
+    pub fn try_analyze_csv_data(&mut self, data: &str, delimiter: Option<u8>) -> TdgResult<()> {
+        debug!("Starting to analyzed the csv data {}", data);
+
+        self.try_analyze_csv_reader(data.as_bytes(), delimiter)
+    }
+
+    /// This function analyzes csv formatted sample data that is streamed from a reader (e.g.: a file, stdin, a socket),
+    /// returning an error if the data couldn't be read or analyzed.
+    /// The records are read and analyzed in chunks of `STREAM_CHUNK_ROWS`, so the memory used doesn't depend on the size of the data.
+    /// _NOTE:_ The csv properties are the same as analyze_csv_data().
+    ///
+    /// # Arguments
+    ///
+    /// * `reader: R` - The reader of the csv formatted sample data.</br>
+    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the delimiter of the dialect.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use std::fs::File;
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///        let file = File::open("./tests/samples/sample-names.csv").unwrap();
+    ///
+    ///     dsp.try_analyze_csv_reader(file, None).unwrap();
+    ///     println!("Generated data record: {:?}", dsp.generate_record());
+    /// }
+    /// ```
+    pub fn try_analyze_csv_reader<R: io::Read>(
+        &mut self,
+        reader: R,
+        delimiter: Option<u8>,
+    ) -> TdgResult<()> {
+        let mut reader = io::BufReader::with_capacity(STREAM_BUFFER_CAPACITY, reader);
+        let dialect = match self.csv_dialect.clone() {
+            Some(mut dialect) => {
+                if let Some(d) = delimiter {
+                    dialect.delimiter = d;
+                }
+                dialect
+            }
+            // sniff the dialect from the first chunk of the data, (without consuming it)
+            None => CsvDialect::sniff(reader.fill_buf().map_err(csv::Error::from)?, delimiter),
+        };
+        debug!("CSV dialect: {:?}", dialect);
+
+        let mut rdr = dialect
+            .reader_builder()
+            .buffer_capacity(STREAM_BUFFER_CAPACITY)
+            .from_reader(reader);
+
+        // without a header row, the fields are named after their position
+        let headers: Vec<String> = match dialect.has_headers {
+            true => rdr.headers()?.iter().map(String::from).collect(),
+            false => (0..rdr.headers()?.len())
+                .map(CsvDialect::column_name)
+                .collect(),
+        };
+
+        //iterate through the headers
+        let mut profile_keys = Vec::new();
+        for header in headers {
+            //add a Profile to the list of profiles to represent the field (indexed using the header label)
+            let p = self.new_profile(&header)?;
+            self.profiles.insert(header.clone(), p);
+            profile_keys.push(header);
+        }
+
+        debug!("CSV headers: {:?}", profile_keys);
+
+        // the combinations of patterns can only be learned for records that have the same fields as the model
+        let correlate = match self.correlation.as_mut() {
+            Some(model) => {
+                if model.fields.is_empty() {
+                    model.fields = profile_keys.clone();
+                }
+                if model.fields != profile_keys {
+                    warn!(
+                        "The csv headers {:?} don't match the fields {:?} of the correlation model. The combinations of patterns won't be learned.",
+                        profile_keys, model.fields
+                    );
+                }
+                model.fields == profile_keys
+            }
+            None => false,
+        };
+        // the patterns of each field are symbolized with the custom classes of chars of its Profile
+        let pttrn_defs: Vec<PatternDefinition> = profile_keys
+            .iter()
+            .map(|key| PatternDefinition::with_classes(&self.profiles[key].char_classes))
+            .collect();
+        let sentinels: Vec<Vec<String>> = profile_keys
+            .iter()
+            .map(|key| self.profiles[key].get_null_sentinels())
+            .collect();
+
+        // read the records in chunks and fold each chunk into the profiles
+        let mut record = csv::StringRecord::new();
+        let mut columns = Self::new_columns(profile_keys.len());
+        let mut rec_cnt = 0;
+
+        while rdr.read_record(&mut record)? {
+            for (column, field) in columns.iter_mut().zip(record.iter()) {
+                column.push(field.to_string());
+            }
+            if let (true, Some(model)) = (correlate, self.correlation.as_mut()) {
+                model.learn(
+                    record
+                        .iter()
+                        .zip(pttrn_defs.iter().zip(sentinels.iter()))
+                        .map(|(f, (pttrn_def, sentinels))| {
+                            match MissingValues::is_missing_in(f, sentinels) {
+                                // a missing value has no pattern, (see Profile::generate_missing())
+                                true => String::new(),
+                                false => pttrn_def.symbolize(f),
+                            }
+                        })
+                        .collect(),
+                );
+            }
+            rec_cnt += 1;
+
+            if rec_cnt % STREAM_CHUNK_ROWS == 0 {
+                let chunk = std::mem::replace(&mut columns, Self::new_columns(profile_keys.len()));
+                self.analyze_columns(profile_keys.clone(), chunk)?;
+                debug!("Analyzed {} records ...", rec_cnt);
+            }
+        }
+
+        if rec_cnt % STREAM_CHUNK_ROWS != 0 {
+            self.analyze_columns(profile_keys, columns)?;
+        }
+
+        debug!("Successfully analyzed the csv data");
+        debug!(
+            "Analyzed {} records, {} fields",
+            rec_cnt,
+            self.profiles.len()
+        );
+
+        //prepare the profiles for data generation
+        self.profiles.iter_mut().for_each(|p| p.1.pre_generate());
+        self.seed_profiles();
+
+        Ok(())
+    }
+
+    /// This function returns a list of empty columns that can hold a chunk of records
+    fn new_columns(count: usize) -> Vec<Vec<String>> {
+        (0..count)
+            .map(|_| Vec::with_capacity(STREAM_CHUNK_ROWS))
+            .collect()
+    }
+
+    /// This function analyzes sample data that is a csv formatted file and returns a boolean if successful.
+    /// _NOTE:_ The csv properties are the dialect of set_csv_dialect(), otherwise they are sniffed from the data, (see CsvDialect::sniff()).
+    ///       The default csv properties are as follows:
+    ///       + headers are included as first line
+    ///       + double quote wrap text
+    ///       + double quote escapes is enabled
+    ///       + delimiter is a comma
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The full path name of the csv formatted sample data file.</br>
+    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the delimiter of the dialect.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        // initalize a new DataSampelParser
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     // Use the default delimiter (comma)
+    ///     assert_eq!(dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap(),1);
+    /// }
+    /// ```
+    pub fn analyze_csv_file(&mut self, path: &str, delimiter: Option<u8>) -> Result<i32, String> {
+        self.try_analyze_csv_file(path, delimiter)
+            .map(|_| 1)
+            .map_err(|e| e.to_string())
+    }
+
+    /// This function analyzes sample data that is a csv formatted file, returning an error if the file couldn't be read or analyzed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The full path name of the csv formatted sample data file.</br>
+    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the delimiter of the dialect.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     assert!(dsp.try_analyze_csv_file("./tests/samples/sample-01.csv", None).is_ok());
+    ///     assert!(dsp.try_analyze_csv_file("./badpath/sample-01.csv", None).is_err());
+    /// }
+    /// ```
+    pub fn try_analyze_csv_file(&mut self, path: &str, delimiter: Option<u8>) -> TdgResult<()> {
+        info!("Starting to analyzed the csv file {}", path);
+
+        let file = File::open(path).map_err(|e| {
+            error!("csv file {} couldn't be opened!", path);
+            TdgError::FileOpen {
+                path: path.to_string(),
+                source: e,
+            }
+        })?;
+
+        // stream the file, so that large files don't have to fit in memory
+        self.try_analyze_csv_reader(file, delimiter)
+            .map_err(|e| match e {
+                TdgError::Csv { source, .. } => TdgError::Csv {
+                    path: Some(path.to_string()),
+                    source,
+                },
+                other => other,
+            })
+    }
+
+    /// This function analyzes sample data that is a JSON formatted string and returns a boolean if successful.
+    /// The data is either an array of JSON objects or a single JSON object, (one object per record).
+    /// Nested objects are flattened into dotted field paths (e.g.: `user.address.city`), and the elements of an array share the path of the array
+    /// followed by `[]` (e.g.: `items[].sku`), so that there is one Profile per path.
+    /// The nested shape of the records (including the lengths of the arrays) is kept, so that generated JSON records have the same shape,
+    /// (see try_generate_json_record()).
+    ///
+    /// # Arguments
+    ///
+    /// * `data: &str` - The textual content of a JSON formatted sample data file.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///        let data = r#"[{"user": {"name": "Aaron", "age": 32}, "tags": ["new"]},
+    ///                       {"user": {"name": "Beth", "age": 41}, "tags": []}]"#;
+    ///
+    ///     assert_eq!(dsp.analyze_json(data).unwrap(), 1);
+    ///     assert_eq!(dsp.extract_headers(), vec!["user.name", "user.age", "tags[]"]);
+    /// }
+    /// ```
+    pub fn analyze_json(&mut self, data: &str) -> Result<i32, String> {
+        self.try_analyze_json(data)
+            .map(|_| 1)
+            .map_err(|e| e.to_string())
+    }
+
+    /// This function analyzes sample data that is a JSON formatted string, returning an error if the data couldn't be parsed or analyzed,
+    /// (see analyze_json()).
+    ///
+    /// # Arguments
+    ///
+    /// * `data: &str` - The textual content of a JSON formatted sample data file.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     assert!(dsp.try_analyze_json(r#"{"order": {"id": 12}}"#).is_ok());
+    ///     assert!(dsp.try_analyze_json(r#"[1, 2, 3]"#).is_err());
+    /// }
+    /// ```
+    pub fn try_analyze_json(&mut self, data: &str) -> TdgResult<()> {
+        debug!("Starting to analyzed the json data {}", data);
+
+        self.try_analyze_json_reader(data.as_bytes())
+    }
+
+    /// This function analyzes JSON formatted sample data that is read from a reader (e.g.: a file, stdin),
+    /// returning an error if the data couldn't be parsed or analyzed, (see analyze_json()).
+    ///
+    /// # Arguments
+    ///
+    /// * `reader: R` - The reader of the JSON formatted sample data.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use std::fs::File;
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///        let file = File::open("./tests/samples/sample-events.json").unwrap();
+    ///
+    ///     dsp.try_analyze_json_reader(file).unwrap();
+    ///     println!("Generated data record: {}", dsp.try_generate_json_record(true).unwrap());
+    /// }
+    /// ```
+    pub fn try_analyze_json_reader<R: io::Read>(&mut self, reader: R) -> TdgResult<()> {
+        let records = match serde_json::from_reader(io::BufReader::new(reader))? {
+            Value::Array(records) => records,
+            record => vec![record],
+        };
+
+        self.try_analyze_json_records(records.into_iter().map(Ok))
+    }
+
+    /// This function analyzes sample data that is a newline-delimited JSON (NDJSON) formatted string and returns a boolean if successful.
+    /// Each line is a JSON object, (one object per record). The objects are analyzed the same way as analyze_json().
+    ///
+    /// # Arguments
+    ///
+    /// * `data: &str` - The textual content of a NDJSON formatted sample data file.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///        let data = "{\"event\": \"login\", \"device\": {\"os\": \"linux\"}}\n{\"event\": \"logout\", \"device\": {\"os\": \"macos\"}}\n";
+    ///
+    ///     assert_eq!(dsp.analyze_ndjson(data).unwrap(), 1);
+    ///     assert!(dsp.get_profile("device.os").is_some());
+    /// }
+    /// ```
+    pub fn analyze_ndjson(&mut self, data: &str) -> Result<i32, String> {
+        self.try_analyze_ndjson(data)
+            .map(|_| 1)
+            .map_err(|e| e.to_string())
+    }
+
+    /// This function analyzes sample data that is a NDJSON formatted string, returning an error if the data couldn't be parsed or analyzed,
+    /// (see analyze_ndjson()).
+    ///
+    /// # Arguments
+    ///
+    /// * `data: &str` - The textual content of a NDJSON formatted sample data file.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     assert!(dsp.try_analyze_ndjson("{\"id\": 1}\n{\"id\": 2}\n").is_ok());
+    ///     assert!(dsp.try_analyze_ndjson("{\"id\": 1}\n{\"id\": \n").is_err());
+    /// }
+    /// ```
+    pub fn try_analyze_ndjson(&mut self, data: &str) -> TdgResult<()> {
+        debug!("Starting to analyzed the ndjson data {}", data);
+
+        self.try_analyze_ndjson_reader(data.as_bytes())
+    }
+
+    /// This function analyzes NDJSON formatted sample data that is streamed from a reader (e.g.: a file, stdin, a socket),
+    /// returning an error if the data couldn't be parsed or analyzed.
+    /// The records are analyzed in chunks of `STREAM_CHUNK_ROWS`, so the memory used doesn't depend on the size of the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader: R` - The reader of the NDJSON formatted sample data.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use std::fs::File;
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///        let file = File::open("./tests/samples/sample-events.ndjson").unwrap();
+    ///
+    ///     dsp.try_analyze_ndjson_reader(file).unwrap();
+    ///     println!("Generated data record: {}", dsp.try_generate_json_record(true).unwrap());
+    /// }
+    /// ```
+    pub fn try_analyze_ndjson_reader<R: io::Read>(&mut self, reader: R) -> TdgResult<()> {
+        let records = serde_json::Deserializer::from_reader(io::BufReader::new(reader))
+            .into_iter::<Value>()
+            .map(|record| record.map_err(TdgError::from));
+
+        self.try_analyze_json_records(records)
+    }
+
+    /// This function analyzes JSON records, (see analyze_json()).
+    fn try_analyze_json_records<I: Iterator<Item = TdgResult<Value>>>(
+        &mut self,
+        records: I,
+    ) -> TdgResult<()> {
+        let mut shape = JsonShape::Value;
+        // the paths that have a Profile for this analysis
+        let mut analyzed = HashSet::new();
+        let mut columns: IndexMap<String, Vec<String>> = IndexMap::new();
+        let mut rec_cnt = 0;
+
+        for record in records {
+            let record = record?;
+            if !record.is_object() {
+                return Err(TdgError::Analyze {
+                    message: format!("The JSON record {} isn't an object", rec_cnt + 1),
+                });
+            }
+
+            shape.learn("", &record, &mut |path, value| {
+                columns.entry(path).or_default().push(value)
+            });
+            rec_cnt += 1;
+
+            if rec_cnt % STREAM_CHUNK_ROWS == 0 {
+                self.analyze_paths(&mut analyzed, std::mem::take(&mut columns))?;
+                debug!("Analyzed {} records ...", rec_cnt);
+            }
+        }
+
+        if !columns.is_empty() {
+            self.analyze_paths(&mut analyzed, columns)?;
+        }
+
+        debug!("Successfully analyzed the json data");
+        debug!(
+            "Analyzed {} records, {} fields",
+            rec_cnt,
+            self.profiles.len()
+        );
+
+        if shape != JsonShape::Value {
+            self.json_shape = Some(shape);
+        }
+
+        //prepare the profiles for data generation
+        self.profiles.iter_mut().for_each(|p| p.1.pre_generate());
+        self.seed_profiles();
+
+        Ok(())
+    }
+
+    /// This function analyzes a chunk of the values of the paths of JSON records, adding a Profile for the paths that haven't been analyzed yet.
+    fn analyze_paths(
+        &mut self,
+        analyzed: &mut HashSet<String>,
+        columns: IndexMap<String, Vec<String>>,
+    ) -> TdgResult<()> {
+        for path in columns.keys() {
+            if analyzed.insert(path.clone()) {
+                let p = self.new_profile(path)?;
+                self.profiles.insert(path.clone(), p);
+            }
+        }
+
+        let (profile_keys, columns) = columns.into_iter().unzip();
+        self.analyze_columns(profile_keys, columns)
+    }
+
+    /// This function generates date as strings using the a `demo` profile
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        // initalize a new DataSampelParser
+    ///        let dsp = DataSampleParser::new();
+    ///
+    ///        // generate some test data using the demo functions
+    ///        println!("generate date:{}", dsp.demo_date());
+    /// }
+    /// ```
+    pub fn demo_date(&self) -> String {
+        let mut profil = self.new_demo_profile();
+
+        profil.analyze("01/04/2017");
+        profil.analyze("02/09/2017");
+        profil.analyze("03/13/2017");
+        profil.analyze("04/17/2017");
+        profil.analyze("05/22/2017");
+        profil.analyze("07/26/2017");
+        profil.analyze("08/30/2017");
+        profil.analyze("09/07/2017");
+        profil.analyze("10/11/2017");
+        profil.analyze("11/15/2017");
+        profil.analyze("12/21/2017");
+        profil.analyze("01/14/2016");
+        profil.analyze("02/19/2016");
+        profil.analyze("03/23/2016");
+        profil.analyze("04/27/2016");
+        profil.analyze("05/02/2016");
+        profil.analyze("07/16/2015");
+        profil.analyze("08/20/2015");
+        profil.analyze("09/17/2015");
+        profil.analyze("10/01/2014");
+        profil.analyze("11/25/2014");
+        profil.analyze("12/31/2018");
+
+        profil.pre_generate();
+        //profil.apply_facts("##p##p####".to_string())
+        profil.generate()
+    }
+
+    /// This function generates people's names as strings using the a `demo` profile
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        // initalize a new DataSampelParser
+    ///        let dsp = DataSampleParser::new();
+    ///
+    ///        // generate some test data using the demo functions
+    ///        println!("generate date:{}", dsp.demo_person_name());
+    /// }
+    pub fn demo_person_name(&self) -> String {
+        let mut profil = self.new_demo_profile();
+
+        profil.analyze("Smith, John");
+        profil.analyze("O'Brien, Henny");
+        profil.analyze("Dale, Danny");
+        profil.analyze("Rickets, Ronnae");
+        profil.analyze("Richard, Richie");
+        profil.analyze("Roberts, Blake");
+        profil.analyze("Conways, Sephen");
+
+        profil.pre_generate();
+        profil.generate()
+    }
+
+    fn new_demo_profile(&self) -> Profile {
+        match self.seed {
+            Some(seed) => Profile::new_with_seed(seed),
+            None => Profile::new(),
+        }
+    }
+
+    /// This function returns a vector of header names
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        // initalize a new DataSampelParser
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     let headers = dsp.extract_headers();
+    ///
+    ///        assert_eq!(headers.len(), 2);
+    /// }
+    pub fn extract_headers(&mut self) -> Vec<String> {
+        let mut headers = vec![];
+
+        for profile in self.profiles.iter_mut() {
+            headers.push(profile.0.to_string());
+        }
+
+        headers
+    }
+
+    /// This function returns the Profile for the specified field name, (e.g.: to inspect what has been analyzed).
+    ///
+    /// # Arguments
+    ///
+    /// * `field: &str` - The name of the field (e.g.: firstname) the represents the profile.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     println!("The first name patterns are {:?}", dsp.get_profile("firstname").unwrap().patterns);
+    ///     assert!(dsp.get_profile("middlename").is_none());
+    /// }
+    /// ```
+    pub fn get_profile(&self, field: &str) -> Option<&Profile> {
+        self.profiles.get(field)
+    }
+
+    /// This function generates test data for the specified field name.
+    ///
+    /// # Arguments
+    ///
+    /// * `field: String` - The name of the field (e.g.: firstname) the represents the profile to use when generating the test data.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        // initalize a new DataSampelParser
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     println!("Generated data for first name {}",dsp.generate_by_field_name("firstname".to_string()));
+    /// }
+    /// ```
+    pub fn generate_by_field_name(&mut self, field: String) -> String {
+        self.try_generate_by_field_name(&field)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// This function generates test data for the specified field name, returning an error if there is no profile for the field
+    /// or the profile couldn't generate the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `field: &str` - The name of the field (e.g.: firstname) the represents the profile to use when generating the test data.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     assert!(dsp.try_generate_by_field_name("firstname").is_ok());
+    ///     assert!(dsp.try_generate_by_field_name("middlename").is_err());
+    /// }
+    /// ```
+    pub fn try_generate_by_field_name(&mut self, field: &str) -> TdgResult<String> {
+        match self.profiles.get_mut(field) {
+            Some(profile) => profile.try_generate(),
+            None => Err(TdgError::ProfileNotFound {
+                field: field.to_string(),
+            }),
+        }
+    }
+
+    /// This function Vec of generates test data fields.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        // initalize a new DataSampelParser
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     println!("Generated data record: {:?}",dsp.generate_record());
+    /// }
+    /// ```
+    pub fn generate_record(&mut self) -> Vec<String> {
+        self.try_generate_record()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// This function generates a Vec of test data fields, returning an error if one of the profiles couldn't generate the data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     assert_eq!(dsp.try_generate_record().unwrap().len(), 2);
+    /// }
+    /// ```
+    pub fn try_generate_record(&mut self) -> TdgResult<Vec<String>> {
+        let mut record = Vec::new();
+
+        // choose the combination of patterns of the record, (if the fields are correlated)
+        let combination = match self.correlation.as_ref() {
+            Some(model) => model
+                .sample(Self::rng(&mut self.rng, self.seed))
+                .map(|c| c.to_vec())
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        for (field, profile) in self.profiles.iter_mut() {
+            let pattern = self
+                .correlation
+                .as_ref()
+                .and_then(|model| model.position(field))
+                .and_then(|idx| combination.get(idx));
+
+            record.push(match pattern {
+                Some(pattern) if pattern.is_empty() => profile.generate_missing(),
+                // date and numeric fields are generated from their models, (their patterns don't ensure valid values)
+                Some(pattern) => profile.try_accepted(|p| match p.generate_typed() {
+                    Some(value) => Ok(value),
+                    None => p.try_generate_from_pattern(pattern.clone()),
+                })?,
+                None => profile.try_generate()?,
+            });
+        }
+
+        Ok(record)
+    }
+
+    /// This function generates a record as a JSON object, returning an error if one of the profiles couldn't generate the data.
+    /// If JSON records were analyzed, the object has the nested shape of the analyzed records (see analyze_json()),
+    /// otherwise it is keyed by the headers, in the same order as the headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `typed: bool` - true to generate the values of numeric and boolean fields as JSON numbers and booleans, otherwise all values are strings.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_json(r#"[{"user": {"name": "Aaron", "age": 32}, "tags": ["a", "b"]}]"#).unwrap();
+    ///
+    ///     let record = dsp.try_generate_json_record(true).unwrap();
+    ///     assert!(record["user"]["age"].is_number());
+    ///     assert_eq!(record["tags"].as_array().unwrap().len(), 2);
+    /// }
+    /// ```
+    pub fn try_generate_json_record(&mut self, typed: bool) -> TdgResult<Value> {
+        let shape = match self.json_shape.as_ref() {
+            Some(shape) => shape,
+            None => {
+                let headers = self.extract_headers();
+                let data_types = self.data_types(typed);
+                let sentinels = self.null_sentinels();
+                let record = self.try_generate_record()?;
+
+                return Ok(Value::Object(
+                    headers
+                        .into_iter()
+                        .zip(
+                            record
+                                .into_iter()
+                                .zip(data_types.into_iter().zip(sentinels)),
+                        )
+                        .map(|(header, (value, (data_type, sentinels)))| {
+                            (
+                                header,
+                                DataType::typed_value_in(data_type, value, &sentinels),
+                            )
+                        })
+                        .collect(),
+                ));
+            }
+        };
+        let profiles = &mut self.profiles;
+
+        shape.generate(
+            "",
+            Self::rng(&mut self.rng, self.seed),
+            &mut |path| match profiles.get_mut(path) {
+                Some(profile) => {
+                    let data_type = if typed { profile.data_type } else { None };
+                    let value = profile.try_generate()?;
+                    Ok(DataType::typed_value_in(
+                        data_type,
+                        value,
+                        &profile.get_null_sentinels(),
+                    ))
+                }
+                None => Ok(Value::Null),
+            },
+        )
+    }
+
+    /// This function returns the random number generator of the records, (created from the seed the first time it is used).
+    fn rng(rng: &mut Option<fastrand::Rng>, seed: Option<u64>) -> &mut fastrand::Rng {
+        rng.get_or_insert_with(|| match seed {
+            Some(s) => fastrand::Rng::with_seed(s),
+            None => fastrand::Rng::new(),
+        })
+    }
+
+    /// This function creates a csv file of generated test data.
+    /// Prior to calling this funciton, you need to call the analyze_csv_file() function.
+    /// _NOTE:_ The csv properties are the dialect of set_csv_dialect(), otherwise they are as follows:
+    ///       + headers are included as first line
+    ///       + double quotes wrap text
+    ///       + double quote escapes is enabled
+    ///       + delimiter is a comma
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `row_count: u32` - The number of rows to generate.</br>
+    /// * `path: &str` - The full path name where to save the csv file.</br>
+    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the delimiter of the dialect.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        // initalize a new DataSampelParser
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     dsp.generate_csv(100, &String::from("./tests/samples/generated-01.csv"), None).unwrap();
+    /// }
+    /// ```
+    pub fn generate_csv(
+        &mut self,
+        row_count: u32,
+        path: &str,
+        delimiter: Option<u8>,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(self.try_generate_csv(row_count, path, delimiter)?)
+    }
+
+    /// This function creates a csv file of generated test data, returning an error if the file couldn't be written
+    /// or the data couldn't be generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `row_count: u32` - The number of rows to generate.</br>
+    /// * `path: &str` - The full path name where to save the csv file.</br>
+    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the delimiter of the dialect.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     assert!(dsp.try_generate_csv(100, "./badpath/generated-01.csv", None).is_err());
+    /// }
+    /// ```
+    pub fn try_generate_csv(
+        &mut self,
+        row_count: u32,
+        path: &str,
+        delimiter: Option<u8>,
+    ) -> TdgResult<()> {
+        info!("generating csv file {}", path);
+
+        let file = Self::try_create_file(path)?;
+
+        self.try_generate_csv_writer(row_count, file, delimiter)
+            .map_err(|e| match e {
+                TdgError::Csv { source, .. } => TdgError::Csv {
+                    path: Some(path.to_string()),
+                    source,
+                },
+                other => other,
+            })
+    }
+
+    /// This function writes csv formatted generated test data to a writer (e.g.: stdout, an in-memory buffer, a pipe),
+    /// returning an error if the data couldn't be generated or written.
+    /// _NOTE:_ The csv properties are the same as generate_csv().
+    ///
+    /// # Arguments
+    ///
+    /// * `row_count: u32` - The number of rows to generate.</br>
+    /// * `writer: W` - The writer of the csv formatted test data.</br>
+    /// * `delimiter: Option<u8>` - The delimiter to use, otherwise use the delimiter of the dialect.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///
+    ///     let mut buffer = Vec::new();
+    ///     dsp.try_generate_csv_writer(10, &mut buffer, None).unwrap();
+    ///
+    ///     let generated = String::from_utf8(buffer).unwrap();
+    ///     assert_eq!(generated.lines().count(), 11);
+    /// }
+    /// ```
+    pub fn try_generate_csv_writer<W: io::Write>(
+        &mut self,
+        row_count: u32,
+        writer: W,
+        delimiter: Option<u8>,
+    ) -> TdgResult<()> {
+        self.check_capacity(row_count)?;
+
+        let mut dialect = self.csv_dialect.clone().unwrap_or_default();
+        if let Some(d) = delimiter {
+            dialect.delimiter = d;
+        }
+        let mut wtr = dialect.writer_builder().from_writer(writer);
+
+        if dialect.has_headers {
+            let headers = self.extract_headers();
+            wtr.write_record(&headers)?;
+        }
+
+        // a JSON null (of analyzed JSON records) is an empty value in csv
+        for record in self.generate_records().take(row_count as usize) {
+            wtr.write_record(record?.iter().map(|v| match v.as_str() {
+                JSON_NULL => "",
+                value => value,
+            }))?;
+        }
+
+        wtr.flush().map_err(csv::Error::from)?;
+
+        Ok(())
+    }
+
+    /// This function creates a JSON file of generated test data, (an array of objects that are keyed by the headers, in the same order as the headers).
+    ///
+    /// # Arguments
+    ///
+    /// * `row_count: u32` - The number of records to generate.</br>
+    /// * `path: &str` - The full path name where to save the JSON file.</br>
+    /// * `typed: bool` - true to write the values of numeric and boolean fields as JSON numbers and booleans, otherwise all values are strings.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     dsp.try_generate_json(100, "./tests/samples/generated-01.json", false).unwrap();
+    /// }
+    /// ```
+    pub fn try_generate_json(&mut self, row_count: u32, path: &str, typed: bool) -> TdgResult<()> {
+        info!("generating json file {}", path);
+
+        let file = Self::try_create_file(path)?;
+        self.try_generate_json_writer(row_count, io::BufWriter::new(file), typed)
+            .map_err(|e| Self::with_path(e, path))
+    }
+
+    /// This function writes generated test data as a JSON array of objects to a writer (e.g.: stdout, an in-memory buffer).
+    /// The objects are keyed by the headers, in the same order as the headers, or have the nested shape of the analyzed JSON records,
+    /// (see try_generate_json_record()).
+    ///
+    /// # Arguments
+    ///
+    /// * `row_count: u32` - The number of records to generate.</br>
+    /// * `writer: W` - The writer of the JSON data.</br>
+    /// * `typed: bool` - true to write the values of numeric and boolean fields as JSON numbers and booleans, otherwise all values are strings.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_csv_data("name,age,member\nAaron,32,true\nBeth,41,false\n", None).unwrap();
+    ///
+    ///     let mut buffer = Vec::new();
+    ///     dsp.try_generate_json_writer(2, &mut buffer, true).unwrap();
+    ///
+    ///     let generated: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+    ///     assert!(generated[0]["age"].is_number());
+    ///     assert!(generated[1]["member"].is_boolean());
+    /// }
+    /// ```
+    pub fn try_generate_json_writer<W: io::Write>(
+        &mut self,
+        row_count: u32,
+        mut writer: W,
+        typed: bool,
+    ) -> TdgResult<()> {
+        self.check_capacity(row_count)?;
+
+        writer.write_all(b"[").map_err(serde_json::Error::io)?;
+        for idx in 0..row_count {
+            if idx > 0 {
+                writer.write_all(b",").map_err(serde_json::Error::io)?;
+            }
+            serde_json::to_writer(&mut writer, &self.try_generate_json_record(typed)?)?;
+        }
+        writer.write_all(b"]").map_err(serde_json::Error::io)?;
+        writer.flush().map_err(serde_json::Error::io)?;
+
+        Ok(())
+    }
+
+    /// This function creates a newline-delimited JSON (NDJSON) file of generated test data, (one object per line, see try_generate_json_record()).
+    ///
+    /// # Arguments
+    ///
+    /// * `row_count: u32` - The number of records to generate.</br>
+    /// * `path: &str` - The full path name where to save the NDJSON file.</br>
+    /// * `typed: bool` - true to write the values of numeric and boolean fields as JSON numbers and booleans, otherwise all values are strings.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///     dsp.try_generate_ndjson(100, "./tests/samples/generated-01.ndjson", false).unwrap();
+    /// }
+    /// ```
+    pub fn try_generate_ndjson(
+        &mut self,
+        row_count: u32,
+        path: &str,
+        typed: bool,
+    ) -> TdgResult<()> {
+        info!("generating ndjson file {}", path);
+
+        let file = Self::try_create_file(path)?;
+        self.try_generate_ndjson_writer(row_count, io::BufWriter::new(file), typed)
+            .map_err(|e| Self::with_path(e, path))
+    }
+
+    /// This function writes generated test data as newline-delimited JSON (NDJSON) to a writer, (one object per line, see try_generate_json_record()).
+    ///
+    /// # Arguments
+    ///
+    /// * `row_count: u32` - The number of records to generate.</br>
+    /// * `writer: W` - The writer of the NDJSON data.</br>
+    /// * `typed: bool` - true to write the values of numeric and boolean fields as JSON numbers and booleans, otherwise all values are strings.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///
+    ///     let mut buffer = Vec::new();
+    ///     dsp.try_generate_ndjson_writer(3, &mut buffer, false).unwrap();
+    ///
+    ///     let generated = String::from_utf8(buffer).unwrap();
+    ///     assert_eq!(generated.lines().count(), 3);
+    ///     assert!(generated.starts_with("{\"firstname\":"));
+    /// }
+    /// ```
+    pub fn try_generate_ndjson_writer<W: io::Write>(
+        &mut self,
+        row_count: u32,
+        mut writer: W,
+        typed: bool,
+    ) -> TdgResult<()> {
+        self.check_capacity(row_count)?;
+
+        for _ in 0..row_count {
+            serde_json::to_writer(&mut writer, &self.try_generate_json_record(typed)?)?;
+            writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+        }
+        writer.flush().map_err(serde_json::Error::io)?;
+
+        Ok(())
+    }
+
+    /// This function creates a SQL script that seeds a database table with generated test data, (see the `sql` module).
+    /// The script can start with a CREATE TABLE statement (with the column types inferred from the analyzed values),
+    /// followed by batched INSERT statements or a COPY ... FROM STDIN block. The column names are the ids of the Profiles.
+    ///
+    /// # Arguments
+    ///
+    /// * `row_count: u32` - The number of records to generate.</br>
+    /// * `path: &str` - The full path name where to save the SQL script.</br>
+    /// * `options: &SqlOptions` - The table name, dialect and kind of statements of the script.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    /// use test_data_generation::sql::{SqlDialect, SqlOptions};
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///
+    ///     let mut options = SqlOptions::new("people");
+    ///     options.dialect = SqlDialect::Sqlite;
+    ///     dsp.try_generate_sql(100, "./tests/samples/generated-01.sql", &options).unwrap();
+    /// }
+    /// ```
+    pub fn try_generate_sql(
+        &mut self,
+        row_count: u32,
+        path: &str,
+        options: &SqlOptions,
+    ) -> TdgResult<()> {
+        info!("generating sql script {}", path);
+
+        options.validate()?;
+        let file = Self::try_create_file(path)?;
+        self.try_generate_sql_writer(row_count, io::BufWriter::new(file), options)
+            .map_err(|e| match e {
+                TdgError::Write { source } => TdgError::FileWrite {
+                    path: path.to_string(),
+                    source,
+                },
+                other => other,
+            })
+    }
+
+    /// This function writes a SQL script that seeds a database table with generated test data to a writer (e.g.: stdout, a pipe to psql),
+    /// (see try_generate_sql()).
+    ///
+    /// # Arguments
+    ///
+    /// * `row_count: u32` - The number of records to generate.</br>
+    /// * `writer: W` - The writer of the SQL script.</br>
+    /// * `options: &SqlOptions` - The table name, dialect and kind of statements of the script.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    /// use test_data_generation::sql::SqlOptions;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_csv_data("name,age\nAaron,32\nBeth,41\n", None).unwrap();
+    ///
+    ///     let mut script = Vec::new();
+    ///     dsp.try_generate_sql_writer(3, &mut script, &SqlOptions::new("people")).unwrap();
+    ///
+    ///     let script = String::from_utf8(script).unwrap();
+    ///     assert!(script.starts_with("CREATE TABLE IF NOT EXISTS \"people\" (\n  \"name\" TEXT,\n  \"age\" BIGINT\n);\n"));
+    ///     assert!(script.contains("INSERT INTO \"people\" (\"name\", \"age\") VALUES\n"));
+    /// }
+    /// ```
+    pub fn try_generate_sql_writer<W: io::Write>(
+        &mut self,
+        row_count: u32,
+        mut writer: W,
+        options: &SqlOptions,
+    ) -> TdgResult<()> {
+        options.validate()?;
+        self.check_capacity(row_count)?;
+
+        let columns: Vec<String> = self
+            .profiles
+            .iter()
+            .map(|(key, p)| p.id.clone().unwrap_or_else(|| key.clone()))
+            .collect();
+        let data_types = self.data_types(true);
+        let sentinels = self.null_sentinels();
+        let mut write = |text: &str| {
+            writer
+                .write_all(text.as_bytes())
+                .map_err(|source| TdgError::Write { source })
+        };
+
+        if options.create_table {
+            write(&options.create_table_statement(&columns, &data_types))?;
+        }
+
+        let mut records = self.generate_records().take(row_count as usize).map(|r| {
+            r.map(|record| {
+                record
+                    .into_iter()
+                    .zip(data_types.iter().zip(sentinels.iter()))
+                    .map(|(value, (data_type, sentinels))| {
+                        DataType::typed_value_in(*data_type, value, sentinels)
+                    })
+                    .collect::<Vec<Value>>()
+            })
+        });
+
+        match options.statement {
+            SqlStatement::Insert => loop {
+                let batch = records
+                    .by_ref()
+                    .take(options.batch_size)
+                    .collect::<TdgResult<Vec<Vec<Value>>>>()?;
+                if batch.is_empty() {
+                    break;
+                }
+                write(&options.insert_statement(&columns, &batch))?;
+            },
+            SqlStatement::Copy => {
+                write(&options.copy_statement(&columns))?;
+                for record in records {
+                    write(&options.copy_record(&record?))?;
+                }
+                write(options.copy_end())?;
+            }
+        }
+
+        writer.flush().map_err(|source| TdgError::Write { source })
+    }
+
+    /// This function returns the type of the values of each field, (or None for every field if the values aren't typed).
+    fn data_types(&self, typed: bool) -> Vec<Option<DataType>> {
+        self.profiles
+            .values()
+            .map(|p| if typed { p.data_type } else { None })
+            .collect()
+    }
+
+    /// This function returns the tokens that represent a null value of each Profile, (see set_null_sentinels()).
+    fn null_sentinels(&self) -> Vec<Vec<String>> {
+        self.profiles
+            .values()
+            .map(|p| p.get_null_sentinels())
+            .collect()
+    }
+
+    /// This function creates the file to write generated test data to.
+    fn try_create_file(path: &str) -> TdgResult<File> {
+        File::create(path).map_err(|e| {
+            error!("file {} couldn't be created!", path);
+            TdgError::FileCreate {
+                path: path.to_string(),
+                source: e,
+            }
+        })
+    }
+
+    /// This function adds the path of the file to a JSON error.
+    fn with_path(e: TdgError, path: &str) -> TdgError {
+        match e {
+            TdgError::Json { source, .. } => TdgError::Json {
+                path: Some(path.to_string()),
+                source,
+            },
+            other => other,
+        }
+    }
+
+    /// This function returns an iterator that lazily generates records of test data, one at a time.
+    /// The iterator never ends, so use take() (or break) to limit the number of records.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::new();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+    ///
+    ///     for record in dsp.generate_records().take(5) {
+    ///         println!("Generated data record: {:?}", record.unwrap());
+    ///     }
+    /// }
+    /// ```
+    pub fn generate_records(&mut self) -> GeneratedRecords<'_> {
+        GeneratedRecords { dsp: self }
+    }
+
+    /// This function generates test data to the csv file defined by the output settings of the configuration file, (see try_new_with()).
+    /// The number of rows defaults to 100 and the delimiter defaults to a comma.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp = DataSampleParser::try_new_with("./tests/config/tdg.yaml").unwrap();
+    ///
+    ///     // the configuration file doesn't have output settings
+    ///     assert!(dsp.try_generate_configured_csv().is_err());
+    /// }
+    /// ```
+    pub fn try_generate_configured_csv(&mut self) -> TdgResult<()> {
+        let output = self
+            .settings()
+            .and_then(|s| s.data_generator.output.clone())
+            .ok_or_else(|| TdgError::InvalidSetting {
+                setting: "data-generator.output".to_string(),
+                message: "there are no output settings in the configuration file".to_string(),
+            })?;
+        let delimiter = to_delimiter("data-generator.output.delimiter", &output.delimiter)?;
+
+        self.try_generate_csv(output.rows.unwrap_or(DEFAULT_ROWS), &output.path, delimiter)
+    }
+
+    /// This function calculates the levenshtein distance between 2 strings.
+    /// See: https://crates.io/crates/levenshtein
+    ///
+    /// # Arguments
+    ///
+    /// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
+    /// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the distance.</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///     // analyze the dataset
+    ///        let mut dsp =  DataSampleParser::new();
+    ///
+    ///     assert_eq!(dsp.levenshtein_distance(&"kitten".to_string(), &"sitting".to_string()), 3 as usize);
+    /// }
+    ///
+    pub fn levenshtein_distance(&mut self, control: &str, experiment: &str) -> usize {
+        // https://docs.rs/levenshtein/1.0.3/levenshtein/fn.levenshtein.html
+        levenshtein_distance!(control, experiment)
+    }
+
+    /// This function calculates the percent difference between 2 strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
+    /// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the percent difference.</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///     // analyze the dataset
+    ///        let mut dsp =  DataSampleParser::new();
+    ///
+    ///     assert_eq!(dsp.realistic_test(&"kitten".to_string(), &"sitting".to_string()), 76.92307692307692 as f64);
+    /// }
+    ///
+    #[allow(clippy::ptr_arg)]
+    pub fn realistic_test(&mut self, control: &String, experiment: &String) -> f64 {
+        //https://docs.rs/GSL/0.4.31/rgsl/statistics/fn.correlation.html
+        //http://www.statisticshowto.com/probability-and-statistics/correlation-coefficient-formula/
+        // pearson's chi square test
+        // cosine similarity - http://blog.christianperone.com/2013/09/machine-learning-cosine-similarity-for-vector-space-models-part-iii/
+        realistic_test!(control, experiment)
+    }
+
+    /// This function returns a boolean that indicates if the data sample parsing had issues
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        // initalize a new DataSampelParser
+    ///        // param: the path to the configuration file is wrong
+    ///        let dsp = DataSampleParser::new_with(&String::from("./target/debug/config/tdg.yaml"));
+    ///
+    ///        // the configuration file couldn't be loaded
+    ///        assert_eq!(dsp.running_with_issues(), &true);
+    /// }
+    pub fn running_with_issues(&self) -> &bool {
+        &self.issues
+    }
+
+    /// This function saves (exports) the DataSampleParser to a JSON file.
+    /// This is useful when you wish to reuse the algorithm to generate more test data later.
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The full path of the export file , excluding the file extension, (e.g.: "./test/data/custom-names").</br>
+    ///
+    /// #Errors
+    /// If this function encounters any form of I/O or other error, an error variant will be returned.
+    /// Otherwise, the function returns Ok(true).</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///     // analyze the dataset
+    ///        let mut dsp =  DataSampleParser::new();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-00.csv"), None).unwrap();
+    ///
+    ///     assert_eq!(dsp.save(&String::from("./tests/samples/sample-00-dsp")).unwrap(), true);
+    /// }
+    ///
+    pub fn save(&mut self, path: &str) -> Result<bool, io::Error> {
+        match self.try_save(path) {
+            Ok(_) => Ok(true),
+            Err(TdgError::FileCreate { source, .. }) | Err(TdgError::FileWrite { source, .. }) => {
+                Err(source)
+            }
+            Err(e) => Err(io::Error::other(e)),
+        }
+    }
+
+    /// This function saves (exports) the DataSampleParser to a JSON file, returning an error that includes the path if it couldn't be saved.
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The full path of the export file , excluding the file extension, (e.g.: "./test/data/custom-names").</br>
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::data_sample_parser::DataSampleParser;
+    ///
+    /// fn main() {
+    ///        let mut dsp =  DataSampleParser::new();
+    ///     dsp.analyze_csv_file(&String::from("./tests/samples/sample-00.csv"), None).unwrap();
+    ///
+    ///     assert!(dsp.try_save("./badpath/sample-00-dsp").is_err());
+    /// }
+    ///
+    pub fn try_save(&mut self, path: &str) -> TdgResult<()> {
+        let dsp_json = match self.export_privacy.as_ref() {
+            Some(dp) => serde_json::to_string(&self.privatize(dp)?)?,
+            None => serde_json::to_string(&self)?,
+        };
+
+        // Create the archive file
+        let mut file = match File::create(format!("{}.json", &path)) {
+            Err(e) => {
+                error!("Could not create file {:?}", &path.to_string());
+                return Err(TdgError::FileCreate {
+                    path: path.to_string(),
+                    source: e,
+                });
+            }
+            Ok(f) => {
+                info!("Successfully exported to {:?}", &path.to_string());
+                f
+            }
+        };
+
+        // Write the json string to file, returns io::Result<()>
+        match file.write_all(dsp_json.as_bytes()) {
+            Err(e) => {
+                error!("Could not write to file {}", &path.to_string());
+                return Err(TdgError::FileWrite {
+                    path: path.to_string(),
+                    source: e,
+                });
+            }
+            Ok(_) => {
+                info!("Successfully exported to {}", &path.to_string());
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::SqlDialect;
+    use std::collections::BTreeSet;
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::path::Path;
+
+    #[test]
+    // ensure a new Data Sample Parser can be created
+    fn test_new() {
+        let _dsp = DataSampleParser::new();
+
+        assert!(true);
+    }
+
+    #[test]
+    // ensure a new Data Sample Parser can't be loaded from a configuration file that doesn't exist
+    fn test_try_new_with_bad_path() {
+        assert!(DataSampleParser::try_new_with("./badpath/tdg.yaml").is_err());
+    }
+
+    #[test]
+    // ensure the settings of the configuration file are applied end to end
+    fn test_try_new_with_settings() {
+        let mut dsp = DataSampleParser::try_new_with("./tests/config/tdg-full.yaml").unwrap();
+
+        assert_eq!(dsp.extract_headers(), vec!["firstname", "lastname"]);
+        for field in ["firstname", "lastname"] {
+            let profile = dsp.get_profile(field).unwrap();
+            assert_eq!(profile.processors, 2);
+            assert_eq!(profile.markov.as_ref().unwrap().order, 2);
+            assert!(profile.seed.is_some());
+        }
+        assert_eq!(
+            dsp.get_correlation().unwrap().fields,
+            vec!["firstname", "lastname"]
+        );
+
+        let seeded = DataSampleParser::try_new_with("./tests/config/tdg-full.yaml")
+            .unwrap()
+            .generate_record();
+        assert_eq!(dsp.generate_record(), seeded);
+
+        dsp.try_generate_configured_csv().unwrap();
+        let generated = std::fs::read_to_string("./tests/samples/generated-config.csv").unwrap();
+        assert_eq!(generated.lines().count(), 11);
+        assert!(generated.starts_with("firstname|lastname"));
+    }
+
+    #[test]
+    // ensure the generated records only use combinations of patterns that occurred together in the data sample
+    fn test_generate_record_with_correlation() {
+        let data = "city,zip\nBoston,02101\nDenver,80201\nToronto,M5V 2T6\nOttawa,K1A 0B1\n";
+        let mut dsp = DataSampleParser::new_with_seed(99);
+        dsp.set_correlation(true);
+        dsp.try_analyze_csv_data(data, None).unwrap();
+
+        let pttrn_def = PatternDefinition::new();
+        let model = dsp.get_correlation().unwrap().clone();
+        assert_eq!(model.total(), 4);
+
+        for _ in 0..50 {
+            let record = dsp.try_generate_record().unwrap();
+            let patterns: Vec<String> = record.iter().map(|v| pttrn_def.symbolize(v)).collect();
+            assert!(
+                model.combinations.contains_key(&patterns),
+                "{:?} has a combination of patterns that wasn't analyzed",
+                record
+            );
+        }
+
+        dsp.set_correlation(false);
+        assert!(dsp.get_correlation().is_none());
+    }
+
+    #[test]
+    // ensure the correlation model is saved in the archive and is only learned from csv data with the same fields
+    fn test_correlation_from_serialized() {
+        let mut dsp = DataSampleParser::new();
+        dsp.set_correlation(true);
+        dsp.try_analyze_csv_file("./tests/samples/sample-01.csv", None)
+            .unwrap();
+        dsp.try_analyze_csv_data("city,zip\nBoston,02101\n", None)
+            .unwrap();
+        let model = dsp.get_correlation().unwrap().clone();
+        assert_eq!(model.fields, vec!["firstname", "lastname"]);
+
+        dsp.try_save("./tests/samples/sample-01-correlation-dsp")
+            .unwrap();
+        let loaded =
+            DataSampleParser::try_from_file("./tests/samples/sample-01-correlation-dsp").unwrap();
+        assert_eq!(loaded.get_correlation(), Some(&model));
+    }
+
+    #[test]
+    // ensure the delimited-string data source of the configuration file is analyzed
+    fn test_try_new_with_delimited_string() {
+        let mut dsp = DataSampleParser::try_new_with("./tests/config/tdg.yaml").unwrap();
+
+        assert_eq!(dsp.extract_headers(), vec!["value"]);
+        assert_eq!(dsp.get_profile("value").unwrap().pattern_total, 6);
+    }
+
+    #[test]
+    // ensure a csv file analyzed after the configured data source is analyzed into its own profiles
+    fn test_analyze_csv_file_after_data_source() {
+        let mut dsp = DataSampleParser::try_new_with("./tests/config/tdg.yaml").unwrap();
+        dsp.analyze_csv_file("./tests/samples/sample-01.csv", None)
+            .unwrap();
+
+        assert_eq!(
+            dsp.extract_headers(),
+            vec!["value", "firstname", "lastname"]
+        );
+        assert_eq!(dsp.get_profile("value").unwrap().pattern_total, 6);
+        assert_eq!(dsp.generate_record().len(), 3);
+    }
+
+    #[test]
+    // ensure a new Data Sample Parser can be created with configurations
+    fn test_new_with() {
+        let dsp = DataSampleParser::new_with(&String::from("./tests/config/tdg-full.yaml"));
+
+        assert!(!dsp.running_with_issues());
+        let profile = dsp.get_profile("firstname").unwrap();
+        assert_eq!(profile.processors, 2);
+        assert_eq!(profile.markov.as_ref().unwrap().order, 2);
+
+        let mut bad = DataSampleParser::new_with(&String::from("./config/tdg.yaml"));
+        assert!(bad.running_with_issues());
+        assert!(bad.extract_headers().is_empty());
+    }
+
+    #[test]
+    // ensure the Data Sample Parser can be restored from archived file
+    fn test_from_file() {
+        let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-00-dsp"));
+        println!("Sample data is [{:?}]", dsp.generate_record()[0]);
+
+        assert_eq!(dsp.generate_record()[0], "OK".to_string());
+    }
+
+    #[test]
+    // ensure the Data Sample Parser can be restored from archived file that
+    // was saved using version 0.2.1 using a configuration
+    fn test_from_file_v021_with_cfg() {
+        let mut dsp =
+            DataSampleParser::from_file(&String::from("./tests/samples/sample-0.2.1-dsp"));
+        println!("Sample data is [{:?}]", dsp.generate_record()[0]);
+
+        assert_eq!(dsp.generate_record()[0], "OK".to_string());
+    }
+
+    #[test]
+    // ensure the Data Sample Parser can be restored from archived file that
     // was saved using version 0.2.1 without a configuration
     fn test_from_file_v021_no_cfg() {
         let mut dsp =
             DataSampleParser::from_file(&String::from("./tests/samples/sample-0.2.1-nocfg-dsp"));
         println!("Sample data is [{:?}]", dsp.generate_record()[0]);
 
-        assert_eq!(dsp.generate_record()[0], "OK".to_string());
+        assert_eq!(dsp.generate_record()[0], "OK".to_string());
+    }
+
+    #[test]
+    // ensure Data Sample Parsers with the same seed generate the same test data
+    fn test_new_with_seed() {
+        let mut dsp_a = DataSampleParser::new_with_seed(1234);
+        let mut dsp_b = DataSampleParser::new_with_seed(1234);
+
+        dsp_a
+            .analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+        dsp_b
+            .analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+
+        for _ in 0..20 {
+            assert_eq!(dsp_a.generate_record(), dsp_b.generate_record());
+        }
+    }
+
+    #[test]
+    // ensure a seeded Data Sample Parser generates the same test data after being restored from an archive
+    fn test_seed_from_file() {
+        let mut dsp = DataSampleParser::new_with_seed(42);
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+        dsp.save(&String::from("./tests/samples/sample-01-seeded-dsp"))
+            .unwrap();
+
+        let mut restored =
+            DataSampleParser::from_file(&String::from("./tests/samples/sample-01-seeded-dsp"));
+        let expected: Vec<Vec<String>> = (0..10).map(|_| dsp.generate_record()).collect();
+        let actual: Vec<Vec<String>> = (0..10).map(|_| restored.generate_record()).collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    // ensure the Data Sample Parser returns an error when the archived file doesn't exist
+    fn test_try_from_file_bad_path() {
+        match DataSampleParser::try_from_file("./tests/samples/bad-path") {
+            Err(TdgError::FileOpen { path, .. }) => assert_eq!(path, "./tests/samples/bad-path"),
+            other => panic!("Expected a FileOpen error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    // ensure the Data Sample Parser returns an error when the archived file isn't valid JSON
+    fn test_try_from_file_bad_data() {
+        match DataSampleParser::try_from_file("./tests/samples/not-readable") {
+            Err(TdgError::Json { path, .. }) => {
+                assert_eq!(path, Some("./tests/samples/not-readable.json".to_string()))
+            }
+            other => panic!("Expected a Json error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    // ensure the Data Sample Parser returns an error when the archive is missing a field
+    fn test_try_from_serialized_missing_field() {
+        let serialized =
+            "{\"issues\":false,\"cfg\":null,\"profiles\":{\"firstname\":{\"patterns\":{}}}}";
+
+        match DataSampleParser::try_from_serialized(serialized) {
+            Err(TdgError::MissingField { field }) => assert_eq!(field, "profiles.firstname.id"),
+            other => panic!("Expected a MissingField error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    // ensure the Data Sample Parser returns an error when generating data for a field that doesn't exist
+    fn test_try_generate_by_field_name_unknown() {
+        let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+
+        match dsp.try_generate_by_field_name("middlename") {
+            Err(TdgError::ProfileNotFound { field }) => assert_eq!(field, "middlename"),
+            other => panic!("Expected a ProfileNotFound error, but got {:?}", other),
+        }
+    }
+
+    // a reader that returns the data a few bytes at a time
+    struct TrickleReader<'a> {
+        data: &'a [u8],
+    }
+
+    impl<'a> Read for TrickleReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(7).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    // ensure streaming the csv data in chunks analyzes every record
+    fn test_try_analyze_csv_reader_chunks() {
+        let data = std::fs::read_to_string("./tests/samples/sample-names.csv").unwrap();
+        let mut dsp = DataSampleParser::new();
+        dsp.try_analyze_csv_reader(
+            TrickleReader {
+                data: data.as_bytes(),
+            },
+            None,
+        )
+        .unwrap();
+
+        let mut expected = Profile::new();
+        for line in data.lines().skip(1) {
+            expected.analyze(line.split(',').next().unwrap());
+        }
+
+        let firstname = dsp.get_profile("firstname").unwrap();
+        assert_eq!(firstname.pattern_total, 15000);
+        assert_eq!(firstname.patterns, expected.patterns);
+        assert_eq!(firstname.facts, expected.facts);
+    }
+
+    #[test]
+    // ensure a record with the wrong number of fields is reported instead of panicking
+    fn test_try_analyze_csv_reader_bad_record() {
+        let mut dsp = DataSampleParser::new();
+        // the sniffed dialect would allow records of different lengths
+        dsp.set_csv_dialect(Some(CsvDialect::new()));
+        let data = "firstname,lastname\nAaron,Aaberg\nAbby\n";
+
+        match dsp.try_analyze_csv_reader(data.as_bytes(), None) {
+            Err(TdgError::Csv { path: None, .. }) => {}
+            other => panic!("Expected a Csv error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    // ensure DataSampleParser returns an error for a csv file that doesn't exist
+    fn test_try_analyze_csv_file_bad_path() {
+        let mut dsp = DataSampleParser::new();
+
+        match dsp.try_analyze_csv_file("./badpath/sample-01.csv", None) {
+            Err(TdgError::FileOpen { path, .. }) => assert_eq!(path, "./badpath/sample-01.csv"),
+            other => panic!("Expected a FileOpen error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    // ensure the Profiles of a Data Sample Parser use the Markov chain order of the parser
+    fn test_set_markov_order() {
+        let mut dsp = DataSampleParser::new();
+        dsp.set_markov_order(2).unwrap();
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+
+        for profile in dsp.profiles.values() {
+            assert_eq!(profile.markov.as_ref().unwrap().order, 2);
+        }
+        assert!(dsp.set_markov_order(0).is_err());
+        assert_eq!(dsp.generate_record().len(), 2);
+    }
+
+    #[test]
+    // ensure the Data Sample Parser can read all the headers from teh csv file
+    fn test_read_headers() {
+        let mut dsp = DataSampleParser::new();
+
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+        let headers = dsp.extract_headers();
+
+        assert_eq!(headers.len(), 2);
+    }
+
+    #[test]
+    // ensure the Data Sample Parser can read all the headers from teh csv file
+    fn test_read_headers_order() {
+        let mut expected = Vec::new();
+        expected.push("column-Z");
+        expected.push("column-D");
+        expected.push("column-A");
+        expected.push("column-G");
+        let mut dsp = DataSampleParser::new();
+
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-02.csv"), None)
+            .unwrap();
+        let headers = dsp.extract_headers();
+
+        assert_eq!(headers, expected);
+    }
+
+    #[test]
+    // ensure DataSampleParser can analyze a csv formatted file
+    fn test_parse_csv_file() {
+        let mut dsp = DataSampleParser::new();
+
+        assert_eq!(
+            dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    // ensure DataSampleParser can analyze a csv formatted text
+    fn test_parse_csv_data_using_defaults() {
+        let mut dsp = DataSampleParser::new();
+        let mut data = String::from("");
+        data.push_str("\"firstname\",\"lastname\"\n");
+        data.push_str("\"Aaron\",\"Aaberg\"\n");
+        data.push_str("\"Aaron\",\"Aaby\"\n");
+        data.push_str("\"Abbey\",\"Aadland\"\n");
+        data.push_str("\"Abbie\",\"Aagaard\"\n");
+        data.push_str("\"Abby\",\"Aakre\"");
+
+        assert_eq!(dsp.analyze_csv_data(&data, None).unwrap(), 1);
+    }
+
+    #[test]
+    // ensure DataSampleParser can analyze a csv formatted text
+    fn test_parse_csv_data() {
+        let mut dsp = DataSampleParser::new();
+        let mut data = String::from("");
+        data.push_str("\"firstname\"|\"lastname\"\n");
+        data.push_str("\"Aaron\"|\"Aaberg\"\n");
+        data.push_str("\"Aaron\"|\"Aaby\"\n");
+        data.push_str("\"Abbey\"|\"Aadland\"\n");
+        data.push_str("\"Abbie\"|\"Aagaard\"\n");
+        data.push_str("\"Abby\"|\"Aakre\"");
+
+        assert_eq!(dsp.analyze_csv_data(&data, Some(b'|')).unwrap(), 1);
+    }
+    #[test]
+    // ensure DataSampleParser can analyze a csv formatted file
+    fn test_generate_field_from_csv_file() {
+        let mut dsp = DataSampleParser::new();
+
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+        println!(
+            "Generated data for first name {}",
+            dsp.generate_by_field_name("firstname".to_string())
+        );
+    }
+
+    #[test]
+    // ensure DataSampleParser can analyze a csv formatted file
+    fn test_generate_record_from_csv_file() {
+        let mut dsp = DataSampleParser::new();
+
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+        assert_eq!(dsp.generate_record().len(), 2);
+    }
+
+    #[test]
+    // ensure DataSampleParser can analyze a csv formatted file
+    fn test_parse_csv_file_bad() {
+        let mut dsp = DataSampleParser::new();
+
+        assert_eq!(
+            dsp.analyze_csv_file(&String::from("./badpath/sample-01.csv"), None)
+                .is_err(),
+            true
+        );
+    }
+
+    #[test]
+    // ensure the DataSampleParser object can be saved to file
+    fn test_save() {
+        let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-00.csv"), None)
+            .unwrap();
+
+        assert_eq!(
+            dsp.save(&String::from("./tests/samples/sample-00-dsp"))
+                .unwrap(),
+            true
+        );
+    }
+
+    #[test]
+    // ensure the DataSampleParser object can recognize the difference between realistic data and unrealistic generated data
+    fn test_levenshtein_test() {
+        let mut dsp = DataSampleParser::new();
+
+        assert_eq!(
+            dsp.levenshtein_distance(&"kitten".to_string(), &"sitting".to_string()),
+            3 as usize
+        );
+    }
+
+    #[test]
+    // ensure the DataSampleParser object can recognize the difference between realistic data and unrealistic generated data
+    fn test_realistic_data_test() {
+        let mut dsp = DataSampleParser::new();
+
+        assert_eq!(
+            dsp.realistic_test(&"kitten".to_string(), &"sitting".to_string()),
+            76.92307692307692 as f64
+        );
+    }
+
+    #[test]
+    // demo test
+    fn test_demo() {
+        let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+
+        println!(
+            "My new name is {} {}",
+            dsp.generate_record()[0],
+            dsp.generate_record()[1]
+        );
+
+        assert!(true);
+    }
+
+    #[test]
+    // ensure the DataSampleParser object can generate test data as a csv file
+    fn test_extract_headers_from_sample() {
+        let mut dsp = DataSampleParser::new();
+
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+        let headers = dsp.extract_headers();
+
+        assert_eq!(headers.len(), 2);
+    }
+
+    #[test]
+    // ensure the DataSampleParser object can generate test data as a csv file
+    fn test_generate_csv_test_data_from_sample() {
+        let mut dsp = DataSampleParser::new();
+
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+        dsp.generate_csv(
+            100,
+            &String::from("./tests/samples/generated-01b.csv"),
+            Some(b'|'),
+        )
+        .unwrap();
+
+        let generated_row_count =
+            match File::open(format!("{}", "./tests/samples/generated-01b.csv")) {
+                Err(_e) => 0,
+                Ok(f) => {
+                    let mut count = 0;
+                    let bf = BufReader::new(f);
+
+                    for _line in bf.lines() {
+                        count += 1;
+                    }
+
+                    count
+                }
+            };
+
+        assert_eq!(generated_row_count, 101);
+    }
+
+    // a writer that fails, (e.g.: a closed pipe)
+    struct BrokenWriter {}
+
+    impl Write for BrokenWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    // ensure the generated csv data can be written to an in-memory buffer
+    fn test_try_generate_csv_writer() {
+        let mut dsp = DataSampleParser::new_with_seed(5);
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+
+        let mut buffer = Vec::new();
+        dsp.try_generate_csv_writer(20, &mut buffer, Some(b';'))
+            .unwrap();
+        let generated = String::from_utf8(buffer).unwrap();
+
+        assert_eq!(generated.lines().count(), 21);
+        assert_eq!(generated.lines().next(), Some("firstname;lastname"));
+    }
+
+    #[test]
+    fn test_try_generate_csv_writer_broken() {
+        let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+
+        match dsp.try_generate_csv_writer(10, BrokenWriter {}, None) {
+            Err(TdgError::Csv { path: None, .. }) => {}
+            other => panic!("Expected a Csv error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    // ensure the records are generated lazily, one at a time, in the same sequence as generate_record()
+    fn test_generate_records() {
+        let mut dsp = DataSampleParser::new_with_seed(11);
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+
+        let records: Vec<Vec<String>> =
+            dsp.generate_records().take(3).map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 3);
+
+        dsp.set_seed(11);
+        for record in records {
+            assert_eq!(dsp.generate_record(), record);
+        }
+    }
+
+    #[test]
+    // ensure the JSON objects keep the order of the headers and numbers and booleans can be native JSON types
+    fn test_try_generate_json_writer_typed() {
+        let data = "zip,name,age,score,member\n02101,Aaron,32,7.5,true\n80201,Beth,41,9,FALSE\n";
+        let mut dsp = DataSampleParser::new_with_seed(3);
+        dsp.try_analyze_csv_data(data, None).unwrap();
+
+        let mut buffer = Vec::new();
+        dsp.try_generate_json_writer(5, &mut buffer, true).unwrap();
+        let generated = String::from_utf8(buffer).unwrap();
+        assert!(generated.starts_with("[{\"zip\":\""));
+
+        let records: Vec<Value> = serde_json::from_str(&generated).unwrap();
+        assert_eq!(records.len(), 5);
+        for record in records {
+            // leading zeros are kept in strings
+            assert!(record["zip"].is_string());
+            assert!(record["name"].is_string());
+            assert!(record["age"].is_i64());
+            assert!(record["score"].is_number());
+            assert!(record["member"].is_boolean());
+        }
+
+        let mut buffer = Vec::new();
+        dsp.try_generate_json_writer(2, &mut buffer, false).unwrap();
+        let records: Vec<Value> = serde_json::from_slice(&buffer).unwrap();
+        assert!(records[0]["age"].is_string());
+    }
+
+    #[test]
+    fn test_try_generate_json_writer_empty() {
+        let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+
+        let mut buffer = Vec::new();
+        dsp.try_generate_json_writer(0, &mut buffer, true).unwrap();
+        assert_eq!(buffer, b"[]");
+    }
+
+    #[test]
+    // ensure the generated NDJSON file has one JSON object per line
+    fn test_try_generate_ndjson() {
+        let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
+        dsp.try_generate_ndjson(10, "./tests/samples/generated-01.ndjson", true)
+            .unwrap();
+
+        let generated = std::fs::read_to_string("./tests/samples/generated-01.ndjson").unwrap();
+        assert_eq!(generated.lines().count(), 10);
+        for line in generated.lines() {
+            let record: Value = serde_json::from_str(line).unwrap();
+            assert_eq!(record.as_object().unwrap().len(), 2);
+        }
+
+        match dsp.try_generate_ndjson(10, "./badpath/generated-01.ndjson", true) {
+            Err(TdgError::FileCreate { .. }) => {}
+            other => panic!("Expected a FileCreate error, but got {:?}", other),
+        }
     }
 
     #[test]
-    // ensure the Data Sample Parser can read all the headers from teh csv file
-    fn test_read_headers() {
+    // ensure the records are inserted in batches of the batch size
+    fn test_try_generate_sql_writer_insert_batches() {
         let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_data("name,age,member\nAaron,32,true\nBeth,41,false\n", None)
+            .unwrap();
 
-        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+        let mut script = Vec::new();
+        dsp.try_generate_sql_writer(250, &mut script, &SqlOptions::new("people"))
             .unwrap();
-        let headers = dsp.extract_headers();
+        let script = String::from_utf8(script).unwrap();
 
-        assert_eq!(headers.len(), 2);
+        assert!(script.starts_with(
+            "CREATE TABLE IF NOT EXISTS \"people\" (\n  \"name\" TEXT,\n  \"age\" BIGINT,\n  \"member\" BOOLEAN\n);\n"
+        ));
+        assert_eq!(script.matches("INSERT INTO \"people\"").count(), 3);
+        assert_eq!(script.matches(";\n").count(), 4);
     }
 
     #[test]
-    // ensure the Data Sample Parser can read all the headers from teh csv file
-    fn test_read_headers_order() {
-        let mut expected = Vec::new();
-        expected.push("column-Z");
-        expected.push("column-D");
-        expected.push("column-A");
-        expected.push("column-G");
+    fn test_try_generate_sql_writer_sqlite() {
         let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_data("name,member\nAaron,true\nBeth,false\n", None)
+            .unwrap();
 
-        dsp.analyze_csv_file(&String::from("./tests/samples/sample-02.csv"), None)
+        let mut options = SqlOptions::new("people");
+        options.dialect = SqlDialect::Sqlite;
+        options.create_table = false;
+
+        let mut script = Vec::new();
+        dsp.try_generate_sql_writer(2, &mut script, &options)
             .unwrap();
-        let headers = dsp.extract_headers();
+        let script = String::from_utf8(script).unwrap();
 
-        assert_eq!(headers, expected);
+        assert!(script.starts_with("INSERT INTO \"people\" (\"name\", \"member\") VALUES\n"));
+        assert!(!script.contains("TRUE") && !script.contains("FALSE"));
     }
 
     #[test]
-    // ensure DataSampleParser can analyze a csv formatted file
-    fn test_parse_csv_file() {
+    fn test_try_generate_sql_writer_copy() {
         let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
 
+        let mut options = SqlOptions::new("people");
+        options.statement = SqlStatement::Copy;
+        options.create_table = false;
+
+        let mut script = Vec::new();
+        dsp.try_generate_sql_writer(5, &mut script, &options)
+            .unwrap();
+        let script = String::from_utf8(script).unwrap();
+        let lines: Vec<&str> = script.lines().collect();
+
+        assert_eq!(lines.len(), 7);
         assert_eq!(
-            dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
-                .unwrap(),
-            1
+            lines[0],
+            "COPY \"people\" (\"firstname\", \"lastname\") FROM STDIN;"
         );
+        assert_eq!(lines[1].split('\t').count(), 2);
+        assert_eq!(lines[6], "\\.");
     }
 
     #[test]
-    // ensure DataSampleParser can analyze a csv formatted text
-    fn test_parse_csv_data_using_defaults() {
+    fn test_try_generate_sql_invalid_options() {
         let mut dsp = DataSampleParser::new();
-        let mut data = String::from("");
-        data.push_str("\"firstname\",\"lastname\"\n");
-        data.push_str("\"Aaron\",\"Aaberg\"\n");
-        data.push_str("\"Aaron\",\"Aaby\"\n");
-        data.push_str("\"Abbey\",\"Aadland\"\n");
-        data.push_str("\"Abbie\",\"Aagaard\"\n");
-        data.push_str("\"Abby\",\"Aakre\"");
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
 
-        assert_eq!(dsp.analyze_csv_data(&data, None).unwrap(), 1);
+        let mut options = SqlOptions::new("people");
+        options.dialect = SqlDialect::Sqlite;
+        options.statement = SqlStatement::Copy;
+
+        match dsp.try_generate_sql(10, "./tests/samples/generated-invalid.sql", &options) {
+            Err(TdgError::InvalidSetting { setting, .. }) => assert_eq!(setting, "statement"),
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+        assert!(!Path::new("./tests/samples/generated-invalid.sql").exists());
     }
 
     #[test]
-    // ensure DataSampleParser can analyze a csv formatted text
-    fn test_parse_csv_data() {
+    fn test_try_generate_sql_writer_broken() {
         let mut dsp = DataSampleParser::new();
-        let mut data = String::from("");
-        data.push_str("\"firstname\"|\"lastname\"\n");
-        data.push_str("\"Aaron\"|\"Aaberg\"\n");
-        data.push_str("\"Aaron\"|\"Aaby\"\n");
-        data.push_str("\"Abbey\"|\"Aadland\"\n");
-        data.push_str("\"Abbie\"|\"Aagaard\"\n");
-        data.push_str("\"Abby\"|\"Aakre\"");
+        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+            .unwrap();
 
-        assert_eq!(dsp.analyze_csv_data(&data, Some(b'|')).unwrap(), 1);
+        match dsp.try_generate_sql_writer(10, BrokenWriter {}, &SqlOptions::new("people")) {
+            Err(TdgError::Write { .. }) => {}
+            other => panic!("Expected a Write error, but got {:?}", other),
+        }
     }
+
     #[test]
-    // ensure DataSampleParser can analyze a csv formatted file
-    fn test_generate_field_from_csv_file() {
+    // ensure the generated records have the nested shape of the analyzed records
+    fn test_analyze_json_nested_shape() {
         let mut dsp = DataSampleParser::new();
+        let file = File::open("./tests/samples/sample-events.json").unwrap();
+        dsp.try_analyze_json_reader(file).unwrap();
 
-        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
-            .unwrap();
-        println!(
-            "Generated data for first name {}",
-            dsp.generate_by_field_name("firstname".to_string())
+        assert_eq!(
+            dsp.extract_headers(),
+            vec![
+                "id",
+                "event",
+                "user.name",
+                "user.email",
+                "user.admin",
+                "tags[]",
+                "items[].sku",
+                "items[].qty",
+                "items[].price"
+            ]
+        );
+        assert_eq!(
+            dsp.get_profile("items[].qty").unwrap().data_type,
+            Some(DataType::Integer)
         );
+
+        for _ in 0..20 {
+            let record = dsp.try_generate_json_record(true).unwrap();
+            let keys: Vec<&String> = record.as_object().unwrap().keys().collect();
+            assert_eq!(keys, vec!["id", "event", "user", "tags", "items"]);
+            assert!(record["id"].is_number());
+            assert!(record["user"]["admin"].is_boolean());
+            assert!(record["tags"].as_array().unwrap().len() <= 2);
+            for item in record["items"].as_array().unwrap() {
+                assert_eq!(item.as_object().unwrap().len(), 3);
+            }
+        }
     }
 
     #[test]
-    // ensure DataSampleParser can analyze a csv formatted file
-    fn test_generate_record_from_csv_file() {
+    // ensure the JSON nulls are kept apart from the empty strings and generated as null
+    fn test_analyze_json_nulls_round_trip() {
         let mut dsp = DataSampleParser::new();
-
-        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+        dsp.try_analyze_json("[{\"a\": null}, {\"a\": null}, {\"a\": \"\"}]")
             .unwrap();
-        assert_eq!(dsp.generate_record().len(), 2);
+
+        let missing = dsp.get_profile("a").unwrap().missing.as_ref().unwrap();
+        assert_eq!(missing.empty, 1);
+        assert_eq!(missing.nulls, 2);
+
+        let mut nulls = 0;
+        let mut empty = 0;
+        for _ in 0..200 {
+            match &dsp.try_generate_json_record(true).unwrap()["a"] {
+                Value::Null => nulls += 1,
+                Value::String(s) if s.is_empty() => empty += 1,
+                other => panic!("Expected null or an empty string, but got {:?}", other),
+            }
+        }
+        assert!(nulls > 0);
+        assert!(empty > 0);
     }
 
     #[test]
-    // ensure DataSampleParser can analyze a csv formatted file
-    fn test_parse_csv_file_bad() {
+    // ensure the ndjson records are analyzed in chunks
+    fn test_analyze_ndjson_reader_chunks() {
+        let mut data = String::new();
+        for idx in 0..(STREAM_CHUNK_ROWS + 5) {
+            data.push_str(&format!(
+                "{{\"id\": {}, \"device\": {{\"os\": \"linux\"}}}}\n\n",
+                idx
+            ));
+        }
+
         let mut dsp = DataSampleParser::new();
+        dsp.try_analyze_ndjson(&data).unwrap();
 
+        assert_eq!(dsp.extract_headers(), vec!["id", "device.os"]);
         assert_eq!(
-            dsp.analyze_csv_file(&String::from("./badpath/sample-01.csv"), None)
-                .is_err(),
-            true
+            dsp.get_profile("id").unwrap().sizes.values().sum::<u32>(),
+            (STREAM_CHUNK_ROWS + 5) as u32
+        );
+        assert_eq!(
+            dsp.try_generate_json_record(false).unwrap()["device"]["os"],
+            Value::from("linux")
         );
     }
 
     #[test]
-    // ensure the DataSampleParser object can be saved to file
-    fn test_save() {
+    fn test_analyze_json_not_objects() {
         let mut dsp = DataSampleParser::new();
-        dsp.analyze_csv_file(&String::from("./tests/samples/sample-00.csv"), None)
-            .unwrap();
+
+        match dsp.try_analyze_ndjson("{\"id\": 1}\n[1, 2]\n") {
+            Err(TdgError::Analyze { message }) => {
+                assert_eq!(message, "The JSON record 2 isn't an object")
+            }
+            other => panic!("Expected an Analyze error, but got {:?}", other),
+        }
+        match dsp.try_analyze_json("[{\"id\": 1},") {
+            Err(TdgError::Json { .. }) => {}
+            other => panic!("Expected a Json error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    // ensure the nested shape is saved and restored with the profiles
+    fn test_json_shape_from_serialized() {
+        let mut dsp = DataSampleParser::new();
+        dsp.analyze_ndjson(
+            &std::fs::read_to_string("./tests/samples/sample-events.ndjson").unwrap(),
+        )
+        .unwrap();
+
+        let serialized = serde_json::to_string(&dsp).unwrap();
+        let mut restored = DataSampleParser::try_from_serialized(&serialized).unwrap();
+
+        assert_eq!(restored.json_shape, dsp.json_shape);
+        assert!(restored.try_generate_json_record(true).unwrap()["user"]["email"].is_string());
+    }
+
+    #[test]
+    // ensure headerless data with a sniffed dialect is analyzed and the dialect is used to generate data
+    fn test_analyze_csv_headerless_dialect() {
+        let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_data(
+            "~Aaron~|~Aaberg~|32\n~Beth~|~Aaby~|41\n~Carla~|~Aadland~|27\n",
+            None,
+        )
+        .unwrap();
 
         assert_eq!(
-            dsp.save(&String::from("./tests/samples/sample-00-dsp"))
-                .unwrap(),
-            true
+            dsp.extract_headers(),
+            vec!["column_1", "column_2", "column_3"]
         );
+        assert_eq!(dsp.get_profile("column_3").unwrap().pattern_total, 3);
+
+        let mut dialect = CsvDialect::new();
+        dialect.has_headers = false;
+        dialect.delimiter = b'|';
+        dialect.quote = b'~';
+        dsp.set_csv_dialect(Some(dialect));
+
+        let mut buffer = Vec::new();
+        dsp.try_generate_csv_writer(4, &mut buffer, None).unwrap();
+        let generated = String::from_utf8(buffer).unwrap();
+        assert_eq!(generated.lines().count(), 4);
+        assert!(generated.lines().all(|l| l.split('|').count() == 3));
+
+        // the delimiter argument overrides the dialect
+        let mut buffer = Vec::new();
+        dsp.try_generate_csv_writer(1, &mut buffer, Some(b';'))
+            .unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap().split(';').count(), 3);
     }
 
     #[test]
-    // ensure the DataSampleParser object can recognize the difference between realistic data and unrealistic generated data
-    fn test_levenshtein_test() {
+    // ensure the sniffed dialect handles comments, padded fields and short records
+    fn test_analyze_csv_sniffed_comments_and_trim() {
+        let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_data(
+            "name     ;city     \n# extract of 2019\nAaron    ;Boston   \nBeth     \nCarl     ;Austin   \n",
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(dsp.extract_headers(), vec!["name", "city"]);
+        assert_eq!(dsp.get_profile("name").unwrap().pattern_total, 3);
+        assert_eq!(dsp.get_profile("city").unwrap().pattern_total, 2);
+        assert!(dsp
+            .get_profile("city")
+            .unwrap()
+            .patterns
+            .contains_key("Cvccvc"));
+    }
+
+    #[test]
+    fn test_csv_dialect_from_serialized() {
         let mut dsp = DataSampleParser::new();
+        let mut dialect = CsvDialect::new();
+        dialect.comment = Some(b'#');
+        dsp.set_csv_dialect(Some(dialect.clone()));
+
+        let serialized = serde_json::to_string(&dsp).unwrap();
+        let restored = DataSampleParser::try_from_serialized(&serialized).unwrap();
+        assert_eq!(restored.get_csv_dialect(), Some(&dialect));
+    }
+
+    #[test]
+    // ensure the generated records have the same ratio of missing values per field as the sample data
+    fn test_generate_records_missing_values() {
+        let mut data = String::from("name,age\n");
+        for idx in 0..100 {
+            match idx % 4 {
+                0 => data.push_str("Aaron,\n"),
+                1 => data.push_str("Beth,NULL\n"),
+                _ => data.push_str(",32\n"),
+            }
+        }
+
+        let mut dsp = DataSampleParser::new_with_seed(3);
+        dsp.set_correlation(true);
+        dsp.analyze_csv_data(&data, None).unwrap();
+        assert_eq!(dsp.get_profile("name").unwrap().missing_rate(), 0.5);
+
+        let mut empty_names = 0;
+        for record in dsp.generate_records().take(2000) {
+            let record = record.unwrap();
+            if record[0].is_empty() {
+                empty_names += 1;
+                // the names were only missing when the age was present
+                assert_eq!(record[1], "32");
+            } else {
+                assert!(MissingValues::is_missing(&record[1]));
+            }
+        }
+        assert!((900..1100).contains(&empty_names));
+    }
+
+    #[test]
+    // ensure the demo dates are valid dates, (the pattern ##p##p#### allows 19/39/2016)
+    fn test_demo_date_valid() {
+        let dsp = DataSampleParser::new();
+
+        for _ in 0..200 {
+            let date = dsp.demo_date();
+            assert!(
+                chrono::NaiveDate::parse_from_str(&date, "%m/%d/%Y").is_ok(),
+                "{}",
+                date
+            );
+        }
+    }
+
+    #[test]
+    // ensure correlated date columns are still valid dates in the shifted range
+    fn test_generate_records_dates_correlated() {
+        let mut dsp = DataSampleParser::new_with_seed(9);
+        dsp.set_correlation(true);
+        dsp.analyze_csv_data(
+            "name,born\nAaron,1/31/1999\nBeth,12/1/2001\nCarla,6/15/2000\n",
+            None,
+        )
+        .unwrap();
+        dsp.set_date_shift(-365);
+
+        for record in dsp.generate_records().take(300) {
+            let record = record.unwrap();
+            let born = chrono::NaiveDate::parse_from_str(&record[1], "%-m/%-d/%Y").unwrap();
+            assert!(born >= chrono::NaiveDate::from_ymd_opt(1998, 1, 31).unwrap());
+            assert!(born <= chrono::NaiveDate::from_ymd_opt(2000, 12, 1).unwrap());
+        }
+    }
 
+    #[test]
+    // ensure the columns of semantic types are detected per field and generated as valid values
+    fn test_generate_records_semantic_types() {
+        let data = "id,email,card\n\
+            0f8fad5b-d9cb-469f-a165-70867728950e,aaron@example.com,4111111111111111\n\
+            7c9e6679-7425-40de-944b-e07fc1f90ae7,beth@example.com,5555555555554444\n";
+        let mut dsp = DataSampleParser::new_with_seed(4);
+        dsp.analyze_csv_data(data, None).unwrap();
+
+        let detectors: Vec<Option<String>> = dsp
+            .profiles
+            .values()
+            .map(|p| p.semantic.as_ref().map(|m| m.detector.clone()))
+            .collect();
         assert_eq!(
-            dsp.levenshtein_distance(&"kitten".to_string(), &"sitting".to_string()),
-            3 as usize
+            detectors,
+            vec![
+                Some("uuid".to_string()),
+                Some("email".to_string()),
+                Some("credit-card".to_string())
+            ]
         );
+
+        for record in dsp.generate_records().take(100) {
+            let record = record.unwrap();
+            assert_eq!(&record[0][14..15], "4");
+            assert!(record[1].ends_with("@example.com"));
+            assert!(crate::engine::semantic::detect(&record[2])
+                .is_some_and(|d| d.name() == "credit-card"));
+        }
     }
 
     #[test]
-    // ensure the DataSampleParser object can recognize the difference between realistic data and unrealistic generated data
-    fn test_realistic_data_test() {
-        let mut dsp = DataSampleParser::new();
+    // ensure the custom classes of chars are used by the Profiles of the fields and the joint model of the patterns
+    fn test_add_char_class_correlated() {
+        let mut dsp = DataSampleParser::new_with_seed(20);
+        dsp.set_correlation(true);
+        dsp.add_char_class(CharClass::chars('h', "0123456789abcdef", 0).unwrap())
+            .unwrap();
+        dsp.analyze_csv_data("color,name\nc0ffee,Coffee\nbada55,Badass\n", None)
+            .unwrap();
 
+        assert_eq!(dsp.get_profile("name").unwrap().char_classes.len(), 1);
         assert_eq!(
-            dsp.realistic_test(&"kitten".to_string(), &"sitting".to_string()),
-            76.92307692307692 as f64
+            dsp.get_correlation()
+                .unwrap()
+                .combinations
+                .keys()
+                .next()
+                .unwrap()[0],
+            "hhhhhh"
         );
+        for record in dsp.generate_records().take(50) {
+            let record = record.unwrap();
+            assert!(record[0].chars().all(|c| c.is_ascii_hexdigit()));
+        }
     }
 
     #[test]
-    // demo test
-    fn test_demo() {
-        let mut dsp = DataSampleParser::new();
-        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+    // ensure the novelty is applied to the profiles of the fields that are analyzed afterwards and is exported
+    fn test_set_novelty() {
+        let mut dsp = DataSampleParser::new_with_seed(22);
+        assert!(dsp.set_novelty(-1.0).is_err());
+        dsp.set_novelty(0.8).unwrap();
+        dsp.analyze_csv_data("name\nBert\nBerta\nBecall\nBart\n", None)
             .unwrap();
 
-        println!(
-            "My new name is {} {}",
-            dsp.generate_record()[0],
-            dsp.generate_record()[1]
+        assert_eq!(dsp.get_profile("name").unwrap().novelty, Some(0.8));
+        for record in dsp.generate_records().take(50) {
+            assert!(record.unwrap()[0].starts_with('B'));
+        }
+
+        let serialized = serde_json::to_string(&dsp).unwrap();
+        let restored = DataSampleParser::try_from_serialized(&serialized).unwrap();
+        assert_eq!(restored.get_profile("name").unwrap().novelty, Some(0.8));
+    }
+
+    #[test]
+    // ensure the custom null sentinel tokens are counted as missing values, written as null and exported
+    fn test_set_null_sentinels() {
+        let mut dsp = DataSampleParser::new_with_seed(16);
+        dsp.set_null_sentinels(Some(vec!["-".to_string()]));
+        dsp.analyze_csv_data("age,surname\n31,Nil\n-,None\n-,-\n45,Nil\n", None)
+            .unwrap();
+
+        assert_eq!(dsp.get_profile("age").unwrap().missing_rate(), 0.5);
+        assert_eq!(dsp.get_profile("surname").unwrap().missing_rate(), 0.25);
+        assert_eq!(dsp.get_profile("surname").unwrap().pattern_total, 3);
+        for _ in 0..50 {
+            let record = dsp.try_generate_json_record(true).unwrap();
+            assert!(record["age"].is_number() || record["age"].is_null());
+        }
+
+        let serialized = serde_json::to_string(&dsp).unwrap();
+        let restored = DataSampleParser::try_from_serialized(&serialized).unwrap();
+        assert_eq!(
+            restored.get_profile("age").unwrap().get_null_sentinels(),
+            vec!["-".to_string()]
         );
+    }
 
-        assert!(true);
+    #[test]
+    // ensure the values of a unique field aren't repeated, and the capacity is checked before generating
+    fn test_set_unique_correlated() {
+        let mut data = String::from("id,name\n");
+        for idx in 0..100 {
+            data.push_str(&format!("{},Aaron\n", 1000 + idx));
+        }
+        let mut dsp = DataSampleParser::new_with_seed(23);
+        dsp.set_unique("id", true);
+        dsp.set_correlation(true);
+        dsp.analyze_csv_data(&data, None).unwrap();
+        assert_eq!(dsp.get_profile("id").unwrap().capacity(), 100.0);
+        assert!(dsp.get_profile("name").unwrap().unique.is_none());
+
+        let mut buffer = Vec::new();
+        dsp.try_generate_csv_writer(60, &mut buffer, None).unwrap();
+        let generated = String::from_utf8(buffer).unwrap();
+        let ids: BTreeSet<&str> = generated
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').next().unwrap())
+            .collect();
+        assert_eq!(ids.len(), 60);
+
+        match dsp.check_capacity(41) {
+            Err(TdgError::UniqueExhausted {
+                field, generated, ..
+            }) => {
+                assert_eq!(field.as_deref(), Some("id"));
+                assert_eq!(generated, 60);
+            }
+            other => panic!("Expected a UniqueExhausted error, but got {:?}", other),
+        }
+
+        dsp.set_unique("id", false);
+        assert!(dsp.check_capacity(1000).is_ok());
     }
 
     #[test]
-    // ensure the DataSampleParser object can generate test data as a csv file
-    fn test_extract_headers_from_sample() {
-        let mut dsp = DataSampleParser::new();
+    // ensure the analyzed values aren't generated in privacy mode, including in correlated records
+    fn test_set_privacy_correlated() {
+        let mut dsp = DataSampleParser::new_with_seed(24);
+        dsp.set_privacy(Some(0));
+        dsp.set_correlation(true);
+        dsp.analyze_csv_file("./tests/samples/sample-01.csv", None)
+            .unwrap();
 
-        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
+        let mut reader = csv::Reader::from_path("./tests/samples/sample-01.csv").unwrap();
+        let analyzed: BTreeSet<String> = reader
+            .records()
+            .map(|r| r.unwrap()[1].to_string())
+            .collect();
+
+        for record in dsp.generate_records().take(100) {
+            assert!(!analyzed.contains(&record.unwrap()[1]));
+        }
+
+        dsp.set_privacy(None);
+        assert!(dsp.get_profile("lastname").unwrap().privacy.is_none());
+    }
+
+    #[test]
+    // ensure the key of the privacy mode isn't exported, and the restored profiles generate once it is supplied again
+    fn test_set_privacy_key() {
+        let mut dsp = DataSampleParser::new_with_seed(24);
+        dsp.set_privacy(Some(0));
+        dsp.set_privacy_key(0x5eed).unwrap();
+        dsp.analyze_csv_file("./tests/samples/sample-01.csv", None)
             .unwrap();
-        let headers = dsp.extract_headers();
 
-        assert_eq!(headers.len(), 2);
+        let serialized = serde_json::to_string(&dsp).unwrap();
+        assert!(!serialized.contains(&0x5eed_u128.to_string()));
+        let mut restored: DataSampleParser = serde_json::from_str(&serialized).unwrap();
+        assert!(restored.generate_records().take(1).all(|r| r.is_err()));
+
+        // the key is checked before the header is written
+        let mut csv = Vec::new();
+        match restored.try_generate_csv_writer(10, &mut csv, None) {
+            Err(TdgError::InvalidSetting { setting, .. }) => assert_eq!(setting, "privacy_key"),
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+        assert!(csv.is_empty());
+
+        assert!(restored.set_privacy_key(0x5eee).is_err());
+        restored.set_privacy_key(0x5eed).unwrap();
+        assert_eq!(restored.privacy_key(), Some(0x5eed));
+        assert!(restored.generate_records().take(10).all(|r| r.is_ok()));
     }
 
     #[test]
-    // ensure the DataSampleParser object can generate test data as a csv file
-    fn test_generate_csv_test_data_from_sample() {
+    // ensure the budget is split between the fields, so the counts of each field are noisier than those of a single profile
+    fn test_privatize_splits_budget() {
+        let mut data = String::from("first,last,city\n");
+        for _ in 0..100 {
+            data.push_str("Anna,Smith,Paris\n");
+        }
         let mut dsp = DataSampleParser::new();
+        dsp.analyze_csv_data(&data, None).unwrap();
+        let dp = DifferentialPrivacy::new(5.0, 1).unwrap();
 
-        dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None)
-            .unwrap();
-        dsp.generate_csv(
-            100,
-            &String::from("./tests/samples/generated-01b.csv"),
-            Some(b'|'),
-        )
-        .unwrap();
+        let spread = |counts: Vec<u32>| {
+            counts
+                .iter()
+                .map(|c| (*c as f64 - 100.0).abs())
+                .sum::<f64>()
+                / counts.len() as f64
+        };
+        let profile = dsp.get_profile("first").unwrap();
+        let alone = spread(
+            (0..300)
+                .map(|_| profile.privatize(&dp).unwrap().pattern_total)
+                .collect(),
+        );
+        let shared = spread(
+            (0..300)
+                .map(|_| dsp.privatize(&dp).unwrap().profiles["first"].pattern_total)
+                .collect(),
+        );
 
-        let generated_row_count =
-            match File::open(format!("{}", "./tests/samples/generated-01b.csv")) {
-                Err(_e) => 0,
-                Ok(f) => {
-                    let mut count = 0;
-                    let bf = BufReader::new(f);
+        assert!(shared > 2.0 * alone);
+    }
 
-                    for _line in bf.lines() {
-                        count += 1;
-                    }
+    #[test]
+    // ensure the noisy archive is exported in the differentially private export mode, without the rare combinations of patterns
+    fn test_set_export_privacy_correlated() {
+        let mut data = String::from("name,zip\n");
+        for idx in 0..30 {
+            data.push_str(&format!("Smith,{}\n", 10000 + idx % 3));
+        }
+        data.push_str("O'Brien,K1A 0B1\n");
 
-                    count
-                }
-            };
+        let mut dsp = DataSampleParser::new();
+        dsp.set_correlation(true);
+        dsp.analyze_csv_data(&data, None).unwrap();
+        assert!(dsp
+            .set_export_privacy(Some(DifferentialPrivacy {
+                epsilon: -1.0,
+                k_threshold: 2
+            }))
+            .is_err());
 
-        assert_eq!(generated_row_count, 101);
+        // with a huge budget, the noise is negligible
+        let dp = DifferentialPrivacy::new(1e9, 2).unwrap();
+        dsp.set_export_privacy(Some(dp)).unwrap();
+        dsp.try_save("./tests/samples/private-dsp").unwrap();
+
+        let mut shared = DataSampleParser::try_from_file("./tests/samples/private-dsp").unwrap();
+        let model = shared.get_correlation().unwrap().clone();
+        assert_eq!(model.combinations.len(), 1);
+        assert_eq!(
+            model
+                .combinations
+                .get(&vec!["Ccvcc".to_string(), "#####".to_string()]),
+            Some(&30)
+        );
+        assert_eq!(shared.get_profile("name").unwrap().patterns.len(), 1);
+        assert_eq!(shared.get_profile("zip").unwrap().patterns.len(), 1);
+        assert_eq!(shared.generate_record()[1].len(), 5);
+
+        // the analyzed statistics are kept to generate data
+        assert_eq!(dsp.get_correlation().unwrap().combinations.len(), 2);
+        assert_eq!(dsp.get_profile("zip").unwrap().patterns.len(), 2);
+    }
+
+    #[test]
+    // ensure numeric columns are generated from the distribution of the analyzed numbers and exported with the profile
+    fn test_generate_records_numeric_columns() {
+        let mut data = String::from("name,age,balance\n");
+        for idx in 0..200 {
+            data.push_str(&format!(
+                "Aaron,{},{}.{}\n",
+                18 + idx % 50,
+                idx * 25,
+                idx % 10
+            ));
+        }
+
+        let mut dsp = DataSampleParser::new_with_seed(5);
+        dsp.analyze_csv_data(&data, None).unwrap();
+        assert!(dsp.get_profile("name").unwrap().numeric.is_none());
+        assert_eq!(
+            dsp.get_profile("age")
+                .unwrap()
+                .numeric
+                .as_ref()
+                .unwrap()
+                .max,
+            67.0
+        );
+
+        for record in dsp.generate_records().take(500) {
+            let record = record.unwrap();
+            let age: u32 = record[1].parse().unwrap();
+            assert!((18..=67).contains(&age));
+            let balance: f64 = record[2].parse().unwrap();
+            assert!((0.0..=4975.9).contains(&balance));
+            assert_eq!(record[2].split_once('.').unwrap().1.len(), 1);
+        }
+
+        let exported: DataSampleParser =
+            serde_json::from_str(&serde_json::to_string(&dsp).unwrap()).unwrap();
+        assert_eq!(
+            exported.get_profile("balance").unwrap().numeric,
+            dsp.get_profile("balance").unwrap().numeric
+        );
     }
 }
@@ -0,0 +1,247 @@
+//!
+//! # CharClass
+//! A CharClass is a user-defined class of chars (e.g.: hex digits, Greek letters, currency symbols) with its own pattern symbol,
+//! so that the chars of the class are analyzed and generated as a class of their own instead of as one of the built-in classes
+//! (see PatternDefinition). The chars of a class are defined by a regex (that is matched against a single char) or by a set of chars.
+//!
+//! The custom classes are checked before the built-in classes, in ascending order of their priority
+//! (classes with the same priority are checked in the order they were added), so the first class that matches a char classifies it.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::engine::char_class::CharClass;
+//! use test_data_generation::engine::PatternDefinition;
+//!
+//! fn main() {
+//!     let mut pttrn_def = PatternDefinition::new();
+//!     pttrn_def.add_class(CharClass::chars('h', "0123456789abcdef", 0).unwrap()).unwrap();
+//!     pttrn_def.add_class(CharClass::regex('$', r"\p{Sc}", 1).unwrap()).unwrap();
+//!
+//!     assert_eq!(pttrn_def.symbolize("€ff0a Z"), "$hhhhSC");
+//! }
+//! ```
+
+use crate::errors::{TdgError, TdgResult};
+use once_cell::sync::OnceCell;
+use regex::Regex;
+
+/// The symbols of the built-in classes, (which can't be used by custom classes)
+pub const BUILT_IN_SYMBOLS: [char; 10] = ['@', 'C', 'c', 'V', 'v', '#', '~', 'S', 'p', 'L'];
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+/// Represents a user-defined class of chars with its pattern symbol
+pub struct CharClass {
+    /// The pattern symbol of the class, (e.g.: 'h' for hex digits)
+    pub symbol: char,
+    /// The regex that a char of the class matches, (e.g.: `\p{Greek}`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    /// The chars of the class, (e.g.: `0123456789abcdef`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chars: Option<String>,
+    /// The order the class is checked in, (lower first)
+    #[serde(default)]
+    pub priority: u8,
+    /// The compiled regex, (None if the regex isn't valid)
+    #[serde(skip)]
+    compiled: OnceCell<Option<Regex>>,
+}
+
+impl PartialEq for CharClass {
+    fn eq(&self, other: &Self) -> bool {
+        self.symbol == other.symbol
+            && self.regex == other.regex
+            && self.chars == other.chars
+            && self.priority == other.priority
+    }
+}
+
+impl CharClass {
+    /// Constructs a new CharClass of the chars that match a regex, returning an InvalidSetting error if the symbol is a symbol
+    /// of a built-in class or the regex isn't valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol: char` - The pattern symbol of the class.</br>
+    /// * `regex: &str` - The regex that a char of the class matches, (it is matched against a single char).</br>
+    /// * `priority: u8` - The order the class is checked in, (lower first).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::char_class::CharClass;
+    ///
+    /// fn main() {
+    ///     let greek = CharClass::regex('G', r"\p{Greek}", 0).unwrap();
+    ///     assert!(greek.matches('λ'));
+    ///
+    ///     assert!(CharClass::regex('G', "[", 0).is_err());
+    ///     assert!(CharClass::regex('#', "[0-9]", 0).is_err());
+    /// }
+    /// ```
+    pub fn regex(symbol: char, regex: &str, priority: u8) -> TdgResult<CharClass> {
+        let class = CharClass {
+            symbol,
+            regex: Some(regex.to_string()),
+            chars: None,
+            priority,
+            compiled: OnceCell::new(),
+        };
+        class.validate()?;
+
+        Ok(class)
+    }
+
+    /// Constructs a new CharClass of a set of chars, returning an InvalidSetting error if the symbol is a symbol
+    /// of a built-in class or the set is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol: char` - The pattern symbol of the class.</br>
+    /// * `chars: &str` - The chars of the class.</br>
+    /// * `priority: u8` - The order the class is checked in, (lower first).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::char_class::CharClass;
+    ///
+    /// fn main() {
+    ///     let hex = CharClass::chars('h', "0123456789abcdef", 0).unwrap();
+    ///     assert!(hex.matches('e'));
+    ///     assert!(!hex.matches('g'));
+    /// }
+    /// ```
+    pub fn chars(symbol: char, chars: &str, priority: u8) -> TdgResult<CharClass> {
+        let class = CharClass {
+            symbol,
+            regex: None,
+            chars: Some(chars.to_string()),
+            priority,
+            compiled: OnceCell::new(),
+        };
+        class.validate()?;
+
+        Ok(class)
+    }
+
+    /// This function checks that the class is defined by either a valid regex or a set of chars, and that its symbol isn't
+    /// a symbol of a built-in class, returning an InvalidSetting error if it isn't.
+    pub fn validate(&self) -> TdgResult<()> {
+        if BUILT_IN_SYMBOLS.contains(&self.symbol) {
+            return Err(invalid(format!(
+                "the symbol {:?} is the symbol of a built-in class",
+                self.symbol
+            )));
+        }
+
+        match (&self.regex, &self.chars) {
+            (Some(regex), None) => match Regex::new(regex) {
+                Ok(_) => Ok(()),
+                Err(e) => Err(invalid(format!(
+                    "the regex of the class {:?} isn't valid: {}",
+                    self.symbol, e
+                ))),
+            },
+            (None, Some(chars)) if !chars.is_empty() => Ok(()),
+            _ => Err(invalid(format!(
+                "the class {:?} must have either a regex or a (non-empty) set of chars",
+                self.symbol
+            ))),
+        }
+    }
+
+    /// This function returns true if the char belongs to the class.
+    pub fn matches(&self, c: char) -> bool {
+        match (&self.regex, &self.chars) {
+            (Some(regex), _) => self
+                .compiled
+                .get_or_init(|| Regex::new(regex).ok())
+                .as_ref()
+                .is_some_and(|re| re.is_match(c.encode_utf8(&mut [0; 4]))),
+            (None, Some(chars)) => chars.contains(c),
+            (None, None) => false,
+        }
+    }
+}
+
+/// This function adds a class to a list of classes in the order they are checked in, (a class replaces the class with the same symbol).
+/// Returns an InvalidSetting error if the class isn't valid.
+///
+/// # Arguments
+///
+/// * `classes: &mut Vec<CharClass>` - The classes, in the order they are checked in.</br>
+/// * `class: CharClass` - The class to add.</br>
+pub fn add_class(classes: &mut Vec<CharClass>, class: CharClass) -> TdgResult<()> {
+    class.validate()?;
+
+    classes.retain(|c| c.symbol != class.symbol);
+    let idx = classes.partition_point(|c| c.priority <= class.priority);
+    classes.insert(idx, class);
+
+    Ok(())
+}
+
+#[inline]
+fn invalid(message: String) -> TdgError {
+    TdgError::InvalidSetting {
+        setting: "char_classes".to_string(),
+        message,
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_class_priority_order() {
+        let mut classes = Vec::new();
+        add_class(&mut classes, CharClass::chars('x', "ab", 2).unwrap()).unwrap();
+        add_class(&mut classes, CharClass::chars('y', "bc", 1).unwrap()).unwrap();
+        add_class(&mut classes, CharClass::chars('z', "cd", 1).unwrap()).unwrap();
+        add_class(&mut classes, CharClass::chars('x', "a", 0).unwrap()).unwrap();
+
+        let symbols: Vec<char> = classes.iter().map(|c| c.symbol).collect();
+        assert_eq!(symbols, vec!['x', 'y', 'z']);
+        assert_eq!(classes[0].chars.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn test_char_class_invalid() {
+        assert!(CharClass::chars('h', "", 0).is_err());
+        assert!(CharClass::chars('C', "abc", 0).is_err());
+        match CharClass::regex('G', "(", 0) {
+            Err(TdgError::InvalidSetting { setting, .. }) => assert_eq!(setting, "char_classes"),
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+
+        let both: CharClass =
+            serde_json::from_str("{\"symbol\":\"h\",\"regex\":\"[a-f]\",\"chars\":\"abc\"}")
+                .unwrap();
+        assert!(both.validate().is_err());
+    }
+
+    #[test]
+    fn test_char_class_serialize() {
+        let class = CharClass::regex('G', r"\p{Greek}", 3).unwrap();
+        let serialized = serde_json::to_string(&class).unwrap();
+
+        assert_eq!(
+            serialized,
+            "{\"symbol\":\"G\",\"regex\":\"\\\\p{Greek}\",\"priority\":3}"
+        );
+        let deserialized: CharClass = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, class);
+        assert!(deserialized.matches('Ω'));
+    }
+}
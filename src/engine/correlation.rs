@@ -0,0 +1,321 @@
+//!
+//! # CorrelationModel
+//! The CorrelationModel is a joint model of the patterns of the fields of the analyzed records.
+//! It counts how many times each combination of patterns (one per field) occurred in the same record, so that the conditional
+//! dependencies between the fields (e.g.: the pattern of the zip code given the pattern of the city) can be reproduced
+//! while generating data, instead of generating each field independently.
+//! The number of combinations that are kept is bounded (see MAX_COMBINATIONS), so that the memory of the model doesn't grow
+//! with the number of records. When the limit is reached, only the most frequent combinations are kept, and the records of the
+//! other combinations are counted as uncorrelated records, which are generated field by field.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//! extern crate fastrand;
+//!
+//! use test_data_generation::engine::correlation::CorrelationModel;
+//!
+//! fn main() {
+//!     let mut model = CorrelationModel::new(vec!["country".to_string(), "zip".to_string()]);
+//!     model.learn(vec!["VCV".to_string(), "#####".to_string()]);
+//!     model.learn(vec!["VC".to_string(), "V#V S#V#".to_string()]);
+//!
+//!     // the zip pattern is always sampled together with the country pattern it was analyzed with
+//!     let mut rng = fastrand::Rng::with_seed(1);
+//!     let record = model.sample(&mut rng).unwrap();
+//!     assert!(record == ["VCV", "#####"] || record == ["VC", "V#V S#V#"]);
+//! }
+//! ```
+
+use indexmap::IndexMap;
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+type CombinationMap = IndexMap<Vec<String>, u32>;
+
+/// The number of combinations of patterns that are kept at most
+pub const MAX_COMBINATIONS: usize = 10_000;
+
+fn is_zero(count: &u32) -> bool {
+    *count == 0
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+/// Represents the joint model of the patterns of the fields of the analyzed records
+pub struct CorrelationModel {
+    /// The names of the fields, in the same order as the patterns of a combination
+    pub fields: Vec<String>,
+    /// The number of records in which each combination of patterns occurred
+    #[serde(with = "indexmap::map::serde_seq")]
+    pub combinations: CombinationMap,
+    /// The number of records whose combination of patterns wasn't kept, (they are generated field by field)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub uncorrelated: u32,
+}
+
+impl CorrelationModel {
+    /// Constructs a new CorrelationModel
+    ///
+    /// # Arguments
+    ///
+    /// * `fields: Vec<String>` - The names of the fields of the records to analyze.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::correlation::CorrelationModel;
+    ///
+    /// fn main() {
+    ///     let model = CorrelationModel::new(vec!["city".to_string(), "zip".to_string()]);
+    ///     assert!(model.is_empty());
+    /// }
+    /// ```
+    pub fn new(fields: Vec<String>) -> CorrelationModel {
+        CorrelationModel {
+            fields,
+            combinations: CombinationMap::new(),
+            uncorrelated: 0,
+        }
+    }
+
+    /// This function adds the combination of patterns of an analyzed record to the model.
+    /// Records that don't have a pattern for every field are ignored. When more than MAX_COMBINATIONS combinations have been added,
+    /// the less frequent half of them is discarded, and their records are counted as uncorrelated records.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns: Vec<String>` - The pattern of each field of the record, in the same order as the fields of the model.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::correlation::CorrelationModel;
+    ///
+    /// fn main() {
+    ///     let mut model = CorrelationModel::new(vec!["city".to_string(), "zip".to_string()]);
+    ///     model.learn(vec!["Cvccvc".to_string(), "#####".to_string()]);
+    ///     model.learn(vec!["Cvccvc".to_string(), "#####".to_string()]);
+    ///
+    ///     assert_eq!(model.total(), 2);
+    /// }
+    /// ```
+    pub fn learn(&mut self, patterns: Vec<String>) {
+        if patterns.len() != self.fields.len() {
+            warn!(
+                "The record has {} patterns, but the correlation model has {} fields. The record was ignored.",
+                patterns.len(),
+                self.fields.len()
+            );
+            return;
+        }
+
+        AddAssign::add_assign(self.combinations.entry(patterns).or_insert(0), 1);
+
+        if self.combinations.len() > MAX_COMBINATIONS {
+            self.prune(MAX_COMBINATIONS / 2);
+        }
+    }
+
+    // keeps the most frequent combinations, and counts the records of the others as uncorrelated records
+    fn prune(&mut self, keep: usize) {
+        self.combinations.sort_by(|_, a, _, b| b.cmp(a));
+        for (_, count) in self.combinations.drain(keep..) {
+            AddAssign::add_assign(&mut self.uncorrelated, count);
+        }
+    }
+
+    /// This function returns true if no records have been added to the model.
+    pub fn is_empty(&self) -> bool {
+        self.combinations.is_empty()
+    }
+
+    /// This function returns the number of records that have been added to the model, (including the uncorrelated records).
+    pub fn total(&self) -> u32 {
+        self.combinations.values().sum::<u32>() + self.uncorrelated
+    }
+
+    /// This function returns the number of times each pattern of a field occurred in the records where another field had the specified pattern,
+    /// (i.e.: the conditional distribution of the patterns of `field` given the pattern of `given_field`).
+    ///
+    /// # Arguments
+    ///
+    /// * `field: &str` - The name of the field whose patterns are counted.</br>
+    /// * `given_field: &str` - The name of the field that the patterns are conditioned on.</br>
+    /// * `given_pattern: &str` - The pattern of `given_field`.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::correlation::CorrelationModel;
+    ///
+    /// fn main() {
+    ///     let mut model = CorrelationModel::new(vec!["city".to_string(), "zip".to_string()]);
+    ///     model.learn(vec!["Cvccvc".to_string(), "#####".to_string()]);
+    ///     model.learn(vec!["Cvccvc".to_string(), "#####-####".to_string()]);
+    ///     model.learn(vec!["Cvcvcv".to_string(), "V#V S#V#".to_string()]);
+    ///
+    ///     let zips = model.conditional("zip", "city", "Cvccvc");
+    ///     assert_eq!(zips.len(), 2);
+    ///     assert_eq!(zips.get("#####"), Some(&1));
+    /// }
+    /// ```
+    pub fn conditional(
+        &self,
+        field: &str,
+        given_field: &str,
+        given_pattern: &str,
+    ) -> BTreeMap<String, u32> {
+        let mut counts = BTreeMap::new();
+
+        if let (Some(idx), Some(given_idx)) = (self.position(field), self.position(given_field)) {
+            for (combination, count) in self.combinations.iter() {
+                if combination[given_idx] == given_pattern {
+                    AddAssign::add_assign(
+                        counts.entry(combination[idx].clone()).or_insert(0),
+                        *count,
+                    );
+                }
+            }
+        }
+
+        counts
+    }
+
+    /// This function chooses a combination of patterns at random, weighted by the number of records in which it occurred.
+    /// Returns None if no records have been added to the model, or if an uncorrelated record was chosen, (see MAX_COMBINATIONS).
+    ///
+    /// # Arguments
+    ///
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate fastrand;
+    ///
+    /// use test_data_generation::engine::correlation::CorrelationModel;
+    ///
+    /// fn main() {
+    ///     let mut model = CorrelationModel::new(vec!["city".to_string(), "zip".to_string()]);
+    ///     let mut rng = fastrand::Rng::new();
+    ///     assert!(model.sample(&mut rng).is_none());
+    ///
+    ///     model.learn(vec!["Cvccvc".to_string(), "#####".to_string()]);
+    ///     assert_eq!(model.sample(&mut rng).unwrap()[1], "#####");
+    /// }
+    /// ```
+    pub fn sample(&self, rng: &mut fastrand::Rng) -> Option<&[String]> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = rng.u32(0..total);
+        for (combination, count) in self.combinations.iter() {
+            if pick < *count {
+                return Some(combination);
+            }
+            pick -= count;
+        }
+
+        None
+    }
+
+    /// This function returns the position of the field in the combinations (if the model has the field).
+    pub fn position(&self, field: &str) -> Option<usize> {
+        self.fields.iter().position(|f| f == field)
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn city_zip_model() -> CorrelationModel {
+        let mut model = CorrelationModel::new(vec!["city".to_string(), "zip".to_string()]);
+        for _ in 0..3 {
+            model.learn(vec!["Cvccvc".to_string(), "#####".to_string()]);
+        }
+        model.learn(vec!["Cvcvcv".to_string(), "V#V S#V#".to_string()]);
+        model
+    }
+
+    #[test]
+    fn test_correlation_learn_ignores_incomplete_records() {
+        let mut model = city_zip_model();
+        model.learn(vec!["Cvccvc".to_string()]);
+
+        assert_eq!(model.total(), 4);
+        assert_eq!(model.combinations.len(), 2);
+    }
+
+    #[test]
+    fn test_correlation_learn_bounded() {
+        let mut model = CorrelationModel::new(vec!["id".to_string(), "city".to_string()]);
+        for _ in 0..3 {
+            model.learn(vec!["#".to_string(), "Cvccvc".to_string()]);
+        }
+        for n in 0..MAX_COMBINATIONS {
+            model.learn(vec![n.to_string(), "Cvccvc".to_string()]);
+        }
+
+        // the frequent combination is kept, and the others are counted as uncorrelated records
+        assert!(model.combinations.len() <= MAX_COMBINATIONS);
+        assert_eq!(
+            model
+                .combinations
+                .get(&vec!["#".to_string(), "Cvccvc".to_string()]),
+            Some(&3)
+        );
+        assert_eq!(model.total(), 3 + MAX_COMBINATIONS as u32);
+        assert!(model.uncorrelated > 0);
+
+        let mut rng = fastrand::Rng::with_seed(3);
+        assert!((0..100).any(|_| model.sample(&mut rng).is_none()));
+    }
+
+    #[test]
+    fn test_correlation_conditional() {
+        let model = city_zip_model();
+
+        let zips = model.conditional("zip", "city", "Cvcvcv");
+        assert_eq!(zips.len(), 1);
+        assert_eq!(zips.get("V#V S#V#"), Some(&1));
+        assert!(model.conditional("zip", "state", "CC").is_empty());
+    }
+
+    #[test]
+    fn test_correlation_sample_only_observed_combinations() {
+        let model = city_zip_model();
+        let mut rng = fastrand::Rng::with_seed(42);
+
+        for _ in 0..100 {
+            let combination = model.sample(&mut rng).unwrap();
+            assert!(model.combinations.contains_key(combination));
+        }
+    }
+
+    #[test]
+    fn test_correlation_serialize() {
+        let model = city_zip_model();
+        let serialized = serde_json::to_string(&model).unwrap();
+
+        assert_eq!(
+            serialized,
+            "{\"fields\":[\"city\",\"zip\"],\"combinations\":[[[\"Cvccvc\",\"#####\"],3],[[\"Cvcvcv\",\"V#V S#V#\"],1]]}"
+        );
+        assert_eq!(
+            serde_json::from_str::<CorrelationModel>(&serialized).unwrap(),
+            model
+        );
+    }
+}
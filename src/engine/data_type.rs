@@ -0,0 +1,273 @@
+//!
+//! # DataType
+//! The DataType is the type of the values of a field (e.g.: numbers, booleans) that is inferred while analyzing the values.
+//! It is used to output generated values as native types (e.g.: JSON numbers and booleans) instead of strings.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::engine::data_type::DataType;
+//!
+//! fn main() {
+//!     let mut data_type = None;
+//!
+//!     for value in ["12", "7.5", "-3"].iter() {
+//!         data_type = DataType::merge(data_type, DataType::of(value));
+//!     }
+//!
+//!     assert_eq!(data_type, Some(DataType::Decimal));
+//! }
+//! ```
+
+use crate::engine::missing::{MissingValues, JSON_NULL, NULL_SENTINELS};
+use serde_json::Value;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// Represents the type of the values of a field
+pub enum DataType {
+    /// true or false, (case insensitive)
+    Boolean,
+    /// whole numbers without leading zeros, (e.g.: 42, -7)
+    Integer,
+    /// numbers with a fractional part, (e.g.: 3.14, -0.5)
+    Decimal,
+    /// any other text, (e.g.: names, zip codes with leading zeros)
+    String,
+}
+
+impl DataType {
+    /// This function returns the type of a value
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The value to classify.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::data_type::DataType;
+    ///
+    /// fn main() {
+    ///     assert_eq!(DataType::of("TRUE"), DataType::Boolean);
+    ///     assert_eq!(DataType::of("-42"), DataType::Integer);
+    ///     assert_eq!(DataType::of("3.14"), DataType::Decimal);
+    ///     // leading zeros would be lost in a number
+    ///     assert_eq!(DataType::of("02101"), DataType::String);
+    /// }
+    /// ```
+    pub fn of(value: &str) -> DataType {
+        if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+            return DataType::Boolean;
+        }
+
+        let unsigned = value.strip_prefix('-').unwrap_or(value);
+        let (whole, fraction) = match unsigned.split_once('.') {
+            Some((w, f)) => (w, Some(f)),
+            None => (unsigned, None),
+        };
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+
+        if !is_digits(whole) || (whole.len() > 1 && whole.starts_with('0')) {
+            return DataType::String;
+        }
+
+        match fraction {
+            None => DataType::Integer,
+            Some(f) if is_digits(f) => DataType::Decimal,
+            Some(_) => DataType::String,
+        }
+    }
+
+    /// This function returns the type that describes the values of both types, (e.g.: integers and decimals are decimals).
+    ///
+    /// # Arguments
+    ///
+    /// * `current: Option<DataType>` - The type of the values so far, (None if there are no values yet).</br>
+    /// * `other: DataType` - The type of the next value.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::data_type::DataType;
+    ///
+    /// fn main() {
+    ///     assert_eq!(DataType::merge(None, DataType::Integer), Some(DataType::Integer));
+    ///     assert_eq!(DataType::merge(Some(DataType::Integer), DataType::Boolean), Some(DataType::String));
+    /// }
+    /// ```
+    pub fn merge(current: Option<DataType>, other: DataType) -> Option<DataType> {
+        Some(match current {
+            None => other,
+            Some(c) if c == other => c,
+            Some(DataType::Integer) | Some(DataType::Decimal)
+                if other == DataType::Integer || other == DataType::Decimal =>
+            {
+                DataType::Decimal
+            }
+            Some(_) => DataType::String,
+        })
+    }
+
+    /// This function converts a generated value to a JSON number or boolean if the field has that type, otherwise to a JSON string.
+    /// Empty values of numeric and boolean fields, and null sentinel tokens (e.g.: NULL, N/A) of typed fields are converted to null,
+    /// as well as JSON nulls, (see engine::missing::JSON_NULL).
+    ///
+    /// # Arguments
+    ///
+    /// * `data_type: Option<DataType>` - The type of the values of the field, (None if the values aren't typed).</br>
+    /// * `value: String` - The generated value.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate serde_json;
+    ///
+    /// use serde_json::Value;
+    /// use test_data_generation::engine::data_type::DataType;
+    ///
+    /// fn main() {
+    ///     assert_eq!(DataType::typed_value(Some(DataType::Integer), "42".to_string()), Value::from(42));
+    ///     assert_eq!(DataType::typed_value(None, "42".to_string()), Value::from("42"));
+    /// }
+    /// ```
+    pub fn typed_value(data_type: Option<DataType>, value: String) -> Value {
+        Self::typed_value_in(data_type, value, &NULL_SENTINELS)
+    }
+
+    /// This function converts a generated value like typed_value(), but with the null sentinel tokens of the field, (see Profile::set_null_sentinels()).
+    ///
+    /// # Arguments
+    ///
+    /// * `data_type: Option<DataType>` - The type of the values of the field, (None if the values aren't typed).</br>
+    /// * `value: String` - The generated value.</br>
+    /// * `sentinels: &[S]` - The tokens that represent a null value.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate serde_json;
+    ///
+    /// use serde_json::Value;
+    /// use test_data_generation::engine::data_type::DataType;
+    ///
+    /// fn main() {
+    ///     assert_eq!(DataType::typed_value_in(Some(DataType::Integer), "-".to_string(), &["-"]), Value::Null);
+    ///     assert_eq!(DataType::typed_value_in(Some(DataType::String), "\u{0}".to_string(), &["-"]), Value::Null);
+    /// }
+    /// ```
+    pub fn typed_value_in<S: AsRef<str>>(
+        data_type: Option<DataType>,
+        value: String,
+        sentinels: &[S],
+    ) -> Value {
+        if value == JSON_NULL {
+            return Value::Null;
+        }
+
+        let typed = match data_type {
+            // the chars of true and false can be mixed while generating (e.g.: "frue"), but their lengths can't
+            Some(DataType::Boolean) => match value.chars().count() {
+                4 => Some(Value::Bool(true)),
+                5 => Some(Value::Bool(false)),
+                _ => None,
+            },
+            Some(DataType::Integer) => value.parse::<i64>().ok().map(Value::from),
+            Some(DataType::Decimal) => value
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number),
+            _ => None,
+        };
+
+        match typed {
+            Some(v) => v,
+            None if value.is_empty() && data_type.is_some_and(|t| t != DataType::String) => {
+                Value::Null
+            }
+            None if data_type.is_some() && MissingValues::is_sentinel_in(&value, sentinels) => {
+                Value::Null
+            }
+            None => Value::String(value),
+        }
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_type_of() {
+        assert_eq!(DataType::of("false"), DataType::Boolean);
+        assert_eq!(DataType::of("0"), DataType::Integer);
+        assert_eq!(DataType::of("-0.5"), DataType::Decimal);
+        assert_eq!(DataType::of("1."), DataType::String);
+        assert_eq!(DataType::of(".5"), DataType::String);
+        assert_eq!(DataType::of("1e5"), DataType::String);
+        assert_eq!(DataType::of("-"), DataType::String);
+        assert_eq!(DataType::of("NaN"), DataType::String);
+    }
+
+    #[test]
+    fn test_data_type_merge() {
+        assert_eq!(
+            DataType::merge(Some(DataType::Decimal), DataType::Integer),
+            Some(DataType::Decimal)
+        );
+        assert_eq!(
+            DataType::merge(Some(DataType::Boolean), DataType::Boolean),
+            Some(DataType::Boolean)
+        );
+        assert_eq!(
+            DataType::merge(Some(DataType::String), DataType::Integer),
+            Some(DataType::String)
+        );
+    }
+
+    #[test]
+    fn test_data_type_typed_value() {
+        assert_eq!(
+            DataType::typed_value(Some(DataType::Integer), String::new()),
+            Value::Null
+        );
+        assert_eq!(
+            DataType::typed_value(Some(DataType::String), String::new()),
+            Value::from("")
+        );
+        assert_eq!(
+            DataType::typed_value(Some(DataType::Decimal), "-0.5".to_string()),
+            Value::from(-0.5)
+        );
+        assert_eq!(
+            DataType::typed_value(Some(DataType::String), "N/A".to_string()),
+            Value::Null
+        );
+        assert_eq!(
+            DataType::typed_value(None, "N/A".to_string()),
+            Value::from("N/A")
+        );
+        assert_eq!(
+            DataType::typed_value(Some(DataType::Boolean), "yes".to_string()),
+            Value::from("yes")
+        );
+        assert_eq!(
+            DataType::typed_value(Some(DataType::Boolean), "frue".to_string()),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            DataType::typed_value(Some(DataType::Boolean), "FALSE".to_string()),
+            Value::Bool(false)
+        );
+    }
+}
@@ -0,0 +1,617 @@
+//!
+//! # DateTimeModel
+//! The DateTimeModel is the format and the range of the dates and timestamps of a field, so that only calendar-valid dates
+//! (e.g.: no 02/30/2017) within the range of the analyzed dates are generated, in the format they were analyzed in.
+//! The common date formats and ISO-8601 timestamps (with or without a time zone offset) are detected, and epoch seconds
+//! are detected if they are enabled, (since any number of 9 or 10 digits, e.g.: an id, looks like epoch seconds).
+//! A format is only detected if it writes every analyzed value exactly as it was analyzed, (e.g.: `01/04/2017` doesn't match `%-m/%-d/%Y`),
+//! and ambiguous formats (e.g.: `%m/%d/%Y` and `%d/%m/%Y`) are narrowed down as more values are analyzed.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//! extern crate fastrand;
+//!
+//! use test_data_generation::engine::date_time::DateTimeModel;
+//!
+//! fn main() {
+//!     let mut model = DateTimeModel::detect("01/04/2017").unwrap();
+//!     assert!(model.learn("12/21/2017"));
+//!
+//!     assert_eq!(model.format(), "%m/%d/%Y");
+//!     assert_eq!(model.range(), ("01/04/2017".to_string(), "12/21/2017".to_string()));
+//!
+//!     let mut rng = fastrand::Rng::with_seed(1);
+//!     println!("generated date: {}", model.sample(0, &mut rng));
+//! }
+//! ```
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+/// The detected formats of dates and timestamps, (in order of preference for values that match several formats)
+pub const FORMATS: [&str; 27] = [
+    // ISO-8601
+    "%Y-%m-%dT%H:%M:%S%.3f%:z",
+    "%Y-%m-%dT%H:%M:%S%:z",
+    "%Y-%m-%dT%H:%M:%S%.3fZ",
+    "%Y-%m-%dT%H:%M:%SZ",
+    "%Y-%m-%dT%H:%M:%S%.3f",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M:%S%:z",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    // US (month first) and European (day first)
+    "%m/%d/%Y %H:%M:%S",
+    "%m/%d/%Y %H:%M",
+    "%m/%d/%Y",
+    "%-m/%-d/%Y",
+    "%m/%d/%y",
+    "%d/%m/%Y %H:%M:%S",
+    "%d/%m/%Y",
+    "%-d/%-m/%Y",
+    "%m-%d-%Y",
+    "%d-%m-%Y",
+    "%d.%m.%Y",
+    // month names and RFC 2822
+    "%d %b %Y",
+    "%b %d, %Y",
+    "%B %d, %Y",
+    "%a, %d %b %Y %H:%M:%S %z",
+    "%a, %d %b %Y %H:%M:%S GMT",
+];
+/// The format of epoch seconds, which is only detected if it is enabled, (see DateTimeModel::detect_with())
+pub const EPOCH_FORMAT: &str = "%s";
+
+/// The number of seconds in a day
+const DAY: i64 = 86_400;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents a format that matches every analyzed value, with the range of the values in that format
+pub struct DateFormat {
+    /// The format, (see chrono::format::strftime)
+    pub format: String,
+    /// The earliest analyzed date and time, (in seconds since 1970-01-01T00:00:00 of the time zone of the value)
+    pub min: i64,
+    /// The latest analyzed date and time, (in seconds since 1970-01-01T00:00:00 of the time zone of the value)
+    pub max: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents the format and the range of the dates and timestamps of a field
+pub struct DateTimeModel {
+    /// The formats that match every analyzed value, (in order of preference)
+    pub formats: Vec<DateFormat>,
+    /// The number of analyzed values
+    pub count: u32,
+    /// The number of times each time zone offset (in seconds east of UTC) was analyzed
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub offsets: BTreeMap<i32, u32>,
+}
+
+impl DateTimeModel {
+    /// This function detects the formats of a date or a timestamp, returning None if the value isn't a date in any of the detected formats.
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The value to analyze, (e.g.: 2017-01-04T10:30:00+01:00).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::date_time::DateTimeModel;
+    ///
+    /// fn main() {
+    ///     let model = DateTimeModel::detect("2017-01-04T10:30:00+01:00").unwrap();
+    ///     assert_eq!(model.format(), "%Y-%m-%dT%H:%M:%S%:z");
+    ///     assert_eq!(model.offsets.get(&3600), Some(&1));
+    ///
+    ///     assert!(DateTimeModel::detect("02/30/2017").is_none());
+    ///     assert!(DateTimeModel::detect("Aaron").is_none());
+    /// }
+    /// ```
+    pub fn detect(value: &str) -> Option<DateTimeModel> {
+        Self::detect_with(value, false)
+    }
+
+    /// This function detects the formats of a date or a timestamp like detect(), and also detects epoch seconds if they are enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The value to analyze, (e.g.: 1483525800).</br>
+    /// * `epoch_seconds: bool` - true to detect numbers of 9 or 10 digits as epoch seconds.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::date_time::DateTimeModel;
+    ///
+    /// fn main() {
+    ///     assert_eq!(DateTimeModel::detect_with("1483525800", true).unwrap().format(), "%s");
+    ///     assert!(DateTimeModel::detect_with("1483525800", false).is_none());
+    /// }
+    /// ```
+    pub fn detect_with(value: &str, epoch_seconds: bool) -> Option<DateTimeModel> {
+        let epoch: &[&str] = match epoch_seconds {
+            true => &[EPOCH_FORMAT],
+            false => &[],
+        };
+        let mut offset = None;
+        let formats: Vec<DateFormat> = FORMATS
+            .iter()
+            .chain(epoch.iter())
+            .filter_map(|format| {
+                let (timestamp, off) = Self::parse(value, format)?;
+                offset = offset.or(off);
+                Some(DateFormat {
+                    format: format.to_string(),
+                    min: timestamp,
+                    max: timestamp,
+                })
+            })
+            .collect();
+
+        if formats.is_empty() {
+            return None;
+        }
+
+        let mut model = DateTimeModel {
+            formats,
+            count: 1,
+            offsets: BTreeMap::new(),
+        };
+        if let Some(off) = offset {
+            model.offsets.insert(off, 1);
+        }
+
+        Some(model)
+    }
+
+    /// This function adds a value to the range of the formats it matches, and discards the formats it doesn't match.
+    /// Returns false if the value doesn't match any of the formats, (i.e.: the values aren't dates of one format).
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The value to analyze.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::date_time::DateTimeModel;
+    ///
+    /// fn main() {
+    ///     // both month first and day first
+    ///     let mut model = DateTimeModel::detect("01/04/2017").unwrap();
+    ///     assert_eq!(model.format(), "%m/%d/%Y");
+    ///
+    ///     // only day first
+    ///     assert!(model.learn("25/12/2017"));
+    ///     assert_eq!(model.format(), "%d/%m/%Y");
+    ///
+    ///     assert!(!model.learn("2017-12-25"));
+    /// }
+    /// ```
+    pub fn learn(&mut self, value: &str) -> bool {
+        let mut offset = None;
+        self.formats.retain_mut(
+            |date_format| match Self::parse(value, &date_format.format) {
+                Some((timestamp, off)) => {
+                    offset = offset.or(off);
+                    date_format.min = date_format.min.min(timestamp);
+                    date_format.max = date_format.max.max(timestamp);
+                    true
+                }
+                None => false,
+            },
+        );
+
+        if self.formats.is_empty() {
+            return false;
+        }
+
+        self.count += 1;
+        if let Some(off) = offset {
+            AddAssign::add_assign(self.offsets.entry(off).or_insert(0), 1);
+        }
+
+        true
+    }
+
+    /// This function returns the preferred format of the analyzed values, (see chrono::format::strftime).
+    pub fn format(&self) -> &str {
+        self.formats
+            .first()
+            .map(|f| f.format.as_str())
+            .unwrap_or_default()
+    }
+
+    /// This function returns the earliest and the latest analyzed values, in the preferred format.
+    pub fn range(&self) -> (String, String) {
+        match self.formats.first() {
+            Some(f) => {
+                let offset = self.common_offset();
+                (
+                    Self::write(f.min, 0, offset, &f.format),
+                    Self::write(f.max, 0, offset, &f.format),
+                )
+            }
+            None => (String::new(), String::new()),
+        }
+    }
+
+    /// This function widens the range of each format to whole months, (or to whole years if the range spans a year or more),
+    /// so that the earliest and the latest analyzed values aren't kept, (e.g.: before the model is exported, see Profile::privatize()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::date_time::DateTimeModel;
+    ///
+    /// fn main() {
+    ///     let mut model = DateTimeModel::detect("2017-01-04").unwrap();
+    ///     model.learn("2017-03-21");
+    ///     model.generalize();
+    ///
+    ///     assert_eq!(model.range(), ("2017-01-01".to_string(), "2017-03-31".to_string()));
+    /// }
+    /// ```
+    pub fn generalize(&mut self) {
+        for date_format in self.formats.iter_mut() {
+            let yearly = date_format.max - date_format.min >= 365 * DAY;
+            date_format.min = Self::period_start(date_format.min, yearly, 0);
+            // the last second of the period
+            date_format.max = Self::period_start(date_format.max, yearly, 1) - 1;
+        }
+    }
+
+    /// This function estimates the number of distinct values that can be generated, (the number of days, minutes, seconds
+    /// or milliseconds in the analyzed range, depending on the preferred format, times the number of analyzed time zone offsets).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::date_time::DateTimeModel;
+    ///
+    /// fn main() {
+    ///     let mut model = DateTimeModel::detect("2016-02-28").unwrap();
+    ///     model.learn("2016-03-01");
+    ///
+    ///     assert_eq!(model.capacity(), 3.0);
+    /// }
+    /// ```
+    pub fn capacity(&self) -> f64 {
+        let date_format = match self.formats.first() {
+            Some(f) => f,
+            None => return 0.0,
+        };
+        let format = date_format.format.as_str();
+
+        let unit = match Self::has_time(format) {
+            true if format.contains("%S") || format.contains("%s") => 1,
+            true => 60,
+            false => DAY,
+        };
+        let mut capacity =
+            (date_format.max.div_euclid(unit) - date_format.min.div_euclid(unit) + 1) as f64;
+        if format.contains("%.3f") {
+            capacity *= 1000.0;
+        }
+        if Self::has_offset(format) {
+            capacity *= self.offsets.len().max(1) as f64;
+        }
+
+        capacity
+    }
+
+    /// This function generates a calendar-valid date (or timestamp) within the analyzed range, in the preferred format.
+    /// Dates are chosen uniformly within the range, and the time zone offset is chosen by the number of times it was analyzed.
+    ///
+    /// # Arguments
+    ///
+    /// * `shift: i64` - The number of days to shift the range by, (e.g.: 365 to generate the dates a year later).</br>
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate fastrand;
+    ///
+    /// use test_data_generation::engine::date_time::DateTimeModel;
+    ///
+    /// fn main() {
+    ///     let mut model = DateTimeModel::detect("2016-02-28").unwrap();
+    ///     model.learn("2016-03-01");
+    ///     let mut rng = fastrand::Rng::new();
+    ///
+    ///     let generated = model.sample(0, &mut rng);
+    ///     assert!(["2016-02-28", "2016-02-29", "2016-03-01"].contains(&generated.as_str()));
+    ///
+    ///     assert_eq!(&model.sample(366, &mut rng)[0..4], "2017");
+    /// }
+    /// ```
+    pub fn sample(&self, shift: i64, rng: &mut fastrand::Rng) -> String {
+        let date_format = match self.formats.first() {
+            Some(f) => f,
+            None => return String::new(),
+        };
+        let format = date_format.format.as_str();
+
+        // dates without a time are chosen by day, so that the last day is as likely as the others
+        let timestamp = match Self::has_time(format) {
+            true => rng.i64(date_format.min..=date_format.max),
+            false => {
+                rng.i64(date_format.min.div_euclid(DAY)..=date_format.max.div_euclid(DAY)) * DAY
+            }
+        };
+        let millis = match format.contains("%.3f") {
+            true => rng.u32(0..1000),
+            false => 0,
+        };
+
+        Self::write(
+            timestamp.saturating_add(shift.saturating_mul(DAY)),
+            millis,
+            self.sample_offset(rng),
+            format,
+        )
+    }
+
+    /// This function returns the start of the month (or the year) of a date and time, or of the following month (or year) if ahead is 1.
+    fn period_start(timestamp: i64, yearly: bool, ahead: u32) -> i64 {
+        let date = match DateTime::from_timestamp(timestamp, 0) {
+            Some(d) => d.date_naive(),
+            None => return timestamp,
+        };
+        let (year, month) = match yearly {
+            true => (date.year() + ahead as i32, 1),
+            false => {
+                let month0 = date.month0() + ahead;
+                (date.year() + (month0 / 12) as i32, month0 % 12 + 1)
+            }
+        };
+
+        NaiveDate::from_ymd_opt(year, month, 1).map_or(timestamp, |d| {
+            d.and_time(NaiveTime::MIN).and_utc().timestamp()
+        })
+    }
+
+    /// This function chooses a time zone offset at random, weighted by the number of times it was analyzed, (0 if none were analyzed).
+    fn sample_offset(&self, rng: &mut fastrand::Rng) -> i32 {
+        let total: u32 = self.offsets.values().sum();
+        if total == 0 {
+            return 0;
+        }
+
+        let mut pick = rng.u32(0..total);
+        for (offset, count) in self.offsets.iter() {
+            if pick < *count {
+                return *offset;
+            }
+            pick -= count;
+        }
+
+        0
+    }
+
+    /// This function returns the time zone offset that was analyzed the most, (0 if none were analyzed).
+    fn common_offset(&self) -> i32 {
+        self.offsets
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(offset, _)| *offset)
+            .unwrap_or(0)
+    }
+
+    /// This function writes a date and time (in seconds since 1970-01-01T00:00:00) in the format,
+    /// with the time zone offset (if the format has an offset).
+    fn write(timestamp: i64, millis: u32, offset: i32, format: &str) -> String {
+        match DateTime::from_timestamp(timestamp, millis * 1_000_000) {
+            Some(d) if Self::has_offset(format) => {
+                Self::write_with_offset(&d.naive_utc(), offset, format)
+            }
+            Some(d) => d.naive_utc().format(format).to_string(),
+            None => String::new(),
+        }
+    }
+
+    fn write_with_offset(naive: &NaiveDateTime, offset: i32, format: &str) -> String {
+        match FixedOffset::east_opt(offset).and_then(|o| naive.and_local_timezone(o).single()) {
+            Some(d) => d.format(format).to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// This function parses a value in the format, returning its date and time (in seconds since 1970-01-01T00:00:00 of its time zone)
+    /// and its time zone offset, or None if the value doesn't match the format.
+    fn parse(value: &str, format: &str) -> Option<(i64, Option<i32>)> {
+        // only numbers of 9 or 10 digits are epoch seconds, (1973 to 2286) so that other numbers (e.g.: ages) aren't dates
+        if format == EPOCH_FORMAT
+            && !((9..=10).contains(&value.len()) && value.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return None;
+        }
+
+        let (naive, offset) = match (Self::has_offset(format), Self::has_time(format)) {
+            (true, _) => DateTime::parse_from_str(value, format)
+                .ok()
+                .map(|d| (d.naive_local(), Some(d.offset().local_minus_utc())))?,
+            (false, true) => (NaiveDateTime::parse_from_str(value, format).ok()?, None),
+            (false, false) => (
+                NaiveDate::parse_from_str(value, format)
+                    .ok()?
+                    .and_time(NaiveTime::MIN),
+                None,
+            ),
+        };
+
+        // only the formats that write the value exactly as it was analyzed, (e.g.: the same padding)
+        let written = match offset {
+            Some(off) => Self::write_with_offset(&naive, off, format),
+            None => naive.format(format).to_string(),
+        };
+        match written == value {
+            true => Some((naive.and_utc().timestamp(), offset)),
+            false => None,
+        }
+    }
+
+    fn has_offset(format: &str) -> bool {
+        format.contains("%z") || format.contains("%:z")
+    }
+
+    fn has_time(format: &str) -> bool {
+        format.contains("%H") || format.contains("%s")
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_time_detect_formats() {
+        let cases = [
+            ("2017-01-04", "%Y-%m-%d"),
+            ("2017-01-04T10:30:00", "%Y-%m-%dT%H:%M:%S"),
+            ("2017-01-04T10:30:00.125Z", "%Y-%m-%dT%H:%M:%S%.3fZ"),
+            ("2017-01-04T10:30:00-05:00", "%Y-%m-%dT%H:%M:%S%:z"),
+            ("2017-01-04 10:30", "%Y-%m-%d %H:%M"),
+            ("1/4/2017", "%-m/%-d/%Y"),
+            ("04.01.2017", "%d.%m.%Y"),
+            ("Jan 04, 2017", "%b %d, %Y"),
+            (
+                "Wed, 04 Jan 2017 10:30:00 +0000",
+                "%a, %d %b %Y %H:%M:%S %z",
+            ),
+        ];
+
+        for (value, format) in cases.iter() {
+            assert_eq!(DateTimeModel::detect(value).unwrap().format(), *format);
+        }
+
+        // epoch seconds are only detected if they are enabled, (e.g.: not for ids of 10 digits)
+        assert!(DateTimeModel::detect("1483525800").is_none());
+        assert_eq!(
+            DateTimeModel::detect_with("1483525800", true)
+                .unwrap()
+                .format(),
+            EPOCH_FORMAT
+        );
+        assert!(DateTimeModel::detect_with("42", true).is_none());
+
+        // not dates, (or not valid dates)
+        for value in [
+            "42",
+            "3.14",
+            "02101",
+            "2017-02-29",
+            "Thu, 04 Jan 2017 10:30:00 +0000",
+        ]
+        .iter()
+        {
+            assert!(DateTimeModel::detect(value).is_none(), "{}", value);
+        }
+    }
+
+    #[test]
+    // ensure only calendar-valid dates within the range are generated
+    fn test_date_time_sample_valid_dates() {
+        let mut model = DateTimeModel::detect("01/30/2016").unwrap();
+        assert!(model.learn("03/02/2016"));
+        let mut rng = fastrand::Rng::with_seed(4);
+
+        let mut leap_days = 0;
+        for _ in 0..2000 {
+            let generated = model.sample(0, &mut rng);
+            let date = NaiveDate::parse_from_str(&generated, "%m/%d/%Y").unwrap();
+            assert!(date >= NaiveDate::from_ymd_opt(2016, 1, 30).unwrap());
+            assert!(date <= NaiveDate::from_ymd_opt(2016, 3, 2).unwrap());
+            if generated == "02/29/2016" {
+                leap_days += 1;
+            }
+        }
+        assert!(leap_days > 0);
+    }
+
+    #[test]
+    fn test_date_time_sample_offsets_and_shift() {
+        let mut model = DateTimeModel::detect("2020-06-01T08:00:00+02:00").unwrap();
+        assert!(model.learn("2020-06-01T09:00:00+02:00"));
+        assert!(model.learn("2020-06-01T07:00:00+01:00"));
+        assert_eq!(model.count, 3);
+        assert_eq!(model.offsets.get(&7200), Some(&2));
+        let mut rng = fastrand::Rng::with_seed(2);
+
+        let mut offsets = BTreeMap::new();
+        for _ in 0..300 {
+            let generated = model.sample(-1, &mut rng);
+            assert!(generated.starts_with("2020-05-31T0"));
+            AddAssign::add_assign(offsets.entry(generated[19..].to_string()).or_insert(0), 1);
+        }
+        assert_eq!(offsets.len(), 2);
+        assert!(offsets["+02:00"] > offsets["+01:00"]);
+
+        // a value without an offset doesn't match the format
+        assert!(!model.learn("2020-06-01T10:00:00Z"));
+    }
+
+    #[test]
+    // ensure the generalized ranges are whole months or years, and the generated values stay within them
+    fn test_date_time_generalize() {
+        let mut model = DateTimeModel::detect("2017-12-04T10:30:00").unwrap();
+        model.learn("2017-12-21T08:15:00");
+        model.generalize();
+        assert_eq!(
+            model.range(),
+            (
+                "2017-12-01T00:00:00".to_string(),
+                "2017-12-31T23:59:59".to_string()
+            )
+        );
+
+        let mut years = DateTimeModel::detect("05/14/2015").unwrap();
+        years.learn("02/02/2017");
+        years.generalize();
+        assert_eq!(
+            years.range(),
+            ("01/01/2015".to_string(), "12/31/2017".to_string())
+        );
+
+        let mut rng = fastrand::Rng::with_seed(18);
+        for _ in 0..100 {
+            let generated = years.sample(0, &mut rng);
+            assert!(["2015", "2016", "2017"].contains(&&generated[6..]));
+        }
+    }
+
+    #[test]
+    fn test_date_time_serialize() {
+        let model = DateTimeModel::detect("2017-01-04").unwrap();
+        let serialized = serde_json::to_string(&model).unwrap();
+
+        assert_eq!(
+            serialized,
+            "{\"formats\":[{\"format\":\"%Y-%m-%d\",\"min\":1483488000,\"max\":1483488000}],\"count\":1}"
+        );
+        assert_eq!(
+            serde_json::from_str::<DateTimeModel>(&serialized).unwrap(),
+            model
+        );
+    }
+}
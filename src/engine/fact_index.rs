@@ -0,0 +1,552 @@
+//! # FactIndex
+//! The FactIndex stores the Facts of a Profile as weighted counts, indexed by the attributes that are used to find the Facts
+//! while generating data, (the pattern placeholder, index offset, starts with, ends with and prior key).
+//! Facts that share the same attributes and key are stored once with the number of times they were analyzed, so the Facts
+//! for a char in a pattern are found with a single lookup instead of scanning every Fact in the Profile.
+//!
+//! Archives that store the Facts in the legacy layout (a list of processors with lists of Facts) are still supported.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//! extern crate fastrand;
+//!
+//! use test_data_generation::engine::fact_index::FactIndex;
+//! use test_data_generation::engine::PatternDefinition;
+//!
+//! fn main() {
+//!     let mut index = FactIndex::new();
+//!     index.insert_all(&PatternDefinition::new().analyze("Ok").1);
+//!
+//!     // the only upper case vowel that has started an entity is 'O'
+//!     let mut rng = fastrand::Rng::new();
+//!     assert_eq!(index.select('V', 0, 1, 0, ' ', &mut rng), Some('O'));
+//! }
+//! ```
+
+use crate::engine::Fact;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::{AddAssign, RangeInclusive};
+
+/// The weight of a Fact that matches the position of the char being generated
+const INDEX_WEIGHT: u32 = 3;
+/// The additional weight of a Fact whose prior key matches the previously generated char
+const PRIOR_KEY_WEIGHT: u32 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Represents the attributes that are used to look up Facts in the FactIndex
+pub struct FactKey {
+    /// the PatternPlaceholder symbol that represents the type of key
+    pub pattern_placeholder: char,
+    /// the position of the key in the entity (zero based)
+    pub index_offset: u32,
+    /// indicates if the key is the first char in the entity (0=no, 1=yes)
+    pub starts_with: u32,
+    /// indicates if the key is the last char in the entity (0=no, 1=yes)
+    pub ends_with: u32,
+    /// the char that appears before (-1) the key in the entity
+    pub prior_key: Option<char>,
+}
+
+impl FactKey {
+    /// Constructs a new FactKey from the attributes of a Fact
+    ///
+    /// # Arguments
+    ///
+    /// * `fact: &Fact` - The Fact to take the attributes from.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::fact_index::FactKey;
+    /// use test_data_generation::engine::Fact;
+    ///
+    /// fn main() {
+    ///     let key = FactKey::from_fact(&Fact::new('r','c',0,0,2));
+    ///     assert_eq!(key.index_offset, 2);
+    /// }
+    /// ```
+    pub fn from_fact(fact: &Fact) -> FactKey {
+        FactKey {
+            pattern_placeholder: fact.pattern_placeholder,
+            index_offset: fact.index_offset,
+            starts_with: fact.starts_with,
+            ends_with: fact.ends_with,
+            prior_key: fact.prior_key,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents a Fact with the number of times it has been analyzed, (the archived layout of the FactIndex)
+pub struct WeightedFact {
+    /// the char that the fact defines (.e.g: 'a', '1', '%', etc.)
+    pub key: char,
+    /// the char that appears before (-1) the key in the entity
+    pub prior_key: Option<char>,
+    /// the PatternPlaceholder symbol that represents the type of key
+    pub pattern_placeholder: char,
+    /// indicates if the key is the first char in the entity (0=no, 1=yes)
+    pub starts_with: u32,
+    /// indicates if the key is the last char in the entity (0=no, 1=yes)
+    pub ends_with: u32,
+    /// the position of the key in the entity (zero based)
+    pub index_offset: u32,
+    /// the number of times the fact has been analyzed
+    pub count: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FactIndexLayout {
+    Legacy(Vec<Vec<Fact>>),
+    Indexed(Vec<WeightedFact>),
+}
+
+type KeyCounts = BTreeMap<char, u32>;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Represents the Facts of a Profile, indexed by the attributes that are used to generate data
+pub struct FactIndex {
+    facts: BTreeMap<FactKey, KeyCounts>,
+}
+
+impl FactIndex {
+    /// Constructs a new, empty FactIndex
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::fact_index::FactIndex;
+    ///
+    /// fn main() {
+    ///     let index = FactIndex::new();
+    ///     assert!(index.is_empty());
+    /// }
+    /// ```
+    pub fn new() -> FactIndex {
+        FactIndex {
+            facts: BTreeMap::new(),
+        }
+    }
+
+    /// This function adds a Fact to the index, increasing its count if it has already been added.
+    ///
+    /// # Arguments
+    ///
+    /// * `fact: &Fact` - The Fact to add.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::fact_index::FactIndex;
+    /// use test_data_generation::engine::Fact;
+    ///
+    /// fn main() {
+    ///     let mut index = FactIndex::new();
+    ///     index.insert(&Fact::new('r','c',0,0,2));
+    ///     index.insert(&Fact::new('r','c',0,0,2));
+    ///
+    ///     assert_eq!(index.len(), 1);
+    ///     assert_eq!(index.total(), 2);
+    /// }
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, fact: &Fact) {
+        self.insert_weighted(FactKey::from_fact(fact), fact.key, 1);
+    }
+
+    /// This function adds all the Facts of an analyzed entity to the index.
+    ///
+    /// # Arguments
+    ///
+    /// * `facts: &[Fact]` - The Facts to add.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::fact_index::FactIndex;
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let mut index = FactIndex::new();
+    ///     index.insert_all(&PatternDefinition::new().analyze("Word").1);
+    ///
+    ///     assert_eq!(index.total(), 4);
+    /// }
+    /// ```
+    pub fn insert_all(&mut self, facts: &[Fact]) {
+        for fact in facts {
+            self.insert(fact);
+        }
+    }
+
+    /// This function adds a key to the index with a count.
+    ///
+    /// # Arguments
+    ///
+    /// * `fact_key: FactKey` - The attributes of the Fact.</br>
+    /// * `key: char` - The char that the Fact defines.</br>
+    /// * `count: u32` - The number of times the Fact has been analyzed.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::fact_index::{FactIndex, FactKey};
+    /// use test_data_generation::engine::Fact;
+    ///
+    /// fn main() {
+    ///     let mut index = FactIndex::new();
+    ///     index.insert_weighted(FactKey::from_fact(&Fact::new('r','c',0,0,2)), 'r', 5);
+    ///
+    ///     assert_eq!(index.total(), 5);
+    /// }
+    /// ```
+    pub fn insert_weighted(&mut self, fact_key: FactKey, key: char, count: u32) {
+        AddAssign::add_assign(
+            self.facts
+                .entry(fact_key)
+                .or_default()
+                .entry(key)
+                .or_insert(0),
+            count,
+        );
+    }
+
+    /// This function returns the number of distinct Facts in the index
+    pub fn len(&self) -> usize {
+        self.facts.values().map(|keys| keys.len()).sum()
+    }
+
+    /// This function returns true if the index has no Facts
+    pub fn is_empty(&self) -> bool {
+        self.facts.is_empty()
+    }
+
+    /// This function returns the number of Facts that have been added to the index
+    pub fn total(&self) -> u32 {
+        self.facts.values().flat_map(|keys| keys.values()).sum()
+    }
+
+    /// This function returns an iterator over the Facts in the index, as the attributes of the Fact, the key and its count
+    pub fn iter(&self) -> impl Iterator<Item = (&FactKey, char, u32)> + '_ {
+        self.facts
+            .iter()
+            .flat_map(|(fact_key, keys)| keys.iter().map(move |(k, c)| (fact_key, *k, *c)))
+    }
+
+    /// This function chooses a char at random from the Facts that match the position of the char being generated.
+    /// Facts are weighted by their count, and Facts whose prior key matches the previously generated char are
+    /// given a better chance of being chosen.
+    /// Returns None if there are no matching Facts.
+    ///
+    /// # Arguments
+    ///
+    /// * `placeholder: char` - The pattern placeholder of the char to generate.</br>
+    /// * `index_offset: u32` - The position of the char in the pattern (zero based).</br>
+    /// * `starts_with: u32` - Indicates if the char is the first char in the pattern (0=no, 1=yes).</br>
+    /// * `ends_with: u32` - Indicates if the char is the last char in the pattern (0=no, 1=yes).</br>
+    /// * `prior_char: char` - The previously generated char, (' ' for the first char).</br>
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate fastrand;
+    ///
+    /// use test_data_generation::engine::fact_index::FactIndex;
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let mut index = FactIndex::new();
+    ///     index.insert_all(&PatternDefinition::new().analyze("Ok").1);
+    ///
+    ///     let mut rng = fastrand::Rng::new();
+    ///     assert_eq!(index.select('c', 1, 0, 1, 'O', &mut rng), Some('k'));
+    ///     assert_eq!(index.select('c', 2, 0, 1, 'O', &mut rng), None);
+    /// }
+    /// ```
+    pub fn select(
+        &self,
+        placeholder: char,
+        index_offset: u32,
+        starts_with: u32,
+        ends_with: u32,
+        prior_char: char,
+        rng: &mut fastrand::Rng,
+    ) -> Option<char> {
+        let from = FactKey {
+            pattern_placeholder: placeholder,
+            index_offset,
+            starts_with,
+            ends_with,
+            prior_key: None,
+        };
+        let to = FactKey {
+            prior_key: Some(char::MAX),
+            ..from
+        };
+
+        self.pick(from..=to, INDEX_WEIGHT, prior_char, rng)
+    }
+
+    /// This function selects a key at random for a pattern placeholder from the Facts at any position, (e.g.: for a char
+    /// in a synthesized pattern at a position where the placeholder wasn't analyzed). Facts whose prior key matches
+    /// the previously generated char are given a better chance of being chosen.
+    /// Returns None if there are no Facts for the pattern placeholder.
+    ///
+    /// # Arguments
+    ///
+    /// * `placeholder: char` - The pattern placeholder of the char to generate.</br>
+    /// * `prior_char: char` - The previously generated char, (' ' for the first char).</br>
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate fastrand;
+    ///
+    /// use test_data_generation::engine::fact_index::FactIndex;
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let mut index = FactIndex::new();
+    ///     index.insert_all(&PatternDefinition::new().analyze("Ok").1);
+    ///
+    ///     let mut rng = fastrand::Rng::new();
+    ///     assert_eq!(index.select('c', 2, 0, 1, 'k', &mut rng), None);
+    ///     assert_eq!(index.select_any('c', 'k', &mut rng), Some('k'));
+    ///     assert_eq!(index.select_any('v', 'k', &mut rng), None);
+    /// }
+    /// ```
+    pub fn select_any(
+        &self,
+        placeholder: char,
+        prior_char: char,
+        rng: &mut fastrand::Rng,
+    ) -> Option<char> {
+        let from = FactKey {
+            pattern_placeholder: placeholder,
+            index_offset: 0,
+            starts_with: 0,
+            ends_with: 0,
+            prior_key: None,
+        };
+        let to = FactKey {
+            pattern_placeholder: placeholder,
+            index_offset: u32::MAX,
+            starts_with: u32::MAX,
+            ends_with: u32::MAX,
+            prior_key: Some(char::MAX),
+        };
+
+        self.pick(from..=to, 1, prior_char, rng)
+    }
+
+    /// This function counts the distinct keys that can be selected for a pattern placeholder at a position in the pattern,
+    /// (see select()), or at any position if the position is None (see select_any()).
+    ///
+    /// # Arguments
+    ///
+    /// * `placeholder: char` - The pattern placeholder of the char to generate.</br>
+    /// * `position: Option<(u32, u32, u32)>` - The index offset, starts with and ends with of the char, (None for any position).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::fact_index::FactIndex;
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let mut index = FactIndex::new();
+    ///     index.insert_all(&PatternDefinition::new().analyze("Abc").1);
+    ///     index.insert_all(&PatternDefinition::new().analyze("Abd").1);
+    ///
+    ///     assert_eq!(index.count_keys('c', Some((2, 0, 1))), 2);
+    ///     assert_eq!(index.count_keys('c', Some((1, 0, 0))), 1);
+    ///     assert_eq!(index.count_keys('c', None), 3);
+    /// }
+    /// ```
+    pub fn count_keys(&self, placeholder: char, position: Option<(u32, u32, u32)>) -> usize {
+        let (from, to) = match position {
+            Some((index_offset, starts_with, ends_with)) => (
+                (index_offset, starts_with, ends_with),
+                (index_offset, starts_with, ends_with),
+            ),
+            None => ((0, 0, 0), (u32::MAX, u32::MAX, u32::MAX)),
+        };
+        let from = FactKey {
+            pattern_placeholder: placeholder,
+            index_offset: from.0,
+            starts_with: from.1,
+            ends_with: from.2,
+            prior_key: None,
+        };
+        let to = FactKey {
+            pattern_placeholder: placeholder,
+            index_offset: to.0,
+            starts_with: to.1,
+            ends_with: to.2,
+            prior_key: Some(char::MAX),
+        };
+
+        self.facts
+            .range(from..=to)
+            .flat_map(|(_, keys)| keys.keys())
+            .collect::<BTreeSet<&char>>()
+            .len()
+    }
+
+    // chooses a key of the Facts in the range at random, weighted by their counts
+    fn pick(
+        &self,
+        range: RangeInclusive<FactKey>,
+        weight: u32,
+        prior_char: char,
+        rng: &mut fastrand::Rng,
+    ) -> Option<char> {
+        let options: Vec<(char, u32)> = self
+            .facts
+            .range(range)
+            .flat_map(|(fact_key, keys)| {
+                let weight = if fact_key.prior_key.unwrap_or(' ') == prior_char {
+                    weight + PRIOR_KEY_WEIGHT
+                } else {
+                    weight
+                };
+                keys.iter().map(move |(k, c)| (*k, c * weight))
+            })
+            .collect();
+
+        let total: u32 = options.iter().map(|o| o.1).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = rng.u32(0..total);
+        for (k, weight) in options {
+            if pick < weight {
+                return Some(k);
+            }
+            pick -= weight;
+        }
+
+        None
+    }
+}
+
+impl Serialize for FactIndex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|(fact_key, key, count)| WeightedFact {
+            key,
+            prior_key: fact_key.prior_key,
+            pattern_placeholder: fact_key.pattern_placeholder,
+            starts_with: fact_key.starts_with,
+            ends_with: fact_key.ends_with,
+            index_offset: fact_key.index_offset,
+            count,
+        }))
+    }
+}
+
+impl<'de> Deserialize<'de> for FactIndex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut index = FactIndex::new();
+
+        match FactIndexLayout::deserialize(deserializer)? {
+            FactIndexLayout::Legacy(processors) => {
+                for facts in processors {
+                    index.insert_all(&facts);
+                }
+            }
+            FactIndexLayout::Indexed(facts) => {
+                for f in facts {
+                    let fact_key = FactKey {
+                        pattern_placeholder: f.pattern_placeholder,
+                        index_offset: f.index_offset,
+                        starts_with: f.starts_with,
+                        ends_with: f.ends_with,
+                        prior_key: f.prior_key,
+                    };
+                    index.insert_weighted(fact_key, f.key, f.count);
+                }
+            }
+        }
+
+        Ok(index)
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::PatternDefinition;
+
+    #[test]
+    fn test_fact_index_insert_counts() {
+        let mut index = FactIndex::new();
+        index.insert_all(&PatternDefinition::new().analyze("Anna").1);
+        index.insert_all(&PatternDefinition::new().analyze("Anna").1);
+
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.total(), 8);
+    }
+
+    #[test]
+    fn test_fact_index_select_prior_key() {
+        let mut index = FactIndex::new();
+        index.insert_all(&PatternDefinition::new().analyze("Ab").1);
+        index.insert_all(&PatternDefinition::new().analyze("Xc").1);
+        let mut rng = fastrand::Rng::with_seed(3);
+
+        let mut counts = BTreeMap::new();
+        for _ in 0..1000 {
+            let c = index.select('c', 1, 0, 1, 'A', &mut rng).unwrap();
+            AddAssign::add_assign(counts.entry(c).or_insert(0), 1);
+        }
+
+        // 'b' follows 'A', so it is weighted 5 to 3
+        assert!(counts[&'b'] > counts[&'c']);
+    }
+
+    #[test]
+    fn test_fact_index_deserialize_legacy() {
+        let legacy = "[[{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0}],[{\"key\":\"K\",\"prior_key\":\"O\",\"next_key\":null,\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1}],[],[]]";
+        let index: FactIndex = serde_json::from_str(legacy).unwrap();
+
+        let mut expected = FactIndex::new();
+        expected.insert_all(&PatternDefinition::new().analyze("OK").1);
+
+        assert_eq!(index, expected);
+    }
+
+    #[test]
+    fn test_fact_index_serialize() {
+        let mut index = FactIndex::new();
+        index.insert_all(&PatternDefinition::new().analyze("OK").1);
+        let serialized = serde_json::to_string(&index).unwrap();
+
+        assert_eq!(serialized, "[{\"key\":\"K\",\"prior_key\":\"O\",\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1,\"count\":1},{\"key\":\"O\",\"prior_key\":null,\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0,\"count\":1}]");
+        assert_eq!(
+            serde_json::from_str::<FactIndex>(&serialized).unwrap(),
+            index
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//!
+//! # JsonShape
+//! The JsonShape is the nested structure (objects, arrays and values) of the analyzed JSON records.
+//! The values are flattened into dotted field paths (e.g.: `user.address.city`), and the elements of an array share the path
+//! of the array followed by `[]` (e.g.: `tags[]`, `items[].sku`), so that the values of each path can be analyzed by a Profile.
+//! The lengths of the arrays are counted as well, so that generated records have the same nested shape as the analyzed records.
+//!
+//! _NOTE:_ Field names that contain a `.` can't be told apart from nested fields in a path.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//! extern crate serde_json;
+//! extern crate fastrand;
+//!
+//! use serde_json::{json, Value};
+//! use test_data_generation::engine::json_shape::JsonShape;
+//!
+//! fn main() {
+//!     let mut shape = JsonShape::Value;
+//!     let mut paths = Vec::new();
+//!
+//!     shape.learn("", &json!({"user": {"name": "Aaron"}, "tags": ["a", "b"]}), &mut |path, _value| paths.push(path));
+//!     assert_eq!(paths, vec!["user.name", "tags[]", "tags[]"]);
+//!
+//!     let mut rng = fastrand::Rng::with_seed(1);
+//!     let record = shape.generate("", &mut rng, &mut |path| Ok(Value::from(path))).unwrap();
+//!     assert_eq!(record, json!({"user": {"name": "user.name"}, "tags": ["tags[]", "tags[]"]}));
+//! }
+//! ```
+
+use crate::engine::missing::JSON_NULL;
+use crate::errors::TdgResult;
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+use std::ops::AddAssign;
+
+/// The suffix of the path of the elements of an array
+pub const ARRAY_SUFFIX: &str = "[]";
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+/// Represents the nested structure of the analyzed JSON records
+pub enum JsonShape {
+    /// a string, number, boolean or null that is generated by the Profile of its path, (or a structure that hasn't been analyzed yet)
+    Value,
+    /// an object with its fields in the order they were first analyzed
+    Object(#[serde(with = "indexmap::map::serde_seq")] IndexMap<String, JsonShape>),
+    /// an array with the number of times each length occurred and the shape of its elements
+    Array {
+        #[serde(with = "indexmap::map::serde_seq")]
+        lengths: IndexMap<usize, u32>,
+        items: Option<Box<JsonShape>>,
+    },
+}
+
+impl JsonShape {
+    /// This function adds the structure of an analyzed JSON value to the shape, and calls `leaf` with the path and the text of each
+    /// string, number, boolean or null in the value, (the text of null is JSON_NULL, see engine::missing).
+    /// Values that don't match the structure that was analyzed first at the same path (e.g.: an array where an object was analyzed) are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The dotted path of the value, (an empty path is the record).</br>
+    /// * `value: &Value` - The JSON value to analyze.</br>
+    /// * `leaf: &mut F` - The function that receives the path and the text of each value.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate serde_json;
+    ///
+    /// use serde_json::json;
+    /// use test_data_generation::engine::json_shape::JsonShape;
+    ///
+    /// fn main() {
+    ///     let mut shape = JsonShape::Value;
+    ///     let mut leaves = Vec::new();
+    ///
+    ///     shape.learn("", &json!({"id": 7, "items": [{"sku": "A-1"}]}), &mut |path, value| leaves.push((path, value)));
+    ///     assert_eq!(leaves, vec![("id".to_string(), "7".to_string()), ("items[].sku".to_string(), "A-1".to_string())]);
+    /// }
+    /// ```
+    pub fn learn<F: FnMut(String, String)>(&mut self, path: &str, value: &Value, leaf: &mut F) {
+        match value {
+            Value::Object(fields) => {
+                if *self == JsonShape::Value {
+                    *self = JsonShape::Object(IndexMap::new());
+                }
+                match self {
+                    JsonShape::Object(shapes) => {
+                        for (key, field) in fields.iter() {
+                            shapes.entry(key.clone()).or_insert(JsonShape::Value).learn(
+                                &Self::join(path, key),
+                                field,
+                                leaf,
+                            );
+                        }
+                    }
+                    _ => warn!(
+                        "The object at {} doesn't match the analyzed shape. It was ignored.",
+                        path
+                    ),
+                }
+            }
+            Value::Array(elements) => {
+                if *self == JsonShape::Value {
+                    *self = JsonShape::Array {
+                        lengths: IndexMap::new(),
+                        items: None,
+                    };
+                }
+                match self {
+                    JsonShape::Array { lengths, items } => {
+                        AddAssign::add_assign(lengths.entry(elements.len()).or_insert(0), 1);
+                        let items_path = format!("{}{}", path, ARRAY_SUFFIX);
+                        for element in elements.iter() {
+                            items
+                                .get_or_insert_with(|| Box::new(JsonShape::Value))
+                                .learn(&items_path, element, leaf);
+                        }
+                    }
+                    _ => warn!(
+                        "The array at {} doesn't match the analyzed shape. It was ignored.",
+                        path
+                    ),
+                }
+            }
+            _ => match self {
+                JsonShape::Value => leaf(path.to_string(), Self::text(value)),
+                // a missing nested structure
+                _ if value.is_null() => {}
+                _ => warn!(
+                    "The value at {} doesn't match the analyzed shape. It was ignored.",
+                    path
+                ),
+            },
+        }
+    }
+
+    /// This function generates a JSON value that has the shape, calling `value` with the path of each string, number, boolean or null to generate.
+    /// The length of each array is chosen at random, weighted by the number of times the length was analyzed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The dotted path of the shape, (an empty path is the record).</br>
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    /// * `value: &mut F` - The function that generates the value of a path.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate serde_json;
+    /// extern crate fastrand;
+    ///
+    /// use serde_json::{json, Value};
+    /// use test_data_generation::engine::json_shape::JsonShape;
+    ///
+    /// fn main() {
+    ///     let mut shape = JsonShape::Value;
+    ///     shape.learn("", &json!({"tags": ["a"]}), &mut |_path, _value| {});
+    ///
+    ///     let mut rng = fastrand::Rng::new();
+    ///     let record = shape.generate("", &mut rng, &mut |_path| Ok(Value::from("z"))).unwrap();
+    ///     assert_eq!(record, json!({"tags": ["z"]}));
+    /// }
+    /// ```
+    pub fn generate<F: FnMut(&str) -> TdgResult<Value>>(
+        &self,
+        path: &str,
+        rng: &mut fastrand::Rng,
+        value: &mut F,
+    ) -> TdgResult<Value> {
+        match self {
+            JsonShape::Value => value(path),
+            JsonShape::Object(shapes) => {
+                let mut fields = Map::new();
+                for (key, shape) in shapes.iter() {
+                    fields.insert(
+                        key.clone(),
+                        shape.generate(&Self::join(path, key), rng, value)?,
+                    );
+                }
+                Ok(Value::Object(fields))
+            }
+            JsonShape::Array { lengths, items } => {
+                let mut elements = Vec::new();
+                if let Some(shape) = items {
+                    let items_path = format!("{}{}", path, ARRAY_SUFFIX);
+                    for _ in 0..Self::sample_length(lengths, rng) {
+                        elements.push(shape.generate(&items_path, rng, value)?);
+                    }
+                }
+                Ok(Value::Array(elements))
+            }
+        }
+    }
+
+    /// This function returns the path of a field of the object at the path.
+    fn join(path: &str, key: &str) -> String {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    }
+
+    /// This function chooses a length at random, weighted by the number of times it was analyzed.
+    fn sample_length(lengths: &IndexMap<usize, u32>, rng: &mut fastrand::Rng) -> usize {
+        let total: u32 = lengths.values().sum();
+        if total == 0 {
+            return 0;
+        }
+
+        let mut pick = rng.u32(0..total);
+        for (length, count) in lengths.iter() {
+            if pick < *count {
+                return *length;
+            }
+            pick -= count;
+        }
+
+        0
+    }
+
+    /// This function returns the text of a string, number, boolean or null, (null is JSON_NULL, so it isn't an empty string).
+    fn text(value: &Value) -> String {
+        match value {
+            Value::String(s) => s.clone(),
+            Value::Null => JSON_NULL.to_string(),
+            other => other.to_string(),
+        }
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn learn_all(shape: &mut JsonShape, records: &[Value]) -> Vec<(String, String)> {
+        let mut leaves = Vec::new();
+        for record in records.iter() {
+            shape.learn("", record, &mut |path, value| leaves.push((path, value)));
+        }
+        leaves
+    }
+
+    #[test]
+    fn test_json_shape_learn_nested_arrays() {
+        let mut shape = JsonShape::Value;
+        let leaves = learn_all(
+            &mut shape,
+            &[json!({"matrix": [[1, 2], [3]], "items": [{"sku": "A", "qty": null}]})],
+        );
+
+        assert_eq!(
+            leaves,
+            vec![
+                ("matrix[][]".to_string(), "1".to_string()),
+                ("matrix[][]".to_string(), "2".to_string()),
+                ("matrix[][]".to_string(), "3".to_string()),
+                ("items[].sku".to_string(), "A".to_string()),
+                ("items[].qty".to_string(), JSON_NULL.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_shape_learn_ignores_mismatches() {
+        let mut shape = JsonShape::Value;
+        let leaves = learn_all(
+            &mut shape,
+            &[
+                json!({"address": {"city": "Boston"}}),
+                json!({"address": null}),
+                json!({"address": ["Boston"]}),
+                json!({"address": "Boston"}),
+            ],
+        );
+
+        assert_eq!(
+            leaves,
+            vec![("address.city".to_string(), "Boston".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_json_shape_generate_lengths() {
+        let mut shape = JsonShape::Value;
+        learn_all(
+            &mut shape,
+            &[json!({"tags": []}), json!({"tags": ["a", "b", "c"]})],
+        );
+        let mut rng = fastrand::Rng::with_seed(3);
+
+        for _ in 0..20 {
+            let record = shape
+                .generate("", &mut rng, &mut |_path| Ok(Value::from("x")))
+                .unwrap();
+            let length = record["tags"].as_array().unwrap().len();
+            assert!(length == 0 || length == 3);
+        }
+    }
+
+    #[test]
+    fn test_json_shape_serialize() {
+        let mut shape = JsonShape::Value;
+        learn_all(&mut shape, &[json!({"id": 1, "tags": ["a"]})]);
+        let serialized = serde_json::to_string(&shape).unwrap();
+
+        assert_eq!(
+            serialized,
+            "{\"object\":[[\"id\",\"value\"],[\"tags\",{\"array\":{\"lengths\":[[1,1]],\"items\":\"value\"}}]]}"
+        );
+        assert_eq!(
+            serde_json::from_str::<JsonShape>(&serialized).unwrap(),
+            shape
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! # MarkovChain
+//! The MarkovChain is an n-gram transition model of the characters in the analyzed entities.
+//! It is built from the same Facts that are applied to a Profile and is used while generating data to choose the next char
+//! based on the previous N generated chars (the `order`). If there are no transitions for the previous N chars,
+//! the model falls back to the previous N-1 chars, and so on.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//! extern crate fastrand;
+//!
+//! use test_data_generation::engine::markov::MarkovChain;
+//! use test_data_generation::engine::PatternDefinition;
+//!
+//! fn main() {
+//!     let mut chain = MarkovChain::new(2);
+//!     chain.learn(&PatternDefinition::new().analyze("Anna").1);
+//!
+//!     // after "An", the only lower case consonant that has been seen is 'n'
+//!     let mut rng = fastrand::Rng::with_seed(1);
+//!     assert_eq!(chain.next_char('c', &['A', 'n'], &mut rng), Some('n'));
+//! }
+//! ```
+
+use crate::engine::fact_index::FactIndex;
+use crate::engine::Fact;
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+/// The lowest order that is supported
+pub const MIN_ORDER: u8 = 1;
+/// The highest order that is supported
+pub const MAX_ORDER: u8 = 4;
+/// The char that marks the start of an entity in a context
+const START: char = '\u{2}';
+
+type TransitionMap = BTreeMap<String, BTreeMap<char, u32>>;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents an n-gram transition model of the chars of the analyzed entities
+pub struct MarkovChain {
+    /// The number of previous chars that are used to choose the next char (1 to 4)
+    pub order: u8,
+    /// The number of times a char followed a context, keyed by the pattern placeholder of the char followed by the context
+    pub transitions: TransitionMap,
+}
+
+impl MarkovChain {
+    /// Constructs a new MarkovChain
+    ///
+    /// # Arguments
+    ///
+    /// * `order: u8` - The number of previous chars to use when choosing the next char. It is limited to the range 1 to 4.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::markov::MarkovChain;
+    ///
+    /// fn main() {
+    ///     let chain = MarkovChain::new(3);
+    ///     assert_eq!(chain.order, 3);
+    /// }
+    /// ```
+    pub fn new(order: u8) -> MarkovChain {
+        MarkovChain {
+            order: order.clamp(MIN_ORDER, MAX_ORDER),
+            transitions: TransitionMap::new(),
+        }
+    }
+
+    /// This function adds the transitions of an analyzed entity to the model.
+    /// Transitions are stored for every order from 0 (no previous chars) up to the order of the model.
+    ///
+    /// # Arguments
+    ///
+    /// * `facts: &[Fact]` - The Facts of the entity, in the order of the chars in the entity.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::markov::MarkovChain;
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let mut chain = MarkovChain::new(1);
+    ///     chain.learn(&PatternDefinition::new().analyze("Ab").1);
+    ///
+    ///     assert_eq!(chain.transitions.len(), 4);
+    /// }
+    /// ```
+    pub fn learn(&mut self, facts: &[Fact]) {
+        let mut history: Vec<char> = vec![START];
+
+        for fact in facts {
+            for n in 0..=(self.order as usize).min(history.len()) {
+                let key = Self::key(fact.pattern_placeholder, &history[history.len() - n..]);
+                AddAssign::add_assign(
+                    self.transitions
+                        .entry(key)
+                        .or_default()
+                        .entry(fact.key)
+                        .or_insert(0),
+                    1,
+                );
+            }
+
+            history.push(fact.key);
+        }
+    }
+
+    /// This function lowers the order of the model, dropping the transitions of the contexts that are longer than the order,
+    /// (the model then has the same transitions as a model of that order that learned the same entities).
+    ///
+    /// # Arguments
+    ///
+    /// * `order: u8` - The new order of the model, (it is limited to the range 1 to the current order).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::markov::MarkovChain;
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let facts = PatternDefinition::new().analyze("Anna").1;
+    ///     let mut lowered = MarkovChain::new(3);
+    ///     lowered.learn(&facts);
+    ///     lowered.lower_order(1);
+    ///     let mut learned = MarkovChain::new(1);
+    ///     learned.learn(&facts);
+    ///
+    ///     assert_eq!(lowered, learned);
+    /// }
+    /// ```
+    pub fn lower_order(&mut self, order: u8) {
+        self.order = order.clamp(MIN_ORDER, self.order);
+
+        // a key is the pattern placeholder followed by the context
+        let longest = self.order as usize + 1;
+        self.transitions
+            .retain(|key, _| key.chars().count() <= longest);
+    }
+
+    /// This function adds the transitions that can be recovered from the indexed Facts of a Profile to the model.
+    /// Since the index only stores the prior key of each Fact, only the transitions of order 0 and 1 are added.
+    ///
+    /// # Arguments
+    ///
+    /// * `index: &FactIndex` - The indexed Facts.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::fact_index::FactIndex;
+    /// use test_data_generation::engine::markov::MarkovChain;
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let facts = PatternDefinition::new().analyze("Ab").1;
+    ///     let mut index = FactIndex::new();
+    ///     index.insert_all(&facts);
+    ///
+    ///     let mut learned = MarkovChain::new(1);
+    ///     learned.learn(&facts);
+    ///     let mut indexed = MarkovChain::new(1);
+    ///     indexed.learn_indexed(&index);
+    ///
+    ///     assert_eq!(indexed, learned);
+    /// }
+    /// ```
+    pub fn learn_indexed(&mut self, index: &FactIndex) {
+        for (fact_key, key, count) in index.iter() {
+            let prior = [fact_key.prior_key.unwrap_or(START)];
+
+            for context in [&prior[..0], &prior[..]] {
+                AddAssign::add_assign(
+                    self.transitions
+                        .entry(Self::key(fact_key.pattern_placeholder, context))
+                        .or_default()
+                        .entry(key)
+                        .or_insert(0),
+                    count,
+                );
+            }
+        }
+    }
+
+    /// This function chooses the next char for the pattern placeholder based on the previously generated chars.
+    /// The longest context (up to the order of the model) that has transitions is used, and the next char is chosen
+    /// at random, weighted by the number of times it followed that context.
+    /// Returns None if no char with the pattern placeholder has been analyzed.
+    ///
+    /// # Arguments
+    ///
+    /// * `placeholder: char` - The pattern placeholder of the char to generate.</br>
+    /// * `generated: &[char]` - The chars that have been generated so far.</br>
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate fastrand;
+    ///
+    /// use test_data_generation::engine::markov::MarkovChain;
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let mut chain = MarkovChain::new(1);
+    ///     chain.learn(&PatternDefinition::new().analyze("Ab").1);
+    ///
+    ///     let mut rng = fastrand::Rng::new();
+    ///     assert_eq!(chain.next_char('C', &[], &mut rng), None);
+    /// }
+    /// ```
+    pub fn next_char(
+        &self,
+        placeholder: char,
+        generated: &[char],
+        rng: &mut fastrand::Rng,
+    ) -> Option<char> {
+        let mut history: Vec<char> = vec![START];
+        history.extend_from_slice(generated);
+
+        for n in (0..=(self.order as usize).min(history.len())).rev() {
+            let key = Self::key(placeholder, &history[history.len() - n..]);
+
+            if let Some(options) = self.transitions.get(&key) {
+                let total: u32 = options.values().sum();
+                let mut pick = rng.u32(0..total);
+
+                for (c, count) in options.iter() {
+                    if pick < *count {
+                        return Some(*c);
+                    }
+                    pick -= count;
+                }
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn key(placeholder: char, context: &[char]) -> String {
+        let mut key = String::with_capacity(context.len() + 1);
+        key.push(placeholder);
+        key.extend(context.iter());
+        key
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::PatternDefinition;
+
+    #[test]
+    fn test_markov_chain_new_clamps_order() {
+        assert_eq!(MarkovChain::new(0).order, MIN_ORDER);
+        assert_eq!(MarkovChain::new(9).order, MAX_ORDER);
+    }
+
+    #[test]
+    fn test_markov_chain_learn() {
+        let mut chain = MarkovChain::new(2);
+        chain.learn(&PatternDefinition::new().analyze("Anna").1);
+
+        // "nn" is preceded by "An"
+        assert_eq!(chain.transitions.get("cAn").unwrap().get(&'n'), Some(&1));
+        // 'a' is preceded by "nn"
+        assert_eq!(chain.transitions.get("vnn").unwrap().get(&'a'), Some(&1));
+        // the first char has no previous chars
+        assert_eq!(chain.transitions.get("V\u{2}").unwrap().get(&'A'), Some(&1));
+    }
+
+    #[test]
+    fn test_markov_chain_next_char_backoff() {
+        let mut chain = MarkovChain::new(4);
+        chain.learn(&PatternDefinition::new().analyze("Anna").1);
+        let mut rng = fastrand::Rng::with_seed(7);
+
+        // "Xyz" was never analyzed, so the model falls back to the lower case vowels it has seen
+        assert_eq!(chain.next_char('v', &['X', 'y', 'z'], &mut rng), Some('a'));
+    }
+
+    #[test]
+    fn test_markov_chain_serialize() {
+        let mut chain = MarkovChain::new(1);
+        chain.learn(&PatternDefinition::new().analyze("OK").1);
+        let serialized = serde_json::to_string(&chain).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<MarkovChain>(&serialized).unwrap(),
+            chain
+        );
+    }
+}
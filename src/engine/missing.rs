@@ -0,0 +1,298 @@
+//!
+//! # MissingValues
+//! The MissingValues are the values of a field that are missing, (i.e.: empty values, JSON nulls and null sentinel tokens such as `NULL` or `N/A`).
+//! They are counted instead of being analyzed as patterns, so that the generated data has the same ratio of missing values per field
+//! (and the same mix of empty values and sentinel tokens) as the sample data.
+//! The sentinel tokens are NULL_SENTINELS by default, and can be replaced for the values of a field whose real values include one of them,
+//! (see Profile::set_null_sentinels()).
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//! extern crate fastrand;
+//!
+//! use test_data_generation::engine::missing::MissingValues;
+//!
+//! fn main() {
+//!     let mut missing = MissingValues::default();
+//!
+//!     for value in ["", "NULL", "Aaron", "n/a"].iter() {
+//!         if MissingValues::is_missing(value) {
+//!             missing.learn(value);
+//!         }
+//!     }
+//!
+//!     assert_eq!(missing.empty, 1);
+//!     assert_eq!(missing.total(), 3);
+//!
+//!     // 1 value was present, so 3 out of 4 generated values are missing
+//!     let mut rng = fastrand::Rng::with_seed(1);
+//!     println!("{:?}", missing.sample(1, &mut rng));
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+/// The tokens that represent a null value by default, (compared case insensitively after trimming whitespace)
+pub const NULL_SENTINELS: [&str; 4] = ["NULL", "N/A", "#N/A", "\\N"];
+/// The text of a JSON null, (a NUL char, which doesn't occur in text values, so that a null can be told apart from an empty string)
+pub const JSON_NULL: &str = "\u{0}";
+
+fn is_zero(count: &u32) -> bool {
+    *count == 0
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+/// Represents the missing values of a field
+pub struct MissingValues {
+    /// The number of empty values
+    pub empty: u32,
+    /// The number of JSON nulls, (see JSON_NULL)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub nulls: u32,
+    /// The number of times each null sentinel token occurred, (as it was analyzed, e.g.: `NULL`, `n/a`)
+    pub sentinels: BTreeMap<String, u32>,
+}
+
+impl MissingValues {
+    /// This function returns true if the value is a null sentinel token, (e.g.: `NULL`, `N/A`).
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The value to check.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::missing::MissingValues;
+    ///
+    /// fn main() {
+    ///     assert!(MissingValues::is_sentinel(" null "));
+    ///     assert!(!MissingValues::is_sentinel(""));
+    ///     assert!(!MissingValues::is_sentinel("Nullsen"));
+    /// }
+    /// ```
+    pub fn is_sentinel(value: &str) -> bool {
+        Self::is_sentinel_in(value, &NULL_SENTINELS)
+    }
+
+    /// This function returns true if the value is one of the specified null sentinel tokens, (compared case insensitively after trimming whitespace).
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The value to check.</br>
+    /// * `sentinels: &[S]` - The tokens that represent a null value.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::missing::MissingValues;
+    ///
+    /// fn main() {
+    ///     assert!(MissingValues::is_sentinel_in("nil", &["NIL", "-"]));
+    ///     assert!(!MissingValues::is_sentinel_in("NULL", &["NIL", "-"]));
+    /// }
+    /// ```
+    pub fn is_sentinel_in<S: AsRef<str>>(value: &str, sentinels: &[S]) -> bool {
+        let value = value.trim();
+        sentinels
+            .iter()
+            .any(|s| s.as_ref().trim().eq_ignore_ascii_case(value))
+    }
+
+    /// This function returns true if the value is empty, a JSON null or a null sentinel token.
+    pub fn is_missing(value: &str) -> bool {
+        Self::is_missing_in(value, &NULL_SENTINELS)
+    }
+
+    /// This function returns true if the value is empty, a JSON null or one of the specified null sentinel tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The value to check.</br>
+    /// * `sentinels: &[S]` - The tokens that represent a null value.</br>
+    pub fn is_missing_in<S: AsRef<str>>(value: &str, sentinels: &[S]) -> bool {
+        value.is_empty() || value == JSON_NULL || Self::is_sentinel_in(value, sentinels)
+    }
+
+    /// This function counts a missing value, (an empty value, a JSON null or a null sentinel token).
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The missing value.</br>
+    pub fn learn(&mut self, value: &str) {
+        match value {
+            "" => self.empty += 1,
+            JSON_NULL => self.nulls += 1,
+            _ => AddAssign::add_assign(self.sentinels.entry(value.to_string()).or_insert(0), 1),
+        }
+    }
+
+    /// This function returns the number of missing values that have been counted.
+    pub fn total(&self) -> u32 {
+        self.empty + self.nulls + self.sentinels.values().sum::<u32>()
+    }
+
+    /// This function returns the ratio of missing values, (0.0 to 1.0).
+    ///
+    /// # Arguments
+    ///
+    /// * `present: u32` - The number of values that weren't missing.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::missing::MissingValues;
+    ///
+    /// fn main() {
+    ///     let mut missing = MissingValues::default();
+    ///     missing.learn("");
+    ///
+    ///     assert_eq!(missing.rate(3), 0.25);
+    /// }
+    /// ```
+    pub fn rate(&self, present: u32) -> f64 {
+        match present + self.total() {
+            0 => 0.0,
+            all => self.total() as f64 / all as f64,
+        }
+    }
+
+    /// This function chooses at random whether a generated value is missing, weighted by the number of missing and present values.
+    /// Returns the missing value (an empty value, a JSON null or a null sentinel token), or None if the value isn't missing.
+    ///
+    /// # Arguments
+    ///
+    /// * `present: u32` - The number of values that weren't missing.</br>
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate fastrand;
+    ///
+    /// use test_data_generation::engine::missing::MissingValues;
+    ///
+    /// fn main() {
+    ///     let mut missing = MissingValues::default();
+    ///     missing.learn("N/A");
+    ///     let mut rng = fastrand::Rng::new();
+    ///
+    ///     assert_eq!(missing.sample(0, &mut rng), Some("N/A".to_string()));
+    /// }
+    /// ```
+    pub fn sample(&self, present: u32, rng: &mut fastrand::Rng) -> Option<String> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = rng.u32(0..present + total);
+        if pick < present {
+            return None;
+        }
+        pick -= present;
+
+        if pick < self.empty {
+            return Some(String::new());
+        }
+        pick -= self.empty;
+
+        if pick < self.nulls {
+            return Some(JSON_NULL.to_string());
+        }
+        pick -= self.nulls;
+
+        for (sentinel, count) in self.sentinels.iter() {
+            if pick < *count {
+                return Some(sentinel.clone());
+            }
+            pick -= count;
+        }
+
+        None
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_learn() {
+        let mut missing = MissingValues::default();
+        for value in ["", "", "NULL", "null", "NULL"].iter() {
+            missing.learn(value);
+        }
+
+        assert_eq!(missing.empty, 2);
+        assert_eq!(missing.sentinels.get("NULL"), Some(&2));
+        assert_eq!(missing.sentinels.get("null"), Some(&1));
+        assert_eq!(missing.rate(0), 1.0);
+        assert_eq!(MissingValues::default().rate(0), 0.0);
+    }
+
+    #[test]
+    fn test_missing_default_sentinels() {
+        // names and words that happen to be null tokens in some languages aren't missing by default
+        for value in ["None", "Nil", "NIL", "none"].iter() {
+            assert!(!MissingValues::is_missing(value));
+        }
+        for value in ["", "NULL", "n/a", "#N/A", "\\N"].iter() {
+            assert!(MissingValues::is_missing(value));
+        }
+        assert!(MissingValues::is_missing_in("", &["-"]));
+        assert!(MissingValues::is_missing_in(" - ", &["-"]));
+        assert!(!MissingValues::is_missing_in("NULL", &["-"]));
+        // a JSON null is always missing
+        assert!(MissingValues::is_missing_in(JSON_NULL, &["-"]));
+    }
+
+    #[test]
+    // ensure the JSON nulls are counted apart from the empty values
+    fn test_missing_json_nulls() {
+        let mut missing = MissingValues::default();
+        missing.learn(JSON_NULL);
+        missing.learn("");
+        assert_eq!((missing.empty, missing.nulls), (1, 1));
+        assert_eq!(missing.total(), 2);
+
+        let mut rng = fastrand::Rng::with_seed(14);
+        let sampled: Vec<Option<String>> = (0..100).map(|_| missing.sample(0, &mut rng)).collect();
+        assert!(sampled.contains(&Some(JSON_NULL.to_string())));
+        assert!(sampled.contains(&Some(String::new())));
+    }
+
+    #[test]
+    fn test_missing_sample_ratio() {
+        let mut missing = MissingValues::default();
+        missing.learn("");
+        missing.learn("N/A");
+        let mut rng = fastrand::Rng::with_seed(11);
+
+        let mut empty = 0;
+        let mut sentinel = 0;
+        for _ in 0..10000 {
+            match missing.sample(8, &mut rng).as_deref() {
+                Some("") => empty += 1,
+                Some("N/A") => sentinel += 1,
+                Some(other) => panic!("Unexpected missing value {}", other),
+                None => {}
+            }
+        }
+
+        // 1 out of 10 values is empty, and 1 out of 10 is N/A
+        assert!((800..1200).contains(&empty));
+        assert!((800..1200).contains(&sentinel));
+    }
+}
@@ -36,6 +36,12 @@
 //! ~ = special regex character [RegExSpcChar]</br>
 //! S = white space [WhiteSpace]</br>
 //! p = punctuation [Punctuation]</br>
+//! L = letter of a script without upper and lower case, (e.g.: Chinese, Arabic) [Letter]</br>
+//!
+//! The symbols are Unicode-aware: accented vowels (e.g.: 'é', 'Ü') and the vowels of the Greek and Cyrillic scripts are vowels,
+//! and any other upper or lower case letter (e.g.: 'Ñ', 'ß', 'Ж') is a consonant.
+//!
+//! Custom classes of chars with their own symbols (e.g.: hex digits, currency symbols) can be added, (see char_class::CharClass).
 //!
 //! ## Example
 //!
@@ -50,13 +56,29 @@
 //! }
 //! ```
 
+pub mod char_class;
+pub mod correlation;
+pub mod data_type;
+pub mod date_time;
+pub mod fact_index;
+pub mod json_shape;
+pub mod markov;
+pub mod missing;
+pub mod noise;
+pub mod numeric;
+pub mod pattern_chain;
+pub mod privacy;
+pub mod semantic;
+pub mod unique;
+
 use regex::Regex;
 use serde_json;
 use std::collections::BTreeMap;
-use std::sync::mpsc;
-use std::sync::mpsc::{Receiver, Sender};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread;
 
+use crate::engine::char_class::CharClass;
+use crate::errors::TdgResult;
 use crate::Profile;
 //use async_trait::async_trait;
 
@@ -69,6 +91,8 @@ macro_rules! regex {
 
 #[allow(dead_code)]
 type PatternMap = BTreeMap<String, char>;
+type AnalyzedEntity = (String, Vec<Fact>);
+type IndexedBatch = (usize, Vec<AnalyzedEntity>);
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 /// Represents a Fact for a character in a sample data entity that has been analyzed
@@ -149,7 +173,30 @@ impl Fact {
     /// ```
     #[inline]
     pub fn from_serialized(serialized: &str) -> Fact {
-        serde_json::from_str(serialized).unwrap()
+        Self::try_from_serialized(serialized).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Constructs a new Fact from a serialized (JSON) string of the Fact object, returning an error instead of panicking
+    /// if the string isn't a valid Fact.
+    ///
+    /// # Arguments
+    ///
+    /// * `serialized: &str` - The JSON string that represents the archived Fact object.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::Fact;
+    ///
+    /// fn main() {
+    ///        assert!(Fact::try_from_serialized("{\"key\":\"r\"}").is_err());
+    /// }
+    /// ```
+    #[inline]
+    pub fn try_from_serialized(serialized: &str) -> TdgResult<Fact> {
+        Ok(serde_json::from_str(serialized)?)
     }
 
     /// This function converts the Fact to a serialize JSON string.
@@ -239,18 +286,25 @@ pub struct Pattern {
     regex_punctuation: &'static Regex,
     /// The regex rule used to find white spaces
     regex_space: &'static Regex,
+    /// The regex rule used to find letters of scripts without upper and lower case
+    regex_letter: &'static Regex,
 }
 
 impl Default for Pattern {
     fn default() -> Self {
         Pattern {
-            regex_consonant_upper: regex!(r"(?-u)[B-DF-HJ-NP-TV-Z]"),
-            regex_consonant_lower: regex!(r"(?-u)[b-df-hj-np-tv-z]"),
-            regex_vowel_upper: regex!(r"(?-u)[A|E|I|O|U]"),
-            regex_vowel_lower: regex!(r"(?-u)[a|e|i|o|u]"),
-            regex_numeric: regex!(r"(?-u)[0-9]"),
-            regex_punctuation: regex!(r"(?-u)[.,\\/#!$%\\^&\\*;:{}=\\-_`~()\\?]"),
-            regex_space: regex!(r"(?-u)[\s]"),
+            regex_consonant_upper: regex!(r"[\p{Lu}\p{Lt}]"),
+            regex_consonant_lower: regex!(r"\p{Ll}"),
+            regex_vowel_upper: regex!(
+                r"[AEIOUÀ-ÆÈ-ÏÒ-ÖØ-ÜĀĂĄĒĔĖĘĚĨĪĬĮİŌŎŐŒŨŪŬŮŰŲΑΆΕΈΗΉΙΊΪΟΌΥΎΫΩΏАЕЁИІЇОУЫЭЮЯ]"
+            ),
+            regex_vowel_lower: regex!(
+                r"[aeiouà-æè-ïò-öø-üāăąēĕėęěĩīĭįıōŏőœũūŭůűųαάεέηήιίϊΐοόυύϋΰωώаеёиіїоуыэюя]"
+            ),
+            regex_numeric: regex!(r"\p{Nd}"),
+            regex_punctuation: regex!(r"[.,\\/#!$%\\^&\\*;:{}=\\-_`~()\\?]|[\p{P}&&[^\x00-\x7F]]"),
+            regex_space: regex!(r"[\s]"),
+            regex_letter: regex!(r"[\p{Lo}\p{Lm}]"),
         }
     }
 }
@@ -259,6 +313,14 @@ impl Default for Pattern {
 pub struct PatternDefinition {
     pattern_map: PatternMap,
     pattern: Pattern,
+    /// The custom classes of chars, in the order they are checked in (before the built-in classes)
+    classes: Vec<CharClass>,
+}
+
+impl Default for PatternDefinition {
+    fn default() -> Self {
+        PatternDefinition::new()
+    }
 }
 
 impl PatternDefinition {
@@ -276,7 +338,7 @@ impl PatternDefinition {
     /// }
     /// ```
     pub fn new() -> PatternDefinition {
-        let symbols: [char; 9] = ['@', 'C', 'c', 'V', 'v', '#', '~', 'S', 'p'];
+        let symbols: [char; 10] = ['@', 'C', 'c', 'V', 'v', '#', '~', 'S', 'p', 'L'];
         let mut pttrn_def = PatternMap::new();
 
         pttrn_def.insert("Unknown".to_string(), symbols[0]);
@@ -288,13 +350,73 @@ impl PatternDefinition {
         pttrn_def.insert("RegExSpcChar".to_string(), symbols[6]);
         pttrn_def.insert("WhiteSpace".to_string(), symbols[7]);
         pttrn_def.insert("Punctuation".to_string(), symbols[8]);
+        pttrn_def.insert("Letter".to_string(), symbols[9]);
 
         PatternDefinition {
             pattern_map: pttrn_def,
             pattern: Pattern::default(),
+            classes: Vec::new(),
         }
     }
 
+    /// Constructs a new PatternDefinition with custom classes of chars, (e.g.: the classes of a Profile).
+    ///
+    /// # Arguments
+    ///
+    /// * `classes: &[CharClass]` - The custom classes, in the order they are checked in.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::char_class::CharClass;
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let classes = vec![CharClass::regex('G', r"\p{Greek}", 0).unwrap()];
+    ///     let pttrn_def = PatternDefinition::with_classes(&classes);
+    ///
+    ///     assert_eq!(pttrn_def.symbolize("Ωmega"), "Gcvcv");
+    /// }
+    /// ```
+    pub fn with_classes(classes: &[CharClass]) -> PatternDefinition {
+        let mut pttrn_def = PatternDefinition::new();
+        pttrn_def.classes = classes.to_vec();
+        pttrn_def
+    }
+
+    /// This function adds a custom class of chars, which is checked before the built-in classes in the order of its priority,
+    /// (it replaces the custom class with the same symbol). Returns an InvalidSetting error if the class isn't valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `class: CharClass` - The custom class to add.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::char_class::CharClass;
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let mut pttrn_def = PatternDefinition::new();
+    ///     pttrn_def.add_class(CharClass::chars('h', "0123456789abcdef", 0).unwrap()).unwrap();
+    ///
+    ///     assert_eq!(pttrn_def.symbolize("#c0ffee"), "phhhhhh");
+    /// }
+    /// ```
+    pub fn add_class(&mut self, class: CharClass) -> TdgResult<()> {
+        char_class::add_class(&mut self.classes, class)
+    }
+
+    /// This function returns the custom classes of chars, in the order they are checked in.
+    pub fn classes(&self) -> &[CharClass] {
+        &self.classes
+    }
+
     /// This function converts an entity (&str) into a tuplet (String, Vec<Fact>)</br>
     ///
     /// # Arguments
@@ -332,7 +454,7 @@ impl PatternDefinition {
             //let fact = self.factualize(&entity, i as u32);
             let idx: u32 = i as u32;
             let fact = self.factualize(entity, idx);
-            pttrn.push_str(&fact.pattern_placeholder.to_string());
+            pttrn.push(fact.pattern_placeholder);
             facts.push(fact);
         }
 
@@ -363,18 +485,20 @@ impl PatternDefinition {
     pub fn factualize(&mut self, entity: &str, idx: u32) -> Fact {
         let c = entity.chars().nth(idx as usize).unwrap();
         let pp = self.symbolize_char(c);
+        // positions are based on chars (not bytes) so that multi-byte chars (e.g.: 'ü') are handled correctly
+        let last = entity.chars().count() as u32 - 1;
         let pk = if idx > 0 {
             entity.chars().nth(idx as usize - 1)
         } else {
             None
         };
-        let nk = if idx < entity.len() as u32 - 1 {
+        let nk = if idx < last {
             entity.chars().nth(idx as usize + 1)
         } else {
             None
         };
         let sw = if idx == 0 { 1 } else { 0 };
-        let ew = if idx == entity.len() as u32 - 1 { 1 } else { 0 };
+        let ew = if idx == last { 1 } else { 0 };
 
         let mut fact = Fact::new(c, pp, sw, ew, idx);
 
@@ -428,26 +552,32 @@ impl PatternDefinition {
     #[inline]
     pub fn symbolize_char(&self, c: char) -> char {
         // if you have to escape regex special characters: &*regex::escape(&*$c.to_string())
+        // NOTE: vowels are matched before consonants, since the consonant rules match any upper or lower case letter
+        // the custom classes are checked first
+        if let Some(class) = self.classes.iter().find(|class| class.matches(c)) {
+            return class.symbol;
+        }
+
         let mut symbol = self.pattern_map.get("Unknown");
         let mut found = false;
 
-        if !found && self.pattern.regex_consonant_upper.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("ConsonantUpper");
+        if !found && self.pattern.regex_vowel_upper.is_match(&c.to_string()) {
+            symbol = self.pattern_map.get("VowelUpper");
             found = true;
         }
 
-        if !found && self.pattern.regex_consonant_lower.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("ConsonantLower");
+        if !found && self.pattern.regex_vowel_lower.is_match(&c.to_string()) {
+            symbol = self.pattern_map.get("VowelLower");
             found = true;
         }
 
-        if !found && self.pattern.regex_vowel_upper.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("VowelUpper");
+        if !found && self.pattern.regex_consonant_upper.is_match(&c.to_string()) {
+            symbol = self.pattern_map.get("ConsonantUpper");
             found = true;
         }
 
-        if !found && self.pattern.regex_vowel_lower.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("VowelLower");
+        if !found && self.pattern.regex_consonant_lower.is_match(&c.to_string()) {
+            symbol = self.pattern_map.get("ConsonantLower");
             found = true;
         }
 
@@ -466,6 +596,11 @@ impl PatternDefinition {
             found = true;
         }
 
+        if !found && self.pattern.regex_letter.is_match(&c.to_string()) {
+            symbol = self.pattern_map.get("Letter");
+            found = true;
+        }
+
         // if not matched, then use "Unknown" placeholder symbol
         if !found {
             symbol = self.pattern_map.get("Unknown");
@@ -473,60 +608,167 @@ impl PatternDefinition {
 
         *symbol.unwrap()
     }
+
+    /// This function converts an entity into its pattern, (the same pattern that analyze() returns, without creating the Facts)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::PatternDefinition;
+    ///
+    /// fn main() {
+    ///     let pttrn_def = PatternDefinition::new();
+    ///     assert_eq!(pttrn_def.symbolize("Hello World"), "CvccvSCvccc");
+    /// }
+    /// ```
+    #[inline]
+    pub fn symbolize(&self, entity: &str) -> String {
+        entity.chars().map(|c| self.symbolize_char(c)).collect()
+    }
 }
 
+/// The number of entities that a worker of the pool analyzes at a time
+pub const BATCH_SIZE: usize = 128;
+
+/// The number of workers of the pool when no number is specified, (the same as the default number of processors of a Profile)
+pub const DEFAULT_WORKERS: usize = 4;
+
+/// Provides the functionality to analyze entities using a fixed-size pool of workers.
+/// The entities are split into batches of `BATCH_SIZE` entities, and each worker keeps taking the next batch until all the batches are analyzed.
 pub trait Engine {
+    /// This function analyzes the entities using the default number of workers (see `DEFAULT_WORKERS`)
+    /// and returns the pattern and Facts of each entity, in the same order as the entities.
     fn analyze_entities(entities: Vec<String>) -> Vec<(String, Vec<Fact>)> {
-        let (tx, rx): (Sender<(String, Vec<Fact>)>, Receiver<(String, Vec<Fact>)>) =
-            mpsc::channel();
-        let mut children = Vec::new();
-
-        for entity in entities.clone() {
-            let thread_tx = tx.clone();
-            let child = thread::spawn(move || {
-                thread_tx
-                    .send(PatternDefinition::new().analyze(&entity))
-                    .unwrap();
-                debug!("PatternDefinition::analyze thread finished for {}", entity);
-            });
-
-            children.push(child);
-        }
+        Self::analyze_entities_with_workers(entities, DEFAULT_WORKERS)
+    }
 
-        let mut results = Vec::new();
-        for entity in entities {
-            results.push(match rx.recv() {
-                Ok(result) => result,
-                Err(_) => {
-                    error!("Error: Could not analyze the entity: {}", entity);
-                    panic!("Error: Could not analyze the data!")
-                }
-            });
-        }
+    /// This function analyzes the entities using a pool of `workers` threads
+    /// and returns the pattern and Facts of each entity, in the same order as the entities.
+    ///
+    /// # Arguments
+    ///
+    /// * `entities: Vec<String>` - The entities to analyze.</br>
+    /// * `workers: usize` - The number of threads of the pool, (a value of 0 is handled as 1).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::Engine;
+    ///
+    /// struct Analyzer {}
+    /// impl Engine for Analyzer {}
+    ///
+    /// fn main() {
+    ///     let words = vec!["Smith".to_string(), "Doe".to_string()];
+    ///     let results = Analyzer::analyze_entities_with_workers(words, 2);
+    ///
+    ///     assert_eq!(results[0].0, "Ccvcc");
+    ///     assert_eq!(results[1].0, "Cvv");
+    /// }
+    /// ```
+    fn analyze_entities_with_workers(
+        entities: Vec<String>,
+        workers: usize,
+    ) -> Vec<(String, Vec<Fact>)> {
+        Self::analyze_entities_with_classes(entities, workers, &[])
+    }
 
-        for child in children {
-            child.join().expect("Error: Could not analyze the data!");
+    /// This function analyzes the entities using a pool of `workers` threads and custom classes of chars (see char_class::CharClass),
+    /// and returns the pattern and Facts of each entity, in the same order as the entities.
+    ///
+    /// # Arguments
+    ///
+    /// * `entities: Vec<String>` - The entities to analyze.</br>
+    /// * `workers: usize` - The number of threads of the pool, (a value of 0 is handled as 1).</br>
+    /// * `classes: &[CharClass]` - The custom classes, in the order they are checked in.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::char_class::CharClass;
+    /// use test_data_generation::engine::Engine;
+    ///
+    /// struct Analyzer {}
+    /// impl Engine for Analyzer {}
+    ///
+    /// fn main() {
+    ///     let classes = vec![CharClass::chars('h', "0123456789abcdef", 0).unwrap()];
+    ///     let results = Analyzer::analyze_entities_with_classes(vec!["beef".to_string()], 2, &classes);
+    ///
+    ///     assert_eq!(results[0].0, "hhhh");
+    /// }
+    /// ```
+    fn analyze_entities_with_classes(
+        entities: Vec<String>,
+        workers: usize,
+        classes: &[CharClass],
+    ) -> Vec<(String, Vec<Fact>)> {
+        let batches: Vec<&[String]> = entities.chunks(BATCH_SIZE).collect();
+        let workers = workers.clamp(1, batches.len().max(1));
+
+        // there is no need to start up threads for a single worker
+        if workers == 1 {
+            let mut pttrn_def = PatternDefinition::with_classes(classes);
+            return entities.iter().map(|e| pttrn_def.analyze(e)).collect();
         }
 
-        results
+        let next_batch = AtomicUsize::new(0);
+        let mut results: Vec<IndexedBatch> = thread::scope(|scope| {
+            let jobs: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut pttrn_def = PatternDefinition::with_classes(classes);
+                        let mut analyzed = Vec::new();
+
+                        // take the next batch that hasn't been analyzed yet
+                        loop {
+                            let idx = next_batch.fetch_add(1, Ordering::Relaxed);
+                            match batches.get(idx) {
+                                Some(batch) => analyzed.push((
+                                    idx,
+                                    batch.iter().map(|e| pttrn_def.analyze(e)).collect(),
+                                )),
+                                None => break,
+                            }
+                        }
+
+                        analyzed
+                    })
+                })
+                .collect();
+
+            jobs.into_iter()
+                .flat_map(|job| job.join().expect("Error: Could not analyze the data!"))
+                .collect()
+        });
+
+        // keep the results in the same order as the entities, so the Facts are stored in the same order on every run
+        results.sort_by_key(|r| r.0);
+        results.into_iter().flat_map(|r| r.1).collect()
     }
 
+    /// This function analyzes the entities using a pool of as many workers as the number of processors of the Profile,
+    /// and applies the patterns and Facts to the Profile.
     fn profile_entities(mut profile: Profile, entities: Vec<String>) -> Result<Profile, String> {
-        let results = Self::analyze_entities(entities);
-
-        for result in results {
-            match profile.apply_facts(result.0, result.1) {
-                Ok(_) => {}
-                Err(e) => {
-                    return Err(format!(
-                    "Error: Couldn't apply the Pattern and Facts to the Profile. Error Message: {}",
-                    e
-                ))
-                }
-            }
+        let results = Self::analyze_entities_with_classes(
+            entities,
+            profile.processors as usize,
+            &profile.char_classes,
+        );
+
+        match profile.apply_facts_all(results) {
+            Ok(_) => Ok(profile),
+            Err(e) => Err(format!(
+                "Error: Couldn't apply the Pattern and Facts to the Profile. Error Message: {}",
+                e
+            )),
         }
-
-        Ok(profile)
     }
 
     fn profile_entities_with_container(container: EngineContainer) -> Result<Profile, String> {
@@ -609,6 +851,34 @@ mod tests {
         assert_eq!(fact1.serialize(), fact2.serialize());
     }
 
+    #[test]
+    fn test_pattern_definition_factualize_unicode() {
+        let mut pttrn_def = PatternDefinition::new();
+        let mut fact1 = pttrn_def.factualize("José", 3);
+        let mut fact2 = Fact::new('é', 'v', 0, 1, 3);
+        fact2.set_prior_key('s');
+
+        assert_eq!(fact1.serialize(), fact2.serialize());
+    }
+
+    #[test]
+    fn test_pattern_definition_symbolize_char_unicode() {
+        let pttrn_def = PatternDefinition::new();
+        let symbolize = |entity: &str| {
+            entity
+                .chars()
+                .map(|c| pttrn_def.symbolize_char(c))
+                .collect::<String>()
+        };
+
+        assert_eq!(symbolize("Müller"), "Cvccvc");
+        assert_eq!(symbolize("Ñúñez"), "Cvcvc");
+        assert_eq!(symbolize("Ødegård"), "Vcvcvcc");
+        assert_eq!(symbolize("Жуков"), "Cvcvc");
+        assert_eq!(symbolize("Σοφία"), "Cvcvv");
+        assert_eq!(symbolize("王 ¿٣"), "LSp#");
+    }
+
     #[test]
     fn test_pattern_definition_analyze() {
         let mut pttrn_def = PatternDefinition::new();
@@ -634,6 +904,29 @@ mod tests {
         assert_eq!(results.len(), 5);
     }
 
+    #[test]
+    fn test_analyze_entities_with_workers_keeps_order() {
+        let words: Vec<String> = (0..(BATCH_SIZE * 5 + 7))
+            .map(|i| format!("word-{}", i))
+            .collect();
+
+        for workers in [0, 1, 3, 16] {
+            let results = Xtest::analyze_entities_with_workers(words.clone(), workers);
+            // rebuild each entity from the keys of its Facts
+            let entities: Vec<String> = results
+                .iter()
+                .map(|r| r.1.iter().map(|f| f.key).collect())
+                .collect();
+
+            assert_eq!(entities, words);
+        }
+    }
+
+    #[test]
+    fn test_analyze_entities_with_workers_empty() {
+        assert!(Xtest::analyze_entities_with_workers(Vec::new(), 4).is_empty());
+    }
+
     #[test]
     fn test_profile_entities() {
         //async {
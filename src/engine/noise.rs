@@ -0,0 +1,327 @@
+//!
+//! # DifferentialPrivacy
+//! The DifferentialPrivacy settings are used to export a Profile (or a DataSampleParser) with noisy statistics instead of the
+//! statistics of the personal data it analyzed, (see Profile::privatize()).
+//! Every count that is exported (the patterns, the sizes, the Facts, the transitions of the Markov chain and the counts of the
+//! typed models) is released with two-sided geometric noise (the discrete equivalent of Laplace noise), calibrated by the privacy
+//! budget `epsilon` (lower is more private), and the keys whose noisy count is below the `k_threshold` are suppressed,
+//! (e.g.: a pattern of a single, unusual name). The threshold only applies to the noisy counts, so whether a key is released
+//! doesn't reveal whether its true count was just below or at the threshold.
+//!
+//! ## Guarantee
+//! Only the keys that were analyzed are released, so the guarantee is (epsilon, delta)-differential privacy rather than pure
+//! epsilon-differential privacy: a key that a single value added (e.g.: the pattern of a single, unusual name) is released with
+//! the chance `delta` (see delta()), which reveals that the value was analyzed, while a sample without the value never releases
+//! the key. The chance shrinks with the k-threshold and grows with epsilon: with a k-threshold of 1 the key of a single value is
+//! released more than half of the time, and with the default k-threshold of 5 (and an epsilon of 1.0 for a statistic with a
+//! sensitivity of 1) it is still released about a quarter of the time. To keep delta below `d`, the k-threshold must be at
+//! least `1 + ln(1 / d) * STATISTICS * sensitivity / epsilon`, (e.g.: about 36 for a delta of 0.001 with an epsilon of 1.0),
+//! so choose it for the number of values that are analyzed, (e.g.: a delta below 1 / the number of values).
+//!
+//! The budget is shared equally by the statistics of a Profile, and the noise of each statistic is scaled by the number of its
+//! counts that a single value changes, (e.g.: a value is counted once by the patterns, but once for each of its chars by the Facts).
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//! extern crate fastrand;
+//!
+//! use test_data_generation::engine::noise::DifferentialPrivacy;
+//!
+//! fn main() {
+//!     let dp = DifferentialPrivacy::new(1.0, 5).unwrap();
+//!     let mut rng = fastrand::Rng::new();
+//!     let released = dp.release(vec![("Cvccv", 120), ("Cvcvcvcv", 1)], 1, dp.k_threshold, &mut rng);
+//!
+//!     // the rare pattern is suppressed (unless the noise lifts it over the threshold),
+//!     // and the count of the common pattern is close to (but not necessarily) 120
+//!     assert_eq!(released[0].0, "Cvccv");
+//! }
+//! ```
+
+use crate::errors::{TdgError, TdgResult};
+
+/// The number of statistics of a Profile that share the privacy budget, (the patterns, the sizes, the Facts, the transitions
+/// of the Markov chain and the typed models)
+pub const STATISTICS: u32 = 5;
+/// The k-threshold (by default) below which counts are suppressed
+pub const DEFAULT_K_THRESHOLD: u32 = 5;
+
+fn default_k_threshold() -> u32 {
+    DEFAULT_K_THRESHOLD
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+/// Represents the settings of the noise that is added to the statistics of an exported Profile
+pub struct DifferentialPrivacy {
+    /// The privacy budget, (lower values add more noise, e.g.: 0.1 is strong privacy and 10.0 is weak privacy)
+    pub epsilon: f64,
+    /// The noisy count below which a pattern, size, Fact or bin is suppressed
+    #[serde(default = "default_k_threshold")]
+    pub k_threshold: u32,
+}
+
+impl DifferentialPrivacy {
+    /// Constructs a new DifferentialPrivacy, returning an InvalidSetting error if epsilon isn't a positive number.
+    ///
+    /// # Arguments
+    ///
+    /// * `epsilon: f64` - The privacy budget, (lower values add more noise).</br>
+    /// * `k_threshold: u32` - The noisy count below which a pattern, size, Fact or bin is suppressed.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::noise::DifferentialPrivacy;
+    ///
+    /// fn main() {
+    ///     assert_eq!(DifferentialPrivacy::new(0.5, 10).unwrap().k_threshold, 10);
+    ///     assert!(DifferentialPrivacy::new(0.0, 10).is_err());
+    /// }
+    /// ```
+    pub fn new(epsilon: f64, k_threshold: u32) -> TdgResult<DifferentialPrivacy> {
+        let dp = DifferentialPrivacy {
+            epsilon,
+            k_threshold,
+        };
+        dp.validate()?;
+
+        Ok(dp)
+    }
+
+    /// This function returns an InvalidSetting error if epsilon isn't a positive number, (e.g.: when the settings were deserialized).
+    pub fn validate(&self) -> TdgResult<()> {
+        match self.epsilon.is_finite() && self.epsilon > 0.0 {
+            true => Ok(()),
+            false => Err(TdgError::InvalidSetting {
+                setting: "epsilon".to_string(),
+                message: format!(
+                    "epsilon must be a positive number, but was {}",
+                    self.epsilon
+                ),
+            }),
+        }
+    }
+
+    /// This function adds noise to the counts of a statistic and suppresses the counts whose noisy count is below the threshold,
+    /// (the true counts aren't compared to the threshold). The noise is calibrated by the share of epsilon of the statistic
+    /// (see STATISTICS) and its sensitivity. Only the keys of the counts are released, so a key of a single value is released
+    /// with the chance of delta(), (see the Guarantee of the module).
+    ///
+    /// # Arguments
+    ///
+    /// * `counts: I` - The keys of the statistic with their counts.</br>
+    /// * `sensitivity: u32` - The number of counts of the statistic that a single analyzed value changes.</br>
+    /// * `threshold: u32` - The count below which a key is suppressed, (1 only suppresses the keys whose noisy count is 0).</br>
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate fastrand;
+    ///
+    /// use test_data_generation::engine::noise::DifferentialPrivacy;
+    ///
+    /// fn main() {
+    ///     // with a huge budget, the noise is negligible
+    ///     let dp = DifferentialPrivacy::new(1e9, 2).unwrap();
+    ///     let mut rng = fastrand::Rng::new();
+    ///
+    ///     assert_eq!(dp.release(vec![('a', 7), ('b', 1)], 3, 2, &mut rng), vec![('a', 7)]);
+    /// }
+    /// ```
+    pub fn release<K, I>(
+        &self,
+        counts: I,
+        sensitivity: u32,
+        threshold: u32,
+        rng: &mut fastrand::Rng,
+    ) -> Vec<(K, u32)>
+    where
+        I: IntoIterator<Item = (K, u32)>,
+    {
+        counts
+            .into_iter()
+            .map(|(key, count)| (key, self.noisy_count(count, sensitivity, rng)))
+            .filter(|(_, noisy)| *noisy >= threshold.max(1))
+            .collect()
+    }
+
+    /// This function returns the chance (delta) that release() releases a key whose true count is 1, (i.e.: a key that only a
+    /// single analyzed value added, which a sample without the value never releases). A statistic is released with
+    /// (epsilon, delta)-differential privacy, (see the Guarantee of the module).
+    ///
+    /// # Arguments
+    ///
+    /// * `sensitivity: u32` - The number of counts of the statistic that a single analyzed value changes.</br>
+    /// * `threshold: u32` - The count below which a key is suppressed.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::noise::DifferentialPrivacy;
+    ///
+    /// fn main() {
+    ///     let dp = DifferentialPrivacy::new(1.0, 5).unwrap();
+    ///
+    ///     // the key of a single value is released more than half of the time with a k-threshold of 1
+    ///     assert!(dp.delta(1, 1) > 0.5);
+    ///     assert!(dp.delta(1, 40) < 0.001);
+    /// }
+    /// ```
+    pub fn delta(&self, sensitivity: u32, threshold: u32) -> f64 {
+        // the chance that the two-sided geometric noise is at least n is p^n / (1 + p)
+        let p = (-self.epsilon / (STATISTICS * sensitivity.max(1)) as f64).exp();
+
+        p.powi(threshold.max(1) as i32 - 1) / (1.0 + p)
+    }
+
+    /// This function adds noise to a single count of a statistic, (the noisy count isn't negative).
+    ///
+    /// # Arguments
+    ///
+    /// * `count: u32` - The true count.</br>
+    /// * `sensitivity: u32` - The number of counts of the statistic that a single analyzed value changes.</br>
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    pub fn noisy_count(&self, count: u32, sensitivity: u32, rng: &mut fastrand::Rng) -> u32 {
+        let epsilon = self.epsilon / (STATISTICS * sensitivity.max(1)) as f64;
+        let noisy = count as i64 + geometric_noise(epsilon, rng);
+
+        noisy.clamp(0, u32::MAX as i64) as u32
+    }
+}
+
+/// This function draws an integer from the two-sided geometric distribution, where the chance of `n` is proportional to `exp(-epsilon * |n|)`.
+///
+/// # Arguments
+///
+/// * `epsilon: f64` - The privacy budget of a count with a sensitivity of 1.</br>
+/// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+///
+/// # Example
+///
+/// ```rust
+/// extern crate test_data_generation;
+/// extern crate fastrand;
+///
+/// use test_data_generation::engine::noise::geometric_noise;
+///
+/// fn main() {
+///     let mut rng = fastrand::Rng::new();
+///     assert_eq!(geometric_noise(1e9, &mut rng), 0);
+/// }
+/// ```
+pub fn geometric_noise(epsilon: f64, rng: &mut fastrand::Rng) -> i64 {
+    // the difference of two geometric draws (with a success chance of 1 - exp(-epsilon)) is two-sided geometric
+    let mut draw = || ((1.0 - rng.f64()).ln() / -epsilon).floor() as i64;
+
+    draw() - draw()
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geometric_noise_distribution() {
+        let mut rng = fastrand::Rng::with_seed(25);
+        let draws: Vec<i64> = (0..20_000)
+            .map(|_| geometric_noise(0.5, &mut rng))
+            .collect();
+
+        // the noise is centered on 0, with a mean absolute value of 2p/(1-p^2) where p = exp(-epsilon)
+        let mean = draws.iter().sum::<i64>() as f64 / draws.len() as f64;
+        let p = (-0.5f64).exp();
+        let expected = 2.0 * p / (1.0 - p * p);
+        let spread = draws.iter().map(|n| n.abs()).sum::<i64>() as f64 / draws.len() as f64;
+        assert!(mean.abs() < 0.1);
+        assert!((spread - expected).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_release_suppresses_rare_counts() {
+        let dp = DifferentialPrivacy::new(50.0, 5).unwrap();
+        let mut rng = fastrand::Rng::with_seed(7);
+
+        for _ in 0..100 {
+            let released = dp.release(vec![("rare", 4), ("common", 1000)], 1, 5, &mut rng);
+            assert_eq!(released.len(), 1);
+            assert!(released[0].1 > 950);
+        }
+    }
+
+    #[test]
+    // ensure the threshold only applies to the noisy counts, so a true count just below it is released about as often as one at it
+    fn test_release_thresholds_noisy_counts() {
+        let dp = DifferentialPrivacy::new(1.0, 5).unwrap();
+        let mut rng = fastrand::Rng::with_seed(25);
+        let trials = 10_000;
+
+        let mut released = [0u32; 2];
+        for _ in 0..trials {
+            for (key, _) in dp.release(vec![(0, 4), (1, 5)], 1, 5, &mut rng) {
+                released[key] += 1;
+            }
+        }
+
+        let below = released[0] as f64 / trials as f64;
+        let at = released[1] as f64 / trials as f64;
+        assert!(below > 0.35 && below < 0.55);
+        assert!(at > 0.45 && at < 0.65);
+        // the ratio of the chances is bounded by exp(epsilon) of the statistic
+        assert!(at / below < (dp.epsilon / STATISTICS as f64).exp() * 1.05);
+    }
+
+    #[test]
+    // ensure the key of a single value is released at the chance of delta, (about half of the time with a k-threshold of 1)
+    fn test_release_presence_of_single_value() {
+        let dp = DifferentialPrivacy::new(0.1, 1).unwrap();
+        let mut rng = fastrand::Rng::with_seed(25);
+        let trials = 10_000;
+
+        let chance = |threshold: u32, rng: &mut fastrand::Rng| {
+            (0..trials)
+                .filter(|_| {
+                    !dp.release(vec![("O'Brien", 1)], 1, threshold, rng)
+                        .is_empty()
+                })
+                .count() as f64
+                / trials as f64
+        };
+
+        let released = chance(1, &mut rng);
+        assert!(released > 0.45 && released < 0.55);
+        assert!((released - dp.delta(1, 1)).abs() < 0.02);
+
+        // a higher k-threshold makes the key of a single value rarer
+        let released = chance(100, &mut rng);
+        assert!((released - dp.delta(1, 100)).abs() < 0.02);
+        assert!(released < 0.1);
+
+        // a sample without the value never releases its key
+        assert!(dp
+            .release(Vec::<(&str, u32)>::new(), 1, 1, &mut rng)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_differential_privacy_deserialize() {
+        let dp: DifferentialPrivacy = serde_yaml::from_str("epsilon: 0.5").unwrap();
+        assert_eq!(
+            dp,
+            DifferentialPrivacy::new(0.5, DEFAULT_K_THRESHOLD).unwrap()
+        );
+
+        let invalid: DifferentialPrivacy =
+            serde_yaml::from_str("epsilon: -1\nk-threshold: 2").unwrap();
+        assert!(invalid.validate().is_err());
+    }
+}
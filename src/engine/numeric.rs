@@ -0,0 +1,396 @@
+//!
+//! # NumericModel
+//! The NumericModel is the distribution of the values of a numeric field (e.g.: amounts, ages), so that generated numbers
+//! are within the range of the analyzed numbers and follow their distribution, instead of being synthesized digit by digit.
+//! The distribution is a streaming histogram of at most `MAX_BINS` bins, so the memory it uses doesn't depend on the number of values.
+//! While there are no more distinct values than bins, each bin is a single value and only the analyzed values are generated.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//! extern crate fastrand;
+//!
+//! use test_data_generation::engine::numeric::NumericModel;
+//!
+//! fn main() {
+//!     let mut model = NumericModel::default();
+//!
+//!     for salary in 1..=1000 {
+//!         model.learn(&format!("{}.00", 30000 + salary * 70));
+//!     }
+//!
+//!     assert_eq!(model.min, 30070.0);
+//!     assert_eq!(model.max, 100000.0);
+//!     assert_eq!(model.precision, 2);
+//!
+//!     let mut rng = fastrand::Rng::with_seed(1);
+//!     let salary: f64 = model.sample(&mut rng).parse().unwrap();
+//!     assert!(salary >= 30070.0 && salary <= 100000.0);
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+/// The maximum number of bins of the histogram
+pub const MAX_BINS: usize = 64;
+/// The number of steps of the grid of the generalized histogram in the range of the analyzed values, (see generalized_bins())
+const GRID_STEPS: f64 = 16.0;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+/// Represents the distribution of the values of a numeric field
+pub struct NumericModel {
+    /// The smallest analyzed value
+    pub min: f64,
+    /// The largest analyzed value
+    pub max: f64,
+    /// The number of analyzed values
+    pub count: u32,
+    /// The maximum number of digits after the decimal point of the analyzed values
+    pub precision: u8,
+    /// The bins of the histogram as (lowest value, highest value, number of values), ordered by value
+    pub bins: Vec<(f64, f64, u32)>,
+}
+
+impl NumericModel {
+    /// This function adds a numeric value to the distribution. Values that aren't numbers are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The numeric value, (e.g.: 42, -0.5).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::numeric::NumericModel;
+    ///
+    /// fn main() {
+    ///     let mut model = NumericModel::default();
+    ///     model.learn("3.25");
+    ///     model.learn("-7");
+    ///     model.learn("n/a");
+    ///
+    ///     assert_eq!(model.count, 2);
+    ///     assert_eq!(model.min, -7.0);
+    ///     assert_eq!(model.precision, 2);
+    /// }
+    /// ```
+    pub fn learn(&mut self, value: &str) {
+        let number = match value.trim().parse::<f64>() {
+            Ok(n) if n.is_finite() => n,
+            _ => return,
+        };
+
+        if self.count == 0 {
+            self.min = number;
+            self.max = number;
+        } else {
+            self.min = self.min.min(number);
+            self.max = self.max.max(number);
+        }
+        self.count += 1;
+
+        let precision = value
+            .trim()
+            .split_once('.')
+            .map(|(_, fraction)| fraction.len())
+            .unwrap_or(0);
+        self.precision = self.precision.max(precision.min(u8::MAX as usize) as u8);
+
+        // the first bin whose highest value isn't lower than the number
+        let idx = self.bins.partition_point(|b| b.1 < number);
+        match self.bins.get_mut(idx) {
+            Some(bin) if bin.0 <= number => bin.2 += 1,
+            _ => {
+                self.bins.insert(idx, (number, number, 1));
+                if self.bins.len() > MAX_BINS {
+                    self.merge_closest_bins();
+                }
+            }
+        }
+    }
+
+    /// This function returns the (approximate) value below which the fraction of the analyzed values falls, (e.g.: 0.5 is the median).
+    ///
+    /// # Arguments
+    ///
+    /// * `q: f64` - The fraction of the values, (0.0 to 1.0).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::numeric::NumericModel;
+    ///
+    /// fn main() {
+    ///     let mut model = NumericModel::default();
+    ///     for age in ["21", "35", "35", "40", "68"].iter() {
+    ///         model.learn(age);
+    ///     }
+    ///
+    ///     assert_eq!(model.quantile(0.5), 35.0);
+    ///     assert_eq!(model.quantile(1.0), 68.0);
+    /// }
+    /// ```
+    pub fn quantile(&self, q: f64) -> f64 {
+        let target = q.clamp(0.0, 1.0) * self.count as f64;
+        let mut cumulative = 0;
+
+        for (low, high, count) in self.bins.iter() {
+            if (cumulative + count) as f64 >= target {
+                // assume the values are spread evenly within the bin
+                let fraction = ((target - cumulative as f64) / *count as f64).max(0.0);
+                return low + (high - low) * fraction;
+            }
+            cumulative += count;
+        }
+
+        self.max
+    }
+
+    /// This function generates a number from the distribution, formatted with the precision of the analyzed values.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate fastrand;
+    ///
+    /// use test_data_generation::engine::numeric::NumericModel;
+    ///
+    /// fn main() {
+    ///     let mut model = NumericModel::default();
+    ///     model.learn("1.5");
+    ///     model.learn("2.0");
+    ///
+    ///     // each bin is a single value, so only the analyzed values are generated
+    ///     let mut rng = fastrand::Rng::new();
+    ///     let generated = model.sample(&mut rng);
+    ///     assert!(generated == "1.5" || generated == "2.0");
+    /// }
+    /// ```
+    pub fn sample(&self, rng: &mut fastrand::Rng) -> String {
+        let total: u32 = self.bins.iter().map(|b| b.2).sum();
+        if total == 0 {
+            return String::new();
+        }
+
+        let mut pick = rng.u32(0..total);
+        for (low, high, count) in self.bins.iter() {
+            if pick < *count {
+                // a bin of several values is sampled uniformly within its range
+                return match low == high {
+                    true => self.format(*low),
+                    false => self.format(low + rng.f64() * (high - low)),
+                };
+            }
+            pick -= count;
+        }
+
+        String::new()
+    }
+
+    /// This function estimates the number of distinct values that can be generated, (the number of values with the
+    /// analyzed decimal precision within the ranges of the bins of the histogram).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::numeric::NumericModel;
+    ///
+    /// fn main() {
+    ///     let mut model = NumericModel::default();
+    ///     model.learn("1.5");
+    ///     assert_eq!(model.capacity(), 1.0);
+    ///
+    ///     model.learn("2.5");
+    ///     assert_eq!(model.capacity(), 2.0);
+    /// }
+    /// ```
+    pub fn capacity(&self) -> f64 {
+        let scale = 10_f64.powi(self.precision as i32);
+
+        self.bins
+            .iter()
+            .map(|(low, high, _)| ((high - low) * scale).round() + 1.0)
+            .sum()
+    }
+
+    /// This function returns the bins of the histogram merged into the cells of a grid, so that the bounds of the bins are
+    /// multiples of the step of the grid instead of analyzed values, (e.g.: so that the smallest and the largest analyzed values
+    /// aren't exported, see Profile::privatize()). The step is the power of ten that divides the range into (at most) 16 cells,
+    /// but not less than the precision of the analyzed values, and each bin is counted in the cell of its middle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::numeric::NumericModel;
+    ///
+    /// fn main() {
+    ///     let mut model = NumericModel::default();
+    ///     for age in ["21", "35", "37", "68"].iter() {
+    ///         model.learn(age);
+    ///     }
+    ///
+    ///     assert_eq!(model.generalized_bins(), vec![(20.0, 30.0, 1), (30.0, 40.0, 2), (60.0, 70.0, 1)]);
+    /// }
+    /// ```
+    pub fn generalized_bins(&self) -> Vec<(f64, f64, u32)> {
+        let span = (self.max - self.min) / GRID_STEPS;
+        let exponent = match span > 0.0 {
+            true => span.log10().ceil() as i32,
+            false => 0,
+        }
+        .max(-(self.precision as i32));
+        // the multiples of the step, (a fractional step is applied by its inverse to keep them exact, e.g.: 0.3 instead of 0.30000000000000004)
+        let grid = |k: f64| match exponent >= 0 {
+            true => k * 10_f64.powi(exponent),
+            false => k / 10_f64.powi(-exponent),
+        };
+        let steps = |value: f64| match exponent >= 0 {
+            true => value / 10_f64.powi(exponent),
+            false => value * 10_f64.powi(-exponent),
+        };
+
+        let mut cells: BTreeMap<i64, u32> = BTreeMap::new();
+        for (low, high, count) in self.bins.iter() {
+            let cell = steps((low + high) / 2.0).floor() as i64;
+            AddAssign::add_assign(cells.entry(cell).or_insert(0), *count);
+        }
+
+        cells
+            .into_iter()
+            .map(|(k, count)| (grid(k as f64), grid(k as f64 + 1.0), count))
+            .collect()
+    }
+
+    /// This function formats a number with the precision of the analyzed values.
+    fn format(&self, value: f64) -> String {
+        let formatted = format!("{:.*}", self.precision as usize, value);
+
+        // avoid negative zeros (e.g.: -0.00)
+        match formatted.strip_prefix('-') {
+            Some(unsigned) if unsigned.bytes().all(|b| b == b'0' || b == b'.') => {
+                unsigned.to_string()
+            }
+            _ => formatted,
+        }
+    }
+
+    /// This function merges the two adjacent bins that make the narrowest bin into one bin.
+    fn merge_closest_bins(&mut self) {
+        let width = |idx: usize| self.bins[idx + 1].1 - self.bins[idx].0;
+        let idx = (0..self.bins.len() - 1)
+            .min_by(|a, b| width(*a).total_cmp(&width(*b)))
+            .unwrap_or(0);
+
+        let (_, high, count) = self.bins.remove(idx + 1);
+        self.bins[idx].1 = high;
+        self.bins[idx].2 += count;
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_bins_are_bounded() {
+        let mut model = NumericModel::default();
+        for value in 0..10000 {
+            model.learn(&value.to_string());
+        }
+
+        assert_eq!(model.bins.len(), MAX_BINS);
+        assert_eq!(model.bins.iter().map(|b| b.2).sum::<u32>(), 10000);
+        assert_eq!(model.bins.first().unwrap().0, 0.0);
+        assert_eq!(model.bins.last().unwrap().1, 9999.0);
+        assert!((model.quantile(0.5) - 5000.0).abs() < 500.0);
+    }
+
+    #[test]
+    // ensure the generated numbers follow the distribution, (90% of the salaries are low)
+    fn test_numeric_sample_distribution() {
+        let mut model = NumericModel::default();
+        for value in 0..900 {
+            model.learn(&format!("{}", 20000 + value * 10));
+        }
+        for value in 0..100 {
+            model.learn(&format!("{}", 500000 + value * 1000));
+        }
+        let mut rng = fastrand::Rng::with_seed(8);
+
+        let mut low = 0;
+        for _ in 0..10000 {
+            let generated = model.sample(&mut rng);
+            assert!(!generated.contains('.'));
+            let value: f64 = generated.parse().unwrap();
+            assert!((model.min..=model.max).contains(&value));
+            if value < 100000.0 {
+                low += 1;
+            }
+        }
+        assert!((8700..9300).contains(&low));
+    }
+
+    #[test]
+    fn test_numeric_format() {
+        let mut model = NumericModel::default();
+        model.learn("-0.001");
+        model.learn("0.5");
+        model.precision = 1;
+
+        assert_eq!(model.format(-0.001), "0.0");
+        assert_eq!(model.format(-1.25), "-1.2");
+    }
+
+    #[test]
+    // ensure the bounds of the generalized bins are multiples of the step instead of analyzed values
+    fn test_numeric_generalized_bins() {
+        let mut model = NumericModel::default();
+        for value in ["0.25", "0.31", "0.33", "1.72"].iter() {
+            model.learn(value);
+        }
+        assert_eq!(
+            model.generalized_bins(),
+            vec![(0.2, 0.3, 1), (0.3, 0.4, 2), (1.7, 1.8, 1)]
+        );
+
+        // not less than the precision of the analyzed values
+        let mut constant = NumericModel::default();
+        constant.learn("42");
+        constant.learn("42");
+        assert_eq!(constant.generalized_bins(), vec![(42.0, 43.0, 2)]);
+        assert!(NumericModel::default().generalized_bins().is_empty());
+    }
+
+    #[test]
+    fn test_numeric_serialize() {
+        let mut model = NumericModel::default();
+        model.learn("4");
+        model.learn("2.5");
+        let serialized = serde_json::to_string(&model).unwrap();
+
+        assert_eq!(
+            serialized,
+            "{\"min\":2.5,\"max\":4.0,\"count\":2,\"precision\":1,\"bins\":[[2.5,2.5,1],[4.0,4.0,1]]}"
+        );
+        assert_eq!(
+            serde_json::from_str::<NumericModel>(&serialized).unwrap(),
+            model
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! # PatternChain
+//! The PatternChain is a Markov model of the pattern symbols of the analyzed entities, with a start and an end state.
+//! It is built from the patterns of a Profile (weighted by the number of times each pattern was analyzed) and is used to
+//! synthesize plausible patterns that weren't analyzed, (e.g.: `Cvccvc` from the patterns `Cvcc`, `Cvccv` and `Cvcvcc`),
+//! so that the generated data isn't limited to the shapes of the data sample.
+//!
+//! The `novelty` (0.0 to 1.0) controls how far the synthesized patterns stray from the analyzed patterns: the next symbol
+//! is chosen based on the previous 3 symbols when the novelty is low, and on fewer previous symbols as the novelty increases.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//! extern crate fastrand;
+//!
+//! use test_data_generation::engine::pattern_chain::PatternChain;
+//!
+//! fn main() {
+//!     let mut chain = PatternChain::new();
+//!     chain.learn("Cvcc", 1);
+//!     chain.learn("Cvccv", 1);
+//!     chain.learn("Cvcvcc", 1);
+//!
+//!     let mut rng = fastrand::Rng::with_seed(1);
+//!     let pattern = chain.synthesize(1.0, &mut rng).unwrap();
+//!     assert!(pattern.starts_with("Cv"));
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+/// The highest number of previous symbols that are used to choose the next symbol
+pub const ORDER: usize = 3;
+/// The symbol that marks the start of a pattern in a context
+const START: char = '\u{2}';
+/// The symbol that marks the end of a pattern
+const END: char = '\u{3}';
+
+type TransitionMap = BTreeMap<String, BTreeMap<char, u32>>;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+/// Represents a Markov model of the pattern symbols of the analyzed entities
+pub struct PatternChain {
+    /// The number of times a symbol (or the end of the pattern) followed a context of previous symbols
+    pub transitions: TransitionMap,
+    /// The length of the longest analyzed pattern, (the synthesized patterns are at most twice as long)
+    pub longest: usize,
+}
+
+impl PatternChain {
+    /// Constructs a new, empty PatternChain
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::pattern_chain::PatternChain;
+    ///
+    /// fn main() {
+    ///     let chain = PatternChain::new();
+    ///     assert!(chain.transitions.is_empty());
+    /// }
+    /// ```
+    pub fn new() -> PatternChain {
+        PatternChain::default()
+    }
+
+    /// This function adds the transitions of an analyzed pattern to the model.
+    /// Transitions are stored for every context from 0 previous symbols up to the ORDER.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern: &str` - The pattern, (e.g.: "Cvccv").</br>
+    /// * `count: u32` - The number of times the pattern was analyzed.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::pattern_chain::PatternChain;
+    ///
+    /// fn main() {
+    ///     let mut chain = PatternChain::new();
+    ///     chain.learn("Cv", 2);
+    ///
+    ///     assert_eq!(chain.longest, 2);
+    ///     assert_eq!(chain.transitions.get("\u{2}").unwrap().get(&'C'), Some(&2));
+    /// }
+    /// ```
+    pub fn learn(&mut self, pattern: &str, count: u32) {
+        let mut history: Vec<char> = vec![START];
+
+        for symbol in pattern.chars().chain(std::iter::once(END)) {
+            for n in 0..=ORDER.min(history.len()) {
+                let key: String = history[history.len() - n..].iter().collect();
+                AddAssign::add_assign(
+                    self.transitions
+                        .entry(key)
+                        .or_default()
+                        .entry(symbol)
+                        .or_insert(0),
+                    count,
+                );
+            }
+
+            history.push(symbol);
+        }
+
+        self.longest = self.longest.max(history.len() - 2);
+    }
+
+    /// This function synthesizes a pattern by choosing each symbol at random, weighted by the number of times it followed
+    /// the previous symbols, until the end of the pattern is chosen. The longest context (up to the number of previous symbols
+    /// that the novelty allows) that has transitions is used.
+    /// Returns None if no patterns have been analyzed or the pattern doesn't end within twice the length of the longest analyzed pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `novelty: f64` - How far the pattern strays from the analyzed patterns, (0.0 uses the previous 3 symbols, 1.0 only the previous symbol).</br>
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate fastrand;
+    ///
+    /// use test_data_generation::engine::pattern_chain::PatternChain;
+    ///
+    /// fn main() {
+    ///     let mut chain = PatternChain::new();
+    ///     let mut rng = fastrand::Rng::new();
+    ///     assert_eq!(chain.synthesize(0.5, &mut rng), None);
+    ///
+    ///     chain.learn("##p##", 1);
+    ///     assert_eq!(chain.synthesize(0.0, &mut rng), Some("##p##".to_string()));
+    /// }
+    /// ```
+    pub fn synthesize(&self, novelty: f64, rng: &mut fastrand::Rng) -> Option<String> {
+        let order = 1 + ((1.0 - novelty.clamp(0.0, 1.0)) * (ORDER - 1) as f64).round() as usize;
+        let mut history: Vec<char> = vec![START];
+
+        while history.len() <= self.longest * 2 {
+            let symbol = self.next_symbol(&history, order, rng)?;
+            if symbol == END {
+                return Some(history[1..].iter().collect());
+            }
+
+            history.push(symbol);
+        }
+
+        None
+    }
+
+    fn next_symbol(&self, history: &[char], order: usize, rng: &mut fastrand::Rng) -> Option<char> {
+        for n in (0..=order.min(history.len())).rev() {
+            let key: String = history[history.len() - n..].iter().collect();
+
+            if let Some(options) = self.transitions.get(&key) {
+                let total: u32 = options.values().sum();
+                let mut pick = rng.u32(0..total);
+
+                for (symbol, count) in options.iter() {
+                    if pick < *count {
+                        return Some(*symbol);
+                    }
+                    pick -= count;
+                }
+            }
+        }
+
+        None
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn sample_chain() -> PatternChain {
+        let mut chain = PatternChain::new();
+        chain.learn("Cvcc", 1);
+        chain.learn("Cvccv", 1);
+        chain.learn("Cvcvcc", 1);
+        chain
+    }
+
+    #[test]
+    fn test_pattern_chain_learn() {
+        let chain = sample_chain();
+
+        assert_eq!(chain.longest, 6);
+        // "Cvc" is followed by 'c' twice and by 'v' once
+        let options = chain.transitions.get("Cvc").unwrap();
+        assert_eq!(options.get(&'c'), Some(&2));
+        assert_eq!(options.get(&'v'), Some(&1));
+        // a pattern ends after "vcc" twice
+        assert_eq!(chain.transitions.get("vcc").unwrap().get(&END), Some(&2));
+    }
+
+    #[test]
+    fn test_pattern_chain_novelty() {
+        let chain = sample_chain();
+        let analyzed = ["Cvcc", "Cvccv", "Cvcvcc"];
+        let mut rng = fastrand::Rng::with_seed(22);
+
+        // without novelty, the patterns are synthesized from the previous 3 symbols, so they rarely stray
+        let faithful: BTreeSet<String> = (0..500)
+            .filter_map(|_| chain.synthesize(0.0, &mut rng))
+            .collect();
+        assert!(analyzed.iter().all(|p| faithful.contains(*p)));
+        assert!(!faithful.contains("Cvccvc"));
+
+        // with the highest novelty, unseen (but plausible) patterns are synthesized
+        let synthesized: BTreeSet<String> = (0..500)
+            .filter_map(|_| chain.synthesize(1.0, &mut rng))
+            .collect();
+        assert!(synthesized.len() > faithful.len());
+        assert!(synthesized.contains("Cvccvc"));
+        assert!(synthesized.iter().all(|p| p.starts_with("Cv")));
+        assert!(synthesized.iter().all(|p| p.len() <= 12));
+    }
+
+    #[test]
+    fn test_pattern_chain_serialize() {
+        let chain = sample_chain();
+        let serialized = serde_json::to_string(&chain).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<PatternChain>(&serialized).unwrap(),
+            chain
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//!
+//! # PrivacyGuard
+//! The PrivacyGuard keeps a Profile in its no-verbatim privacy mode from generating the real values it analyzed,
+//! (e.g.: a profile of the single value `O'Brien` would otherwise generate `O'Brien`).
+//! It keeps a set of keyed hashes (SipHash-2-4) of the analyzed values, so that a generated value that matches an analyzed value
+//! is rejected without the analyzed values being exported with the Profile. The secret key of the hashes isn't exported either,
+//! so the hashes of an exported Profile can't be used to test whether a guessed value was analyzed. A restored guard is locked
+//! until the key is supplied again (see set_key()). Optionally, a generated value must also be at a minimum Levenshtein distance
+//! from every analyzed value, (which is only checked while the analyzed values are in memory, since they aren't exported).
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::engine::privacy::PrivacyGuard;
+//!
+//! fn main() {
+//!     let mut guard = PrivacyGuard::new(2);
+//!     guard.learn("O'Brien");
+//!
+//!     assert!(!guard.allows("O'Brien"));
+//!     assert!(!guard.allows("O'Brian"));
+//!     assert!(guard.allows("O'Bryan"));
+//!
+//!     // the key isn't exported, so a restored guard is locked until the key is supplied again
+//!     let key = guard.key().unwrap();
+//!     let mut restored: PrivacyGuard = serde_json::from_str(&serde_json::to_string(&guard).unwrap()).unwrap();
+//!     assert!(restored.key().is_none());
+//!     restored.set_key(key).unwrap();
+//!     assert!(!restored.allows("O'Brien"));
+//! }
+//! ```
+
+use crate::errors::{TdgError, TdgResult};
+use std::collections::BTreeSet;
+
+/// The number of times a value is generated again (by default) when it is rejected
+pub const DEFAULT_RETRIES: u32 = 100;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents the keyed hashes (and, in memory, the values) of the analyzed values that must not be generated
+pub struct PrivacyGuard {
+    /// The secret key of the hashes, (it isn't exported, so the hashes can't be compared to the hashes of guessed values)
+    #[serde(skip)]
+    key: Option<u128>,
+    /// The hash of an empty value with the key, so that a key that is supplied again can be checked (see set_key())
+    pub key_check: u64,
+    /// The keyed hashes of the analyzed values
+    pub hashes: BTreeSet<u64>,
+    /// The minimum Levenshtein distance between a generated value and every analyzed value, (0 only rejects the analyzed values)
+    #[serde(default)]
+    pub min_distance: usize,
+    /// The number of times a value is generated again when it is rejected
+    pub retries: u32,
+    /// The analyzed values, (only kept in memory to check the distance, or until the key of a locked guard is supplied)
+    #[serde(skip)]
+    values: BTreeSet<String>,
+}
+
+fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+/// This function returns the SipHash-2-4 of the data with a 128 bit key, (a keyed hash that can't be computed without the key).
+///
+/// # Arguments
+///
+/// * `key: u128` - The secret key, (its little endian bytes are the key of the reference implementation).</br>
+/// * `data: &[u8]` - The data to hash.</br>
+///
+/// # Example
+///
+/// ```rust
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::engine::privacy::siphash;
+///
+/// fn main() {
+///     let key = u128::from_le_bytes([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+///     assert_eq!(siphash(key, &[]), 0x726f_db47_dd0e_0e31);
+/// }
+/// ```
+pub fn siphash(key: u128, data: &[u8]) -> u64 {
+    let (k0, k1) = (key as u64, (key >> 64) as u64);
+    let mut v = [
+        k0 ^ 0x736f_6d65_7073_6575,
+        k1 ^ 0x646f_7261_6e64_6f6d,
+        k0 ^ 0x6c79_6765_6e65_7261,
+        k1 ^ 0x7465_6462_7974_6573,
+    ];
+    let compress = |v: &mut [u64; 4], m: u64| {
+        v[3] ^= m;
+        sip_round(v);
+        sip_round(v);
+        v[0] ^= m;
+    };
+
+    let mut chunks = data.chunks_exact(8);
+    for chunk in &mut chunks {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(chunk);
+        compress(&mut v, u64::from_le_bytes(bytes));
+    }
+
+    // the last block has the remaining bytes and the length of the data in its highest byte
+    let last = chunks
+        .remainder()
+        .iter()
+        .enumerate()
+        .fold((data.len() as u64) << 56, |m, (i, b)| {
+            m | (*b as u64) << (8 * i)
+        });
+    compress(&mut v, last);
+
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        sip_round(&mut v);
+    }
+
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+impl PrivacyGuard {
+    /// Constructs a new PrivacyGuard with a random key
+    ///
+    /// # Arguments
+    ///
+    /// * `min_distance: usize` - The minimum Levenshtein distance between a generated value and every analyzed value.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::privacy::PrivacyGuard;
+    ///
+    /// fn main() {
+    ///     let guard = PrivacyGuard::new(0);
+    ///     assert!(guard.hashes.is_empty());
+    /// }
+    /// ```
+    pub fn new(min_distance: usize) -> PrivacyGuard {
+        Self::new_with_key(min_distance, fastrand::u128(..))
+    }
+
+    /// Constructs a new PrivacyGuard with a key that is supplied by the caller, (e.g.: a key that is kept in a secret store
+    /// so that it can be supplied again when the Profile is restored, see set_key()).
+    ///
+    /// # Arguments
+    ///
+    /// * `min_distance: usize` - The minimum Levenshtein distance between a generated value and every analyzed value.</br>
+    /// * `key: u128` - The secret key of the hashes.</br>
+    pub fn new_with_key(min_distance: usize, key: u128) -> PrivacyGuard {
+        PrivacyGuard {
+            key: Some(key),
+            key_check: siphash(key, &[]),
+            hashes: BTreeSet::new(),
+            min_distance,
+            retries: DEFAULT_RETRIES,
+            values: BTreeSet::new(),
+        }
+    }
+
+    /// This function returns the secret key of the hashes, or None if the guard is locked, (i.e.: it was restored and the key
+    /// hasn't been supplied again). Keep the key outside of the exported Profile.
+    pub fn key(&self) -> Option<u128> {
+        self.key
+    }
+
+    /// This function supplies the secret key of the hashes of a restored guard, returning an InvalidSetting error
+    /// if it isn't the key the values were analyzed with, (a guard that hasn't analyzed any value takes any key).
+    ///
+    /// # Arguments
+    ///
+    /// * `key: u128` - The secret key of the hashes, (see key()).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::privacy::PrivacyGuard;
+    ///
+    /// fn main() {
+    ///     let mut guard = PrivacyGuard::new_with_key(0, 42);
+    ///     guard.learn("O'Brien");
+    ///
+    ///     assert!(guard.set_key(7).is_err());
+    ///     assert!(guard.set_key(42).is_ok());
+    /// }
+    /// ```
+    pub fn set_key(&mut self, key: u128) -> TdgResult<()> {
+        if self.hashes.is_empty() && self.values.is_empty() {
+            self.key_check = siphash(key, &[]);
+        } else if siphash(key, &[]) != self.key_check {
+            return Err(TdgError::InvalidSetting {
+                setting: "privacy_key".to_string(),
+                message: "the key isn't the key of the hashes of the analyzed values".to_string(),
+            });
+        }
+
+        self.key = Some(key);
+
+        // hash the values that were analyzed while the guard was locked
+        for value in self.values.iter() {
+            self.hashes.insert(siphash(key, value.as_bytes()));
+        }
+        if self.min_distance == 0 {
+            self.values.clear();
+        }
+
+        Ok(())
+    }
+
+    /// This function adds an analyzed value to the values that must not be generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The analyzed value.</br>
+    pub fn learn(&mut self, value: &str) {
+        match self.key {
+            Some(key) => {
+                self.hashes.insert(siphash(key, value.as_bytes()));
+
+                if self.min_distance > 0 {
+                    self.values.insert(value.to_string());
+                }
+            }
+            // the value is hashed once the key is supplied
+            None => {
+                self.values.insert(value.to_string());
+            }
+        }
+    }
+
+    /// This function returns true if a generated value may be used, (it isn't an analyzed value and it is at the minimum
+    /// distance from the analyzed values that are in memory). A locked guard (see key()) doesn't allow any value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The generated value.</br>
+    pub fn allows(&self, value: &str) -> bool {
+        let key = match self.key {
+            Some(key) => key,
+            None => return false,
+        };
+
+        if self.hashes.contains(&siphash(key, value.as_bytes())) {
+            return false;
+        }
+
+        self.values
+            .iter()
+            .all(|v| levenshtein_distance!(v, value) >= self.min_distance)
+    }
+
+    /// This function returns the number of distinct analyzed values.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// This function returns true if no values have been analyzed.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_siphash_reference_vectors() {
+        let key = u128::from_le_bytes([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+        let data: Vec<u8> = (0..15).collect();
+
+        assert_eq!(siphash(key, &data[..8]), 0x93f5_f579_9a93_2462);
+        assert_eq!(siphash(key, &data), 0xa129_ca61_49be_45e5);
+    }
+
+    #[test]
+    fn test_privacy_guard_keyed() {
+        let mut first = PrivacyGuard::new(0);
+        let mut second = PrivacyGuard::new_with_key(0, first.key().unwrap().wrapping_add(1));
+        first.learn("Smith, John");
+        second.learn("Smith, John");
+
+        assert_ne!(first.hashes, second.hashes);
+        assert_eq!(first.len(), 1);
+        assert!(!first.allows("Smith, John"));
+        // without a minimum distance, only the analyzed values are rejected
+        assert!(first.allows("Smith, Joan"));
+    }
+
+    #[test]
+    fn test_privacy_guard_serialize() {
+        let mut guard = PrivacyGuard::new(3);
+        guard.learn("O'Brien");
+        let serialized = serde_json::to_string(&guard).unwrap();
+
+        // neither the values nor the key are exported
+        assert!(!serialized.contains("O'Brien"));
+        assert!(!serialized.contains(&guard.key().unwrap().to_string()));
+        let mut restored: PrivacyGuard = serde_json::from_str(&serialized).unwrap();
+        assert!(!restored.allows("O'Bryan"));
+        assert!(restored
+            .set_key(guard.key().unwrap().wrapping_add(1))
+            .is_err());
+
+        restored.set_key(guard.key().unwrap()).unwrap();
+        assert!(!restored.allows("O'Brien"));
+        // the analyzed values aren't exported, so only the hashes are checked
+        assert!(restored.allows("O'Brian"));
+    }
+
+    #[test]
+    // ensure the values that are analyzed by a locked guard are hashed once the key is supplied
+    fn test_privacy_guard_learn_locked() {
+        let guard = PrivacyGuard::new_with_key(0, 24);
+        let mut restored: PrivacyGuard =
+            serde_json::from_str(&serde_json::to_string(&guard).unwrap()).unwrap();
+        restored.learn("O'Brien");
+        restored.set_key(24).unwrap();
+
+        assert_eq!(restored.len(), 1);
+        assert!(!restored.allows("O'Brien"));
+        assert!(restored.allows("O'Brian"));
+    }
+}
@@ -0,0 +1,692 @@
+//!
+//! # Semantic Types
+//! The semantic types are the kinds of values (e.g.: emails, UUIDs, credit card numbers) whose structure can't be described by
+//! the character classes of the patterns, so that their generated values are structurally valid, (e.g.: Luhn-valid card numbers,
+//! emails with a single `@`, version 4 UUIDs).
+//!
+//! The values of a field are checked by the registered SemanticDetectors before they are analyzed as patterns.
+//! If every value of the field is detected by the same detector, the values are generated by the detector instead of from the patterns.
+//! A detector can keep a part of the values (e.g.: the domain of an email) so that the generated values resemble the analyzed values.
+//!
+//! The built-in detectors are `email`, `phone`, `uuid`, `ip` (v4 and v6), `url` and `credit-card`.
+//! Custom detectors can be registered (see register()), and are checked before the built-in detectors.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//! extern crate fastrand;
+//!
+//! use test_data_generation::engine::semantic::SemanticModel;
+//!
+//! fn main() {
+//!     let mut model = SemanticModel::detect("aaron@example.com").unwrap();
+//!     assert!(model.learn("beth@example.org"));
+//!     assert_eq!(model.detector, "email");
+//!
+//!     let mut rng = fastrand::Rng::with_seed(1);
+//!     let email = model.sample(&mut rng);
+//!     assert!(email.ends_with("@example.com") || email.ends_with("@example.org"));
+//! }
+//! ```
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::ops::AddAssign;
+use std::sync::{Arc, RwLock};
+
+/// Detects the values of a semantic type, and generates structurally valid values of the type.
+pub trait SemanticDetector: Send + Sync {
+    /// The name of the semantic type, (stored on the Profile, so it should be unique)
+    fn name(&self) -> &str;
+
+    /// Returns true if the value is of the semantic type.
+    fn detect(&self, value: &str) -> bool;
+
+    /// Returns the part of a detected value that is kept to generate values that resemble it, (e.g.: the domain of an email).
+    fn component(&self, _value: &str) -> Option<String> {
+        None
+    }
+
+    /// Generates a structurally valid value of the semantic type from a kept component (if any).
+    fn generate(&self, component: Option<&str>, rng: &mut fastrand::Rng) -> String;
+}
+
+static DETECTORS: Lazy<RwLock<Vec<Arc<dyn SemanticDetector>>>> = Lazy::new(|| {
+    RwLock::new(vec![
+        Arc::new(Uuid),
+        Arc::new(Email),
+        Arc::new(Url),
+        Arc::new(Ip),
+        Arc::new(CreditCard),
+        Arc::new(Phone),
+    ])
+});
+
+/// This function registers a custom detector, which is checked before the detectors that are already registered.
+/// A detector replaces the registered detector with the same name.
+///
+/// # Arguments
+///
+/// * `detector: Arc<dyn SemanticDetector>` - The detector to register.</br>
+///
+/// # Example
+///
+/// ```rust
+/// extern crate test_data_generation;
+/// extern crate fastrand;
+///
+/// use std::sync::Arc;
+/// use test_data_generation::engine::semantic::{register, SemanticDetector, SemanticModel};
+///
+/// struct Sku;
+///
+/// impl SemanticDetector for Sku {
+///     fn name(&self) -> &str {
+///         "sku"
+///     }
+///
+///     fn detect(&self, value: &str) -> bool {
+///         value.len() == 8 && value.starts_with("SKU-") && value[4..].bytes().all(|b| b.is_ascii_digit())
+///     }
+///
+///     fn generate(&self, _component: Option<&str>, rng: &mut fastrand::Rng) -> String {
+///         format!("SKU-{:04}", rng.u32(0..10000))
+///     }
+/// }
+///
+/// fn main() {
+///     register(Arc::new(Sku));
+///
+///     let model = SemanticModel::detect("SKU-0042").unwrap();
+///     assert_eq!(model.detector, "sku");
+/// }
+/// ```
+pub fn register(detector: Arc<dyn SemanticDetector>) {
+    let mut detectors = DETECTORS.write().unwrap_or_else(|e| e.into_inner());
+    detectors.retain(|d| d.name() != detector.name());
+    detectors.insert(0, detector);
+}
+
+/// This function returns the registered detector with the name.
+pub fn detector(name: &str) -> Option<Arc<dyn SemanticDetector>> {
+    DETECTORS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|d| d.name() == name)
+        .cloned()
+}
+
+/// This function returns the first registered detector that detects the value.
+pub fn detect(value: &str) -> Option<Arc<dyn SemanticDetector>> {
+    DETECTORS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|d| d.detect(value))
+        .cloned()
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents the semantic type of the values of a field
+pub struct SemanticModel {
+    /// The name of the detector of the semantic type, (e.g.: email)
+    pub detector: String,
+    /// The number of analyzed values
+    pub count: u32,
+    /// The parts of the values that are kept to generate values that resemble them, with the number of times they occurred
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, u32>,
+}
+
+impl SemanticModel {
+    /// This function detects the semantic type of a value, returning None if none of the registered detectors detects it.
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The value to analyze.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::semantic::SemanticModel;
+    ///
+    /// fn main() {
+    ///     assert_eq!(SemanticModel::detect("4111 1111 1111 1111").unwrap().detector, "credit-card");
+    ///     assert!(SemanticModel::detect("Aaron").is_none());
+    /// }
+    /// ```
+    pub fn detect(value: &str) -> Option<SemanticModel> {
+        let detector = detect(value)?;
+        let mut model = SemanticModel {
+            detector: detector.name().to_string(),
+            count: 0,
+            components: BTreeMap::new(),
+        };
+        model.keep(detector.as_ref(), value);
+
+        Some(model)
+    }
+
+    /// This function adds a value to the model. Returns false if the detector of the model doesn't detect the value,
+    /// (i.e.: the values aren't of the same semantic type).
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The value to analyze.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::semantic::SemanticModel;
+    ///
+    /// fn main() {
+    ///     let mut model = SemanticModel::detect("192.168.0.1").unwrap();
+    ///
+    ///     assert!(model.learn("::1"));
+    ///     assert!(!model.learn("localhost"));
+    /// }
+    /// ```
+    pub fn learn(&mut self, value: &str) -> bool {
+        match detector(&self.detector) {
+            Some(detector) if detector.detect(value) => {
+                self.keep(detector.as_ref(), value);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// This function generates a value of the semantic type, from a kept component chosen by the number of times it occurred.
+    /// Returns None if the detector of the model isn't registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    pub fn try_sample(&self, rng: &mut fastrand::Rng) -> Option<String> {
+        let detector = detector(&self.detector)?;
+        let component = self.sample_component(rng);
+
+        Some(detector.generate(component, rng))
+    }
+
+    /// This function chooses a kept component at random, weighted by the number of times it occurred.
+    fn sample_component(&self, rng: &mut fastrand::Rng) -> Option<&str> {
+        let total: u32 = self.components.values().sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = rng.u32(0..total);
+        for (component, count) in self.components.iter() {
+            if pick < *count {
+                return Some(component);
+            }
+            pick -= count;
+        }
+
+        None
+    }
+
+    /// This function generates a value of the semantic type, (an empty value if the detector of the model isn't registered).
+    ///
+    /// # Arguments
+    ///
+    /// * `rng: &mut fastrand::Rng` - The random number generator to use.</br>
+    pub fn sample(&self, rng: &mut fastrand::Rng) -> String {
+        self.try_sample(rng).unwrap_or_default()
+    }
+
+    fn keep(&mut self, detector: &dyn SemanticDetector, value: &str) {
+        self.count += 1;
+        if let Some(component) = detector.component(value) {
+            AddAssign::add_assign(self.components.entry(component).or_insert(0), 1);
+        }
+    }
+}
+
+/// Generates `len` random chars from the alphabet.
+fn random_chars(alphabet: &[u8], len: usize, rng: &mut fastrand::Rng) -> String {
+    (0..len)
+        .map(|_| alphabet[rng.usize(0..alphabet.len())] as char)
+        .collect()
+}
+
+const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const HEX: &[u8] = b"0123456789abcdef";
+
+/// emails with a single `@`, (e.g.: aaron.aaberg@example.com)
+struct Email;
+
+static EMAIL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Za-z0-9!#$%&'*+/=?^_`{|}~-]+(\.[A-Za-z0-9!#$%&'*+/=?^_`{|}~-]+)*@([A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?\.)+[A-Za-z]{2,63}$").unwrap()
+});
+
+impl SemanticDetector for Email {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    fn detect(&self, value: &str) -> bool {
+        value.len() <= 254 && EMAIL.is_match(value)
+    }
+
+    // the domain
+    fn component(&self, value: &str) -> Option<String> {
+        value.rsplit_once('@').map(|(_, d)| d.to_lowercase())
+    }
+
+    fn generate(&self, component: Option<&str>, rng: &mut fastrand::Rng) -> String {
+        let len = rng.usize(3..9);
+        let local = match rng.bool() {
+            true => random_chars(LOWER, len, rng),
+            false => format!(
+                "{}.{}",
+                random_chars(LOWER, len, rng),
+                random_chars(LOWER, rng.usize(3..9), rng)
+            ),
+        };
+
+        format!("{}@{}", local, component.unwrap_or("example.com"))
+    }
+}
+
+/// phone numbers with 10 to 15 digits and separators, (e.g.: (555) 234-5678, +44 20 7946 0958)
+struct Phone;
+
+impl SemanticDetector for Phone {
+    fn name(&self) -> &str {
+        "phone"
+    }
+
+    fn detect(&self, value: &str) -> bool {
+        let number = value.strip_prefix('+').unwrap_or(value);
+        let digits = number.bytes().filter(|b| b.is_ascii_digit()).count();
+
+        // a number without separators is an integer, (unless it has the international prefix)
+        (10..=15).contains(&digits)
+            && (number.len() > digits || value.starts_with('+'))
+            && number
+                .bytes()
+                .next()
+                .is_some_and(|b| b.is_ascii_digit() || b == b'(')
+            && number
+                .bytes()
+                .all(|b| b.is_ascii_digit() || b" ().-".contains(&b))
+    }
+
+    // the format, (e.g.: (###) ###-####)
+    fn component(&self, value: &str) -> Option<String> {
+        Some(
+            value
+                .chars()
+                .map(|c| if c.is_ascii_digit() { '#' } else { c })
+                .collect(),
+        )
+    }
+
+    fn generate(&self, component: Option<&str>, rng: &mut fastrand::Rng) -> String {
+        let mut first = true;
+        component
+            .unwrap_or("(###) ###-####")
+            .chars()
+            .map(|c| match c {
+                // the number doesn't start with 0 or 1, (trunk and country prefixes)
+                '#' if first => {
+                    first = false;
+                    char::from(b'0' + rng.u8(2..10))
+                }
+                '#' => char::from(b'0' + rng.u8(0..10)),
+                other => other,
+            })
+            .collect()
+    }
+}
+
+/// UUIDs, (e.g.: 0f8fad5b-d9cb-469f-a165-70867728950e)
+struct Uuid;
+
+impl SemanticDetector for Uuid {
+    fn name(&self) -> &str {
+        "uuid"
+    }
+
+    fn detect(&self, value: &str) -> bool {
+        value.len() == 36
+            && value.char_indices().all(|(idx, c)| match idx {
+                8 | 13 | 18 | 23 => c == '-',
+                _ => c.is_ascii_hexdigit(),
+            })
+    }
+
+    // the case of the hex digits
+    fn component(&self, value: &str) -> Option<String> {
+        match value.bytes().any(|b| b.is_ascii_uppercase()) {
+            true => Some("upper".to_string()),
+            false => Some("lower".to_string()),
+        }
+    }
+
+    // a version 4 (random) UUID
+    fn generate(&self, component: Option<&str>, rng: &mut fastrand::Rng) -> String {
+        let mut hex = random_chars(HEX, 32, rng).into_bytes();
+        hex[12] = b'4';
+        hex[16] = b"89ab"[rng.usize(0..4)];
+        let hex = String::from_utf8(hex).unwrap_or_default();
+
+        let uuid = format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        );
+        match component {
+            Some("upper") => uuid.to_uppercase(),
+            _ => uuid,
+        }
+    }
+}
+
+/// IPv4 and IPv6 addresses, (e.g.: 192.168.0.1, 2001:db8::1)
+struct Ip;
+
+impl SemanticDetector for Ip {
+    fn name(&self) -> &str {
+        "ip"
+    }
+
+    fn detect(&self, value: &str) -> bool {
+        value.parse::<IpAddr>().is_ok()
+    }
+
+    // the version
+    fn component(&self, value: &str) -> Option<String> {
+        match value.parse::<IpAddr>() {
+            Ok(IpAddr::V6(_)) => Some("v6".to_string()),
+            _ => Some("v4".to_string()),
+        }
+    }
+
+    fn generate(&self, component: Option<&str>, rng: &mut fastrand::Rng) -> String {
+        match component {
+            Some("v6") => Ipv6Addr::new(
+                0x2001,
+                0x0db8,
+                rng.u16(..),
+                rng.u16(..),
+                rng.u16(..),
+                rng.u16(..),
+                rng.u16(..),
+                rng.u16(..),
+            )
+            .to_string(),
+            // a unicast address, (not a network or broadcast address)
+            _ => Ipv4Addr::new(rng.u8(1..224), rng.u8(..), rng.u8(..), rng.u8(1..255)).to_string(),
+        }
+    }
+}
+
+/// http and https URLs, (e.g.: https://www.example.com/path?query)
+struct Url;
+
+static URL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^((?i:https?)://([A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?\.)*[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?(:[0-9]{1,5})?)(/[^\s?#]*)?(\?[^\s#]*)?(#\S*)?$").unwrap()
+});
+
+impl SemanticDetector for Url {
+    fn name(&self) -> &str {
+        "url"
+    }
+
+    fn detect(&self, value: &str) -> bool {
+        URL.is_match(value)
+    }
+
+    // the scheme and the host, (e.g.: https://www.example.com)
+    fn component(&self, value: &str) -> Option<String> {
+        URL.captures(value)
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().to_lowercase())
+    }
+
+    fn generate(&self, component: Option<&str>, rng: &mut fastrand::Rng) -> String {
+        let mut url = component.unwrap_or("https://www.example.com").to_string();
+        for _ in 0..rng.usize(0..3) {
+            url.push('/');
+            url.push_str(&random_chars(LOWER, rng.usize(3..9), rng));
+        }
+
+        url
+    }
+}
+
+/// Luhn-valid credit card numbers of 13 to 19 digits, optionally grouped by spaces or dashes, (e.g.: 4111-1111-1111-1111)
+struct CreditCard;
+
+impl CreditCard {
+    /// Returns the Luhn check digit of the digits, (that don't include the check digit).
+    fn check_digit(digits: &[u8]) -> u8 {
+        let sum: u32 = digits
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(idx, d)| match idx % 2 {
+                0 => {
+                    let doubled = (*d as u32) * 2;
+                    if doubled > 9 {
+                        doubled - 9
+                    } else {
+                        doubled
+                    }
+                }
+                _ => *d as u32,
+            })
+            .sum();
+
+        ((10 - sum % 10) % 10) as u8
+    }
+}
+
+impl SemanticDetector for CreditCard {
+    fn name(&self) -> &str {
+        "credit-card"
+    }
+
+    fn detect(&self, value: &str) -> bool {
+        if !value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b == b' ' || b == b'-')
+        {
+            return false;
+        }
+
+        let digits: Vec<u8> = value
+            .bytes()
+            .filter(|b| b.is_ascii_digit())
+            .map(|b| b - b'0')
+            .collect();
+
+        // the major industry identifier of the card networks, (e.g.: 4 is Visa)
+        (13..=19).contains(&digits.len())
+            && (3..=6).contains(&digits[0])
+            && value.bytes().next().is_some_and(|b| b.is_ascii_digit())
+            && Self::check_digit(&digits[..digits.len() - 1]) == digits[digits.len() - 1]
+    }
+
+    // the format with the first digit, (e.g.: 4###-####-####-####)
+    fn component(&self, value: &str) -> Option<String> {
+        let mut first = true;
+        Some(
+            value
+                .chars()
+                .map(|c| match c.is_ascii_digit() {
+                    true if first => {
+                        first = false;
+                        c
+                    }
+                    true => '#',
+                    false => c,
+                })
+                .collect(),
+        )
+    }
+
+    fn generate(&self, component: Option<&str>, rng: &mut fastrand::Rng) -> String {
+        let format = component.unwrap_or("4###############");
+        let len = format
+            .chars()
+            .filter(|c| *c == '#' || c.is_ascii_digit())
+            .count();
+
+        let mut digits: Vec<u8> = format
+            .chars()
+            .filter(|c| *c == '#' || c.is_ascii_digit())
+            .take(len.saturating_sub(1))
+            .map(|c| match c.to_digit(10) {
+                Some(d) => d as u8,
+                None => rng.u8(0..10),
+            })
+            .collect();
+        digits.push(Self::check_digit(&digits));
+
+        let mut digits = digits.into_iter();
+        format
+            .chars()
+            .map(|c| match c == '#' || c.is_ascii_digit() {
+                true => char::from(b'0' + digits.next().unwrap_or(0)),
+                false => c,
+            })
+            .collect()
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_all(value: &str, count: usize) -> (SemanticModel, Vec<String>) {
+        let model = SemanticModel::detect(value).unwrap();
+        let mut rng = fastrand::Rng::with_seed(19);
+        let generated = (0..count).map(|_| model.sample(&mut rng)).collect();
+        (model, generated)
+    }
+
+    #[test]
+    fn test_semantic_detect() {
+        let cases = [
+            ("aaron.aaberg@example.com", "email"),
+            ("(555) 234-5678", "phone"),
+            ("+44 20 7946 0958", "phone"),
+            ("0F8FAD5B-D9CB-469F-A165-70867728950E", "uuid"),
+            ("10.0.0.255", "ip"),
+            ("2001:db8::ff00:42:8329", "ip"),
+            ("https://www.example.com/path?query=1", "url"),
+            ("378282246310005", "credit-card"),
+            ("4111-1111-1111-1111", "credit-card"),
+        ];
+        for (value, name) in cases.iter() {
+            assert_eq!(
+                SemanticModel::detect(value).map(|m| m.detector),
+                Some(name.to_string()),
+                "{}",
+                value
+            );
+        }
+
+        for value in [
+            "aaron@@example.com",
+            ".aaron@example.com",
+            "5552345678",
+            "2017-01-04",
+            "4111-1111-1111-1112",
+            "1234567890123",
+            "256.0.0.1",
+            "ftp://example.com",
+            "Aaron",
+        ]
+        .iter()
+        {
+            assert!(SemanticModel::detect(value).is_none(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_semantic_generate_valid_emails() {
+        let (model, generated) = generate_all("Aaron@Example.com", 200);
+        assert_eq!(model.components.get("example.com"), Some(&1));
+
+        for email in generated.iter() {
+            assert!(Email.detect(email), "{}", email);
+            assert_eq!(email.matches('@').count(), 1);
+            assert!(email.ends_with("@example.com"));
+        }
+    }
+
+    #[test]
+    fn test_semantic_generate_valid_cards() {
+        let (_, generated) = generate_all("3782-822463-10005", 200);
+
+        for card in generated.iter() {
+            assert!(CreditCard.detect(card), "{}", card);
+            assert_eq!(card.len(), 17);
+            assert!(card.starts_with('3'));
+        }
+    }
+
+    #[test]
+    fn test_semantic_generate_v4_uuids() {
+        let (_, generated) = generate_all("0f8fad5b-d9cb-469f-a165-70867728950e", 200);
+
+        for uuid in generated.iter() {
+            assert!(Uuid.detect(uuid), "{}", uuid);
+            assert_eq!(&uuid[14..15], "4");
+            assert!("89ab".contains(&uuid[19..20]));
+            assert_eq!(uuid.to_lowercase(), *uuid);
+        }
+    }
+
+    #[test]
+    fn test_semantic_generate_others() {
+        let (_, phones) = generate_all("+1 (555) 234-5678", 50);
+        let (_, ips) = generate_all("2001:db8::1", 50);
+        let (_, urls) = generate_all("HTTP://Example.com:8080/index.html", 50);
+
+        for phone in phones.iter() {
+            assert!(Phone.detect(phone), "{}", phone);
+            assert!(phone.starts_with('+'));
+            assert_eq!(phone.len(), 17);
+        }
+        for ip in ips.iter() {
+            assert!(ip.parse::<Ipv6Addr>().is_ok(), "{}", ip);
+        }
+        for url in urls.iter() {
+            assert!(Url.detect(url), "{}", url);
+            assert!(url.starts_with("http://example.com:8080"));
+        }
+    }
+
+    #[test]
+    fn test_semantic_serialize() {
+        let model = SemanticModel::detect("10.0.0.1").unwrap();
+        let serialized = serde_json::to_string(&model).unwrap();
+
+        assert_eq!(
+            serialized,
+            "{\"detector\":\"ip\",\"count\":1,\"components\":{\"v4\":1}}"
+        );
+        assert_eq!(
+            serde_json::from_str::<SemanticModel>(&serialized).unwrap(),
+            model
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//!
+//! # UniqueValues
+//! The UniqueValues track the values that a Profile has generated in its uniqueness mode, (e.g.: for primary keys and usernames),
+//! so that a value that was already generated is discarded and generated again, up to a budget of retries.
+//! Only the budget is exported with the Profile, so a restored Profile starts tracking the values anew.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::engine::unique::UniqueValues;
+//!
+//! fn main() {
+//!     let mut unique = UniqueValues::default();
+//!
+//!     assert!(unique.insert("jdoe"));
+//!     assert!(!unique.insert("jdoe"));
+//!     assert_eq!(unique.len(), 1);
+//! }
+//! ```
+
+use std::collections::HashSet;
+
+/// The number of times a value is generated again (by default) when it was already generated
+pub const DEFAULT_RETRIES: u32 = 100;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents the values that have been generated in uniqueness mode
+pub struct UniqueValues {
+    /// The number of times a value is generated again when it was already generated, before the value space is considered exhausted
+    pub retries: u32,
+    /// The values that have been generated
+    #[serde(skip)]
+    generated: HashSet<String>,
+}
+
+impl Default for UniqueValues {
+    fn default() -> Self {
+        UniqueValues::new(DEFAULT_RETRIES)
+    }
+}
+
+impl UniqueValues {
+    /// Constructs a new UniqueValues
+    ///
+    /// # Arguments
+    ///
+    /// * `retries: u32` - The number of times a value is generated again when it was already generated.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::unique::UniqueValues;
+    ///
+    /// fn main() {
+    ///     let unique = UniqueValues::new(10);
+    ///     assert_eq!(unique.retries, 10);
+    ///     assert!(unique.is_empty());
+    /// }
+    /// ```
+    pub fn new(retries: u32) -> UniqueValues {
+        UniqueValues {
+            retries,
+            generated: HashSet::new(),
+        }
+    }
+
+    /// This function tracks a generated value, returning false if the value was already generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `value: &str` - The generated value.</br>
+    pub fn insert(&mut self, value: &str) -> bool {
+        match self.generated.contains(value) {
+            true => false,
+            false => self.generated.insert(value.to_string()),
+        }
+    }
+
+    /// This function returns the number of values that have been generated.
+    pub fn len(&self) -> usize {
+        self.generated.len()
+    }
+
+    /// This function returns true if no values have been generated.
+    pub fn is_empty(&self) -> bool {
+        self.generated.is_empty()
+    }
+
+    /// This function forgets the values that have been generated, (e.g.: to generate another data set).
+    pub fn clear(&mut self) {
+        self.generated.clear();
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_values_clear() {
+        let mut unique = UniqueValues::new(3);
+        assert!(unique.insert("a"));
+        assert!(unique.insert("b"));
+        assert!(!unique.insert("a"));
+
+        unique.clear();
+        assert!(unique.is_empty());
+        assert!(unique.insert("a"));
+    }
+
+    #[test]
+    fn test_unique_values_serialize() {
+        let mut unique = UniqueValues::new(5);
+        unique.insert("a");
+        let serialized = serde_json::to_string(&unique).unwrap();
+
+        assert_eq!(serialized, "{\"retries\":5}");
+        let deserialized: UniqueValues = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.retries, 5);
+        assert!(deserialized.is_empty());
+    }
+}
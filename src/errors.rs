@@ -0,0 +1,352 @@
+//! The `errors` module provides the error type that is returned by the `try_` functions of the library,
+//! (e.g.: loading, saving, analyzing and generating), so that bad input can be handled instead of panicking.
+//!
+//! # Examples
+//!
+//!
+//! Handle a profile archive that doesn't exist ...
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::errors::TdgError;
+//! use test_data_generation::Profile;
+//!
+//! fn main() {
+//!     match Profile::try_from_file("./tests/samples/bad-path") {
+//!         Ok(_profile) => println!("The profile was loaded"),
+//!         Err(TdgError::FileOpen { path, .. }) => println!("There is no profile at {}", path),
+//!         Err(e) => println!("The profile couldn't be loaded: {}", e),
+//!     }
+//! }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// A specialized Result type for the `try_` functions of the library
+pub type TdgResult<T> = std::result::Result<T, TdgError>;
+
+#[derive(Debug)]
+/// Represents the errors that can occur while loading, saving, analyzing or generating data
+pub enum TdgError {
+    /// A file couldn't be opened
+    FileOpen {
+        /// the path of the file
+        path: String,
+        /// the underlying I/O error
+        source: io::Error,
+    },
+    /// A file couldn't be read
+    FileRead {
+        /// the path of the file
+        path: String,
+        /// the underlying I/O error
+        source: io::Error,
+    },
+    /// A file couldn't be created
+    FileCreate {
+        /// the path of the file
+        path: String,
+        /// the underlying I/O error
+        source: io::Error,
+    },
+    /// A file couldn't be written to
+    FileWrite {
+        /// the path of the file
+        path: String,
+        /// the underlying I/O error
+        source: io::Error,
+    },
+    /// The generated data couldn't be written to a writer (e.g.: stdout, a pipe)
+    Write {
+        /// the underlying I/O error
+        source: io::Error,
+    },
+    /// A JSON string or file couldn't be (de)serialized
+    Json {
+        /// the path of the file, if the JSON came from a file
+        path: Option<String>,
+        /// the underlying serde_json error
+        source: serde_json::Error,
+    },
+    /// A YAML configuration file couldn't be parsed (or doesn't match the configuration schema)
+    Yaml {
+        /// the path of the file
+        path: String,
+        /// the underlying serde_yaml error
+        source: serde_yaml::Error,
+    },
+    /// CSV data couldn't be read or written
+    Csv {
+        /// the path of the file, if the csv data came from a file
+        path: Option<String>,
+        /// the underlying csv error
+        source: csv::Error,
+    },
+    /// An archive is missing a field (or the field has the wrong type)
+    MissingField {
+        /// the name of the field
+        field: String,
+    },
+    /// There is no Profile for the field name
+    ProfileNotFound {
+        /// the name of the field
+        field: String,
+    },
+    /// The Profile has no patterns to generate data from
+    EmptyProfile,
+    /// The Profile has no Facts for a symbol of the pattern
+    NoFacts {
+        /// the pattern being generated
+        pattern: String,
+        /// the position of the symbol in the pattern
+        index: usize,
+    },
+    /// The Profile can't generate any more unique values, (see Profile::set_unique())
+    UniqueExhausted {
+        /// the field of the Profile, if it has one
+        field: Option<String>,
+        /// the number of unique values that were generated
+        generated: usize,
+        /// the estimated number of distinct values the Profile can generate, (see Profile::capacity())
+        capacity: f64,
+    },
+    /// The Profile can't generate a value that isn't (or isn't too close to) an analyzed value, (see Profile::set_privacy())
+    PrivacyExhausted {
+        /// the field of the Profile, if it has one
+        field: Option<String>,
+        /// the number of times the value was generated again
+        retries: u32,
+    },
+    /// A setting has a value that isn't supported
+    InvalidSetting {
+        /// the name of the setting
+        setting: String,
+        /// a description of the supported values
+        message: String,
+    },
+    /// The data couldn't be analyzed
+    Analyze {
+        /// a description of the problem
+        message: String,
+    },
+    /// A worker thread failed
+    Thread {
+        /// a description of the problem
+        message: String,
+    },
+}
+
+impl fmt::Display for TdgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TdgError::FileOpen { path, source } => {
+                write!(f, "Could not open file {:?} because of {}", path, source)
+            }
+            TdgError::FileRead { path, source } => {
+                write!(f, "Could not read file {:?} because of {}", path, source)
+            }
+            TdgError::FileCreate { path, source } => {
+                write!(f, "Could not create file {:?} because of {}", path, source)
+            }
+            TdgError::FileWrite { path, source } => {
+                write!(
+                    f,
+                    "Could not write to file {:?} because of {}",
+                    path, source
+                )
+            }
+            TdgError::Write { source } => {
+                write!(
+                    f,
+                    "Could not write the generated data because of {}",
+                    source
+                )
+            }
+            TdgError::Json {
+                path: Some(path),
+                source,
+            } => write!(
+                f,
+                "Could not parse the JSON in file {:?} because of {}",
+                path, source
+            ),
+            TdgError::Json { path: None, source } => {
+                write!(f, "Could not parse the JSON because of {}", source)
+            }
+            TdgError::Yaml { path, source } => {
+                write!(
+                    f,
+                    "Could not parse the YAML in file {:?} because of {}",
+                    path, source
+                )
+            }
+            TdgError::Csv {
+                path: Some(path),
+                source,
+            } => write!(
+                f,
+                "Could not process the csv file {:?} because of {}",
+                path, source
+            ),
+            TdgError::Csv { path: None, source } => {
+                write!(f, "Could not process the csv data because of {}", source)
+            }
+            TdgError::MissingField { field } => {
+                write!(f, "The archive is missing the field {:?}", field)
+            }
+            TdgError::ProfileNotFound { field } => {
+                write!(f, "There is no profile for the field {:?}", field)
+            }
+            TdgError::EmptyProfile => write!(
+                f,
+                "The profile has no patterns. Analyze some data and call pre_generate() first"
+            ),
+            TdgError::NoFacts { pattern, index } => write!(
+                f,
+                "There are no facts for the symbol at index {} of the pattern {:?}",
+                index, pattern
+            ),
+            TdgError::UniqueExhausted {
+                field,
+                generated,
+                capacity,
+            } => write!(
+                f,
+                "The unique values of the field {:?} are exhausted: {} values were generated out of an estimated capacity of {}",
+                field.as_deref().unwrap_or_default(),
+                generated,
+                capacity
+            ),
+            TdgError::PrivacyExhausted { field, retries } => write!(
+                f,
+                "Could not generate a value of the field {:?} that isn't too close to an analyzed value within {} retries",
+                field.as_deref().unwrap_or_default(),
+                retries
+            ),
+            TdgError::InvalidSetting { setting, message } => {
+                write!(
+                    f,
+                    "Invalid value for the setting {:?}: {}",
+                    setting, message
+                )
+            }
+            TdgError::Analyze { message } => write!(f, "Could not analyze the data: {}", message),
+            TdgError::Thread { message } => write!(f, "A worker thread failed: {}", message),
+        }
+    }
+}
+
+impl Error for TdgError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TdgError::FileOpen { source, .. }
+            | TdgError::FileRead { source, .. }
+            | TdgError::FileCreate { source, .. }
+            | TdgError::FileWrite { source, .. }
+            | TdgError::Write { source } => Some(source),
+            TdgError::Json { source, .. } => Some(source),
+            TdgError::Yaml { source, .. } => Some(source),
+            TdgError::Csv { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for TdgError {
+    fn from(source: serde_json::Error) -> Self {
+        TdgError::Json { path: None, source }
+    }
+}
+
+impl From<csv::Error> for TdgError {
+    fn from(source: csv::Error) -> Self {
+        TdgError::Csv { path: None, source }
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_file_open() {
+        let err = TdgError::FileOpen {
+            path: "./tests/samples/bad-path.json".to_string(),
+            source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "Could not open file \"./tests/samples/bad-path.json\" because of not found"
+        );
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn from_serde_json_error() {
+        let err: TdgError = serde_json::from_str::<u32>("{").unwrap_err().into();
+
+        match err {
+            TdgError::Json { path: None, .. } => {}
+            _ => panic!("Expected a Json error, but got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn display_write() {
+        let err = TdgError::Write {
+            source: io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"),
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "Could not write the generated data because of broken pipe"
+        );
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn display_unique_exhausted() {
+        let err = TdgError::UniqueExhausted {
+            field: Some("id".to_string()),
+            generated: 10,
+            capacity: 10.0,
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "The unique values of the field \"id\" are exhausted: 10 values were generated out of an estimated capacity of 10"
+        );
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn display_privacy_exhausted() {
+        let err = TdgError::PrivacyExhausted {
+            field: None,
+            retries: 5,
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "Could not generate a value of the field \"\" that isn't too close to an analyzed value within 5 retries"
+        );
+    }
+
+    #[test]
+    fn display_missing_field() {
+        let err = TdgError::MissingField {
+            field: "profiles".to_string(),
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "The archive is missing the field \"profiles\""
+        );
+        assert!(err.source().is_none());
+    }
+}
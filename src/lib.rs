@@ -142,9 +142,21 @@ extern crate regex;
 extern crate serde;
 extern crate serde_json;
 extern crate serde_yaml;
-extern crate yaml_rust;
 
+use crate::engine::char_class::{self, CharClass};
+use crate::engine::data_type::DataType;
+use crate::engine::date_time::DateTimeModel;
+use crate::engine::fact_index::FactIndex;
+use crate::engine::markov::{MarkovChain, MAX_ORDER, MIN_ORDER};
+use crate::engine::missing::{MissingValues, NULL_SENTINELS};
+use crate::engine::noise::DifferentialPrivacy;
+use crate::engine::numeric::NumericModel;
+use crate::engine::pattern_chain::PatternChain;
+use crate::engine::privacy::PrivacyGuard;
+use crate::engine::semantic::SemanticModel;
+use crate::engine::unique::UniqueValues;
 use crate::engine::{Fact, PatternDefinition};
+use crate::errors::{TdgError, TdgResult};
 use std::collections::BTreeMap;
 use std::fs::File;
 use std::io;
@@ -156,6 +168,12 @@ type PatternMap = BTreeMap<String, u32>;
 type SizeMap = BTreeMap<u32, u32>;
 type SizeRankMap = BTreeMap<u32, f64>;
 
+/// The number of times a pattern is synthesized to find one that wasn't analyzed, (see set_novelty())
+const SYNTHESIS_ATTEMPTS: usize = 8;
+/// The number of counts of the typed models that a single value changes at most, (a bin of the numeric model,
+/// the count and an offset of the date model, and the count and a component of the semantic model)
+const MODEL_COUNTS: u32 = 5;
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 /// Represents a Profile for sample data that has been analyzed and can be used to generate realistic data
 pub struct Profile {
@@ -181,10 +199,66 @@ pub struct Profile {
     pub size_total: u32,
     /// A list of pattern sizes (lengths) with a running total of their percent chance of occurrence, in increasing order
     pub size_ranks: Vec<(u32, f64)>,
-    /// The number of processors used to distribute the work load (multi-thread) while finding Facts to generate data
+    /// The number of workers (threads) of the pool that analyzes the entities while finding Facts to generate data
     pub processors: u8,
-    /// A list of processors (which are lists of Facts) that store all the Facts in the profile
-    pub facts: Vec<Vec<Fact>>,
+    /// The Facts in the profile, indexed by the attributes that are used to find them while generating data
+    /// (archives that store the Facts as a list of processors with lists of Facts are also supported)
+    pub facts: FactIndex,
+    /// The seed used for the random number generator so that the generated data can be reproduced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// The n-gram transition model that is used to choose chars based on the previously generated chars (if enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markov: Option<MarkovChain>,
+    /// The type of the analyzed (non-empty) values, (e.g.: numbers or booleans), None if no values have been analyzed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<DataType>,
+    /// The empty values and null sentinel tokens (e.g.: NULL, N/A) that were analyzed, (they aren't analyzed as patterns)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub missing: Option<MissingValues>,
+    /// The tokens that represent a null value, (None for the default tokens, see set_null_sentinels())
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub null_sentinels: Option<Vec<String>>,
+    /// The distribution (range, histogram and decimal precision) of the analyzed numbers, so that the values of numeric fields
+    /// are generated from the distribution instead of digit by digit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numeric: Option<NumericModel>,
+    /// The format and the range of the analyzed dates and timestamps, (None if a value isn't a date of the same format as the others)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_time: Option<DateTimeModel>,
+    /// The semantic type (e.g.: email, UUID, credit card) of the analyzed values, (None if a value isn't of the same semantic type as the others)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub semantic: Option<SemanticModel>,
+    /// The number of days to shift the range of the generated dates by, (see set_date_shift())
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_shift: Option<i64>,
+    /// Indicates if numbers of 9 or 10 digits are analyzed as epoch seconds, (see set_epoch_seconds())
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub epoch_seconds: bool,
+    /// The custom classes of chars that the values are analyzed with, in the order they are checked in (see add_char_class())
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub char_classes: Vec<CharClass>,
+    /// The chance (0.0 to 1.0) that a value is generated from a synthesized pattern instead of an analyzed pattern, (see set_novelty())
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub novelty: Option<f64>,
+    /// The values that have been generated in uniqueness mode, (see set_unique())
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique: Option<UniqueValues>,
+    /// The keyed hashes of the analyzed values that aren't generated in privacy mode, (see set_privacy())
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privacy: Option<PrivacyGuard>,
+    /// The Markov model of the pattern symbols that synthesizes the patterns (lazily built from the patterns)
+    #[serde(skip)]
+    pattern_chain: Option<PatternChain>,
+    /// The random number generator used when generating data (lazily created from the seed)
+    #[serde(skip)]
+    rng: Option<fastrand::Rng>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile::new()
+    }
 }
 
 impl Profile {
@@ -214,7 +288,23 @@ impl Profile {
             size_total: 0,
             size_ranks: Vec::new(),
             processors: 4,
-            facts: Profile::new_facts(4),
+            facts: FactIndex::new(),
+            seed: None,
+            markov: None,
+            data_type: None,
+            missing: None,
+            null_sentinels: None,
+            numeric: None,
+            date_time: None,
+            semantic: None,
+            date_shift: None,
+            epoch_seconds: false,
+            char_classes: Vec::new(),
+            novelty: None,
+            unique: None,
+            privacy: None,
+            pattern_chain: None,
+            rng: None,
         }
     }
 
@@ -244,19 +334,33 @@ impl Profile {
             size_total: 0,
             size_ranks: Vec::new(),
             processors: 4,
-            facts: Profile::new_facts(4),
+            facts: FactIndex::new(),
+            seed: None,
+            markov: None,
+            data_type: None,
+            missing: None,
+            null_sentinels: None,
+            numeric: None,
+            date_time: None,
+            semantic: None,
+            date_shift: None,
+            epoch_seconds: false,
+            char_classes: Vec::new(),
+            novelty: None,
+            unique: None,
+            privacy: None,
+            pattern_chain: None,
+            rng: None,
         }
     }
 
     /// Constructs a new Profile with a specified number of processors to analyze the data.
-    /// Each processor shares the load of generating the data based on the Facts it has been assigned to manage.
+    /// Each processor is a worker of the pool that shares the load of analyzing the entities in batches.
     ///
     /// # Arguments
     ///
-    /// * `p: u8` - A number that sets the number of processors to start up to manage the Facts.</br>
-    ///         Increasing the number of processors will speed up the generator be distributing the workload.
-    ///         The recommended number of processors is 1 per 10K data points (e.g.: profiling 20K names should be handled by 2 processors)</br>
-    ///         NOTE: The default number of processors is 4.
+    /// * `p: u8` - A number that sets the number of processors (worker threads) to start up to analyze the entities.</br>
+    ///   NOTE: The default number of processors is 4. Since the Facts are indexed, the number of processors doesn't affect the speed of the generator.
     ///
     /// #Example
     ///
@@ -283,15 +387,32 @@ impl Profile {
             size_total: 0,
             size_ranks: Vec::new(),
             processors: p,
-            facts: Profile::new_facts(p),
+            facts: FactIndex::new(),
+            seed: None,
+            markov: None,
+            data_type: None,
+            missing: None,
+            null_sentinels: None,
+            numeric: None,
+            date_time: None,
+            semantic: None,
+            date_shift: None,
+            epoch_seconds: false,
+            char_classes: Vec::new(),
+            novelty: None,
+            unique: None,
+            privacy: None,
+            pattern_chain: None,
+            rng: None,
         }
     }
 
-    /// Constructs a new Profile from an exported JSON file. This is used when restoring from "archive"
+    /// Constructs a new Profile that uses a seeded random number generator.
+    /// Two profiles with the same seed that have analyzed the same data will generate the same test data.
     ///
     /// # Arguments
     ///
-    /// * `path: &str` - The full path of the export file , excluding the file extension, (e.g.: "./test/data/custom-names").</br>
+    /// * `seed: u64` - The seed for the random number generator.</br>
     ///
     /// #Example
     ///
@@ -301,54 +422,1463 @@ impl Profile {
     /// use test_data_generation::Profile;
     ///
     /// fn main() {
-    ///        let mut profile = Profile::from_file("./tests/samples/sample-00-profile");
+    ///     let mut profile_a = Profile::new_with_seed(1234);
+    ///     let mut profile_b = Profile::new_with_seed(1234);
+    ///
+    ///     for name in ["Smith, John", "Doe, John", "Dale, Danny"].iter() {
+    ///         profile_a.analyze(name);
+    ///         profile_b.analyze(name);
+    ///     }
+    ///
+    ///     profile_a.pre_generate();
+    ///     profile_b.pre_generate();
+    ///
+    ///     assert_eq!(profile_a.generate(), profile_b.generate());
+    /// }
+    /// ```
+    pub fn new_with_seed(seed: u64) -> Profile {
+        let mut profile = Profile::new();
+        profile.set_seed(seed);
+        profile
+    }
+
+    /// This function (re)seeds the random number generator of the Profile.
+    /// The seed is stored with the Profile, so an exported Profile will generate the same data when restored.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed: u64` - The seed for the random number generator.</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.analyze("Smith, John");
+    ///     profile.analyze("Doe, John");
+    ///     profile.pre_generate();
+    ///
+    ///     profile.set_seed(1234);
+    ///     let first = profile.generate();
+    ///
+    ///     profile.set_seed(1234);
+    ///     assert_eq!(profile.generate(), first);
+    /// }
+    /// ```
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+        self.rng = Some(fastrand::Rng::with_seed(seed));
+    }
+
+    /// This function injects the random number generator the Profile uses when generating data.
+    /// _NOTE:_ The seed of an injected generator is not known, so it is not stored with the Profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng: fastrand::Rng` - The random number generator to use.</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate fastrand;
     ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.analyze("Smith, John");
     ///     profile.pre_generate();
     ///
+    ///     profile.set_rng(fastrand::Rng::with_seed(1234));
     ///     println!("The generated name is {:?}", profile.generate());
     /// }
     /// ```
-    pub fn from_file(path: &'static str) -> Profile {
-        // open the archive file
-        let mut file = match File::open(format!("{}.json", &path)) {
-            Err(_e) => {
-                error!("Could not open file {:?}", &path.to_string());
-                panic!("Could not open file {:?}", &path.to_string());
-            }
-            Ok(f) => {
-                info!("Successfully opened file {:?}", &path.to_string());
-                f
-            }
+    pub fn set_rng(&mut self, rng: fastrand::Rng) {
+        self.seed = None;
+        self.rng = Some(rng);
+    }
+
+    /// This function returns the random number generator of the Profile, creating it from the seed (if any) on first use.
+    #[inline]
+    fn rng(&mut self) -> &mut fastrand::Rng {
+        Self::init_rng(&mut self.rng, self.seed)
+    }
+
+    #[inline]
+    fn init_rng(rng: &mut Option<fastrand::Rng>, seed: Option<u64>) -> &mut fastrand::Rng {
+        rng.get_or_insert_with(|| match seed {
+            Some(s) => fastrand::Rng::with_seed(s),
+            None => fastrand::Rng::new(),
+        })
+    }
+
+    /// This function shifts the range of the generated dates by a number of days, (e.g.: 365 to generate the dates a year later than analyzed).
+    /// The dates are still calendar-valid and in the format they were analyzed in.
+    ///
+    /// # Arguments
+    ///
+    /// * `days: i64` - The number of days to shift the range by, (negative to shift it to earlier dates).</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.set_date_shift(-1);
+    ///
+    ///     profile.analyze("2017-01-01");
+    ///     profile.pre_generate();
+    ///
+    ///     assert_eq!(profile.generate(), "2016-12-31");
+    /// }
+    /// ```
+    pub fn set_date_shift(&mut self, days: i64) {
+        self.date_shift = Some(days);
+    }
+
+    /// This function enables (or disables) the detection of epoch seconds, so that a field of numbers of 9 or 10 digits
+    /// (e.g.: 1483525800) is generated as calendar-valid timestamps within the analyzed range instead of as numbers.
+    /// It is disabled by default, since numbers of the same length (e.g.: ids and phone numbers) would be generated as timestamps.
+    /// NOTE: Enable the detection before analyzing, since the values that were already analyzed aren't analyzed again.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled: bool` - true to detect epoch seconds, false to analyze the numbers as numbers.</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.set_epoch_seconds(true);
+    ///
+    ///     profile.analyze("1483488000");
+    ///     profile.analyze("1483574399");
+    ///
+    ///     assert_eq!(profile.date_time.unwrap().format(), "%s");
+    /// }
+    /// ```
+    pub fn set_epoch_seconds(&mut self, enabled: bool) {
+        self.epoch_seconds = enabled;
+    }
+
+    /// This function adds a custom class of chars with its own pattern symbol (e.g.: hex digits), so that its chars are analyzed
+    /// and generated as a class of their own. The custom classes are checked before the built-in classes, in the order of their priority.
+    /// Returns an InvalidSetting error if the class isn't valid.
+    /// NOTE: Add the classes before analyzing, since the values that were already analyzed keep their patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `class: CharClass` - The custom class to add, (it replaces the custom class with the same symbol).</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::char_class::CharClass;
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.add_char_class(CharClass::chars('h', "0123456789abcdef", 0).unwrap()).unwrap();
+    ///
+    ///     profile.analyze("c0ffee");
+    ///     profile.analyze("beef");
+    ///     profile.pre_generate();
+    ///
+    ///     assert!(profile.patterns.contains_key("hhhhhh"));
+    ///     println!("The generated hex value is {:?}", profile.generate());
+    /// }
+    /// ```
+    pub fn add_char_class(&mut self, class: CharClass) -> TdgResult<()> {
+        char_class::add_class(&mut self.char_classes, class)
+    }
+
+    /// This function enables the n-gram (Markov chain) model that chooses each generated char based on the previous `order` generated chars.
+    /// If no transitions exist for the previous `order` chars, the model falls back to fewer chars, and lastly to the Facts.
+    /// The model is updated by every subsequent analyze(). If the Profile has already analyzed values, the model of a lower order
+    /// is derived from the enabled model, and the model of order 1 is rebuilt from the Facts, but an InvalidSetting error is
+    /// returned for a higher order, since the analyzed entities aren't kept.
+    /// NOTE: Enable the model before analyzing to use an order higher than 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `order: u8` - The number of previous chars to use when choosing the next char (1 to 4).</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.set_markov_order(3).unwrap();
+    ///
+    ///     profile.analyze("Jonathon");
+    ///     profile.analyze("Johnathan");
+    ///     profile.pre_generate();
+    ///
+    ///     println!("The generated name is {:?}", profile.generate());
+    ///     assert!(profile.set_markov_order(5).is_err());
+    /// }
+    /// ```
+    pub fn set_markov_order(&mut self, order: u8) -> TdgResult<()> {
+        if !(MIN_ORDER..=MAX_ORDER).contains(&order) {
+            return Err(TdgError::InvalidSetting {
+                setting: "markov_order".to_string(),
+                message: format!(
+                    "the order must be between {} and {}, but was {}",
+                    MIN_ORDER, MAX_ORDER, order
+                ),
+            });
+        }
+
+        // the transitions of the longer contexts have been learned
+        if let Some(chain) = self.markov.as_mut().filter(|chain| chain.order >= order) {
+            chain.lower_order(order);
+            return Ok(());
+        }
+
+        // only the transitions of order 1 can be recovered from the Facts
+        if order > MIN_ORDER && !self.facts.is_empty() {
+            return Err(TdgError::InvalidSetting {
+                setting: "markov_order".to_string(),
+                message: format!(
+                    "the order can't be raised to {} after values have been analyzed, (set it before analyzing)",
+                    order
+                ),
+            });
+        }
+
+        let mut chain = MarkovChain::new(order);
+        chain.learn_indexed(&self.facts);
+        self.markov = Some(chain);
+
+        Ok(())
+    }
+
+    /// This function sets the novelty of the generated data, (the chance that a value is generated from a pattern that is synthesized
+    /// by a Markov model of the pattern symbols instead of from an analyzed pattern). The higher the novelty, the more often
+    /// and the further the patterns stray from the analyzed patterns, (e.g.: `Cvccvc` from the patterns `Cvcc`, `Cvccv` and `Cvcvcc`).
+    /// A novelty of 0.0 only generates data from the analyzed patterns, (the default).
+    /// Returns an InvalidSetting error if the novelty isn't between 0.0 and 1.0.
+    ///
+    /// # Arguments
+    ///
+    /// * `novelty: f64` - The chance (0.0 to 1.0) that a value is generated from a synthesized pattern.</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.set_novelty(0.5).unwrap();
+    ///
+    ///     profile.analyze("Jon");
+    ///     profile.analyze("Jonah");
+    ///     profile.analyze("Jonathan");
+    ///     profile.pre_generate();
+    ///
+    ///     println!("The generated name is {:?}", profile.generate());
+    ///     assert!(profile.set_novelty(1.5).is_err());
+    /// }
+    /// ```
+    pub fn set_novelty(&mut self, novelty: f64) -> TdgResult<()> {
+        if !(0.0..=1.0).contains(&novelty) {
+            return Err(TdgError::InvalidSetting {
+                setting: "novelty".to_string(),
+                message: format!("the novelty must be between 0 and 1, but was {}", novelty),
+            });
+        }
+
+        self.novelty = Some(novelty);
+
+        Ok(())
+    }
+
+    /// This function enables (or disables) the uniqueness mode, (e.g.: for primary keys and usernames).
+    /// In uniqueness mode, a generated value that was already generated is discarded and generated again, up to a budget of
+    /// retries (see UniqueValues), and a UniqueExhausted error is returned once the budget is spent. Missing values (see missing_rate())
+    /// aren't required to be unique. Enabling the mode forgets the values that were already generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled: bool` - true to only generate values that weren't already generated, false to allow repeated values.</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.set_unique(true);
+    ///
+    ///     profile.analyze("B1");
+    ///     profile.analyze("C2");
+    ///     profile.pre_generate();
+    ///
+    ///     // there are only 4 values, (B1, B2, C1 and C2)
+    ///     for _ in 0..4 {
+    ///         profile.try_generate().unwrap();
+    ///     }
+    ///     assert!(profile.try_generate().is_err());
+    /// }
+    /// ```
+    pub fn set_unique(&mut self, enabled: bool) {
+        self.unique = match enabled {
+            true => Some(UniqueValues::default()),
+            false => None,
         };
+    }
 
-        //read the archive file
-        let mut serialized = String::new();
-        match file.read_to_string(&mut serialized) {
-            Err(e) => {
-                error!(
-                    "Could not read file {:?} because of {:?}",
-                    &path.to_string(),
-                    e.to_string()
-                );
-                panic!(
-                    "Could not read file {:?} because of {:?}",
-                    &path.to_string(),
-                    e.to_string()
-                );
+    /// This function enables (or disables) the no-verbatim privacy mode, (e.g.: for samples of personal data), so that the Profile
+    /// doesn't generate the values it analyzed. The keyed hashes of the analyzed values are kept (and exported with the Profile)
+    /// instead of the values, and a generated value that matches one of them is discarded and generated again, up to a budget of
+    /// retries (see PrivacyGuard), after which a PrivacyExhausted error is returned. A generated value can also be required to be at
+    /// a minimum Levenshtein distance from every analyzed value, (which is checked until the Profile is exported, since the values
+    /// aren't exported). Missing values (see missing_rate()) are always allowed.
+    /// The secret key of the hashes is random and isn't exported, so a restored Profile returns an InvalidSetting error when it
+    /// generates a value until the key is supplied again, (see privacy_key() and set_privacy_key()).
+    /// NOTE: Enable the mode before analyzing, since the values that were already analyzed can't be recovered from the Profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_distance: Option<usize>` - The minimum Levenshtein distance from every analyzed value, (0 to only reject the analyzed values), or None to disable the mode.</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.set_privacy(Some(0));
+    ///
+    ///     profile.analyze("O'Brien");
+    ///     profile.pre_generate();
+    ///
+    ///     // the only value the profile can generate is the analyzed value
+    ///     assert!(profile.try_generate().is_err());
+    /// }
+    /// ```
+    pub fn set_privacy(&mut self, min_distance: Option<usize>) {
+        self.privacy = min_distance.map(PrivacyGuard::new);
+    }
+
+    /// This function returns the secret key of the hashes of the privacy mode (see set_privacy()), or None if the Profile isn't in
+    /// privacy mode or it was restored and the key hasn't been supplied again. Keep the key outside of the exported Profile,
+    /// (e.g.: in a secret store), so that it can be supplied again with set_privacy_key().
+    pub fn privacy_key(&self) -> Option<u128> {
+        self.privacy.as_ref().and_then(|guard| guard.key())
+    }
+
+    /// This function supplies the secret key of the hashes of the privacy mode, (e.g.: of a restored Profile, see privacy_key()).
+    /// Returns an InvalidSetting error if the Profile isn't in privacy mode or it isn't the key the values were analyzed with.
+    ///
+    /// # Arguments
+    ///
+    /// * `key: u128` - The secret key of the hashes of the analyzed values.</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.set_privacy(Some(0));
+    ///     profile.analyze("O'Brien");
+    ///     let key = profile.privacy_key().unwrap();
+    ///
+    ///     let mut restored = Profile::from_serialized(&profile.serialize());
+    ///     restored.pre_generate();
+    ///     assert!(restored.try_generate().is_err());
+    ///
+    ///     restored.set_privacy_key(key).unwrap();
+    ///     assert!(restored.privacy_key().is_some());
+    /// }
+    /// ```
+    pub fn set_privacy_key(&mut self, key: u128) -> TdgResult<()> {
+        match self.privacy.as_mut() {
+            Some(guard) => guard.set_key(key),
+            None => Err(TdgError::InvalidSetting {
+                setting: "privacy_key".to_string(),
+                message: "the Profile isn't in privacy mode".to_string(),
+            }),
+        }
+    }
+
+    /// This function estimates the number of distinct values the Profile can generate, (the capacity of its value space).
+    /// The capacity of a date or numeric field is based on the range of the analyzed values, and the capacity of the patterns
+    /// is the number of combinations of the chars that were analyzed for each position of each pattern, so it is an upper bound.
+    /// Fields of a semantic type and Profiles that synthesize patterns (see set_novelty()) have an infinite capacity.
+    /// In privacy mode (see set_privacy()), the analyzed values are excluded from the capacity.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     profile.analyze("B1");
+    ///     profile.analyze("C2");
+    ///     profile.analyze("D");
+    ///
+    ///     assert_eq!(profile.capacity(), 5.0);
+    /// }
+    /// ```
+    pub fn capacity(&self) -> f64 {
+        // the analyzed values aren't generated in privacy mode
+        let analyzed = self.privacy.as_ref().map_or(0, |p| p.len()) as f64;
+
+        (self.value_space() - analyzed).max(0.0)
+    }
+
+    // estimates the number of distinct values that the models and the patterns of the Profile can generate
+    fn value_space(&self) -> f64 {
+        if self.semantic.is_some() || self.novelty.is_some_and(|n| n > 0.0) {
+            return f64::INFINITY;
+        }
+
+        if let Some(model) = self.date_time.as_ref() {
+            return model.capacity();
+        }
+
+        if let (Some(DataType::Integer) | Some(DataType::Decimal), Some(numeric)) =
+            (self.data_type, self.numeric.as_ref())
+        {
+            return numeric.capacity();
+        }
+
+        self.patterns
+            .keys()
+            .map(|pattern| {
+                self.keys_per_position(pattern)
+                    .iter()
+                    .map(|keys| *keys as f64)
+                    .product::<f64>()
+            })
+            .sum()
+    }
+
+    // counts the chars that can be generated for each position of the pattern
+    fn keys_per_position(&self, pattern: &str) -> Vec<usize> {
+        // the n-gram model can choose any char of a symbol, regardless of its position
+        let any_position = self.markov.is_some();
+        let last = pattern.chars().count().saturating_sub(1);
+
+        pattern
+            .chars()
+            .enumerate()
+            .map(|(idx, ch)| {
+                let position = (idx as u32, (idx == 0) as u32, (idx == last) as u32);
+                self.facts
+                    .count_keys(ch, Some(position).filter(|_| !any_position))
+            })
+            .collect()
+    }
+
+    /// This function returns a copy of the Profile with noisy statistics instead of the statistics of the personal data it
+    /// analyzed, (e.g.: to export it with try_save()). Noise that is calibrated by the privacy budget is added to the counts
+    /// of the patterns, the sizes, the Facts, the transitions of the Markov chain and the typed models, and the keys whose noisy
+    /// count is below the k-threshold are suppressed, (as well as the patterns that can't be generated from the remaining Facts).
+    /// The smallest and the largest analyzed numbers and dates aren't exported: the numeric bins are generalized to a grid of
+    /// round numbers (see NumericModel::generalized_bins()) and the ranges of the dates to whole months or years (see
+    /// DateTimeModel::generalize()). The hashes of the privacy mode (see set_privacy()) are removed, since they reveal whether
+    /// a value was analyzed to whoever holds their key.
+    /// The noise isn't drawn from the seeded random number generator of the Profile, so that it can't be reproduced.
+    /// NOTE: The guarantee is (epsilon, delta)-differential privacy: a pattern, size or Fact that only a single value added is
+    /// still exported with the chance of DifferentialPrivacy::delta(), (e.g.: more than half of the time with a k-threshold of 1),
+    /// which reveals that the value was analyzed, so choose a k-threshold whose delta is negligible (see engine::noise).
+    /// Returns an InvalidSetting error if epsilon isn't a positive number.
+    ///
+    /// # Arguments
+    ///
+    /// * `dp: &DifferentialPrivacy` - The privacy budget and the k-threshold, (see engine::noise::DifferentialPrivacy).</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::noise::DifferentialPrivacy;
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile = Profile::new();
+    ///     for _ in 0..50 {
+    ///         profile.analyze("Smith");
+    ///     }
+    ///     profile.analyze("O'Brien");
+    ///
+    ///     let shared = profile.privatize(&DifferentialPrivacy::new(10.0, 5).unwrap()).unwrap();
+    ///
+    ///     // the pattern of the single O'Brien is suppressed, (unless the noise lifts its count over the k-threshold)
+    ///     assert!(shared.patterns.contains_key("Ccvcc"));
+    /// }
+    /// ```
+    pub fn privatize(&self, dp: &DifferentialPrivacy) -> TdgResult<Profile> {
+        dp.validate()?;
+
+        let mut rng = fastrand::Rng::new();
+        let mut profile = self.clone();
+        profile.privacy = None;
+        profile.rng = None;
+
+        profile.patterns = dp
+            .release(self.patterns.clone(), 1, dp.k_threshold, &mut rng)
+            .into_iter()
+            .collect();
+        profile.sizes = dp
+            .release(self.sizes.clone(), 1, dp.k_threshold, &mut rng)
+            .into_iter()
+            .collect();
+
+        // a value is counted by a Fact for each of its chars
+        let longest = self.sizes.keys().max().copied().unwrap_or(1);
+        profile.facts = FactIndex::new();
+        for ((fact_key, key), count) in dp.release(
+            self.facts.iter().map(|(f, k, c)| ((*f, k), c)),
+            longest,
+            dp.k_threshold,
+            &mut rng,
+        ) {
+            profile.facts.insert_weighted(fact_key, key, count);
+        }
+
+        // only the noisy transitions between the chars of the analyzed entities are released
+        if let Some(chain) = profile.markov.as_mut() {
+            // a value is counted by a transition for each of its chars and each order up to the order of the model
+            let transitions = std::mem::take(&mut chain.transitions);
+            for ((context, c), count) in dp.release(
+                transitions
+                    .iter()
+                    .flat_map(|(k, m)| m.iter().map(move |(c, n)| ((k.clone(), *c), *n))),
+                longest * (chain.order as u32 + 1),
+                dp.k_threshold,
+                &mut rng,
+            ) {
+                chain
+                    .transitions
+                    .entry(context)
+                    .or_default()
+                    .insert(c, count);
+            }
+        }
+
+        let unreachable: Vec<String> = profile
+            .patterns
+            .keys()
+            .filter(|pattern| profile.keys_per_position(pattern).contains(&0))
+            .cloned()
+            .collect();
+        for pattern in unreachable.iter() {
+            profile.patterns.remove(pattern);
+        }
+
+        profile.privatize_models(dp, &mut rng);
+        profile.update_pattern_totals();
+        profile.pre_generate();
+
+        Ok(profile)
+    }
+
+    // adds noise to the counts of the typed models, (see privatize())
+    fn privatize_models(&mut self, dp: &DifferentialPrivacy, rng: &mut fastrand::Rng) {
+        if let Some(missing) = self.missing.as_mut() {
+            missing.empty = dp.noisy_count(missing.empty, MODEL_COUNTS, rng);
+            missing.nulls = dp.noisy_count(missing.nulls, MODEL_COUNTS, rng);
+            missing.sentinels = dp
+                .release(
+                    std::mem::take(&mut missing.sentinels),
+                    MODEL_COUNTS,
+                    dp.k_threshold,
+                    rng,
+                )
+                .into_iter()
+                .collect();
+        }
+
+        // the bins are generalized, so that their bounds aren't analyzed values
+        if let Some(numeric) = self.numeric.as_mut() {
+            numeric.bins = dp
+                .release(
+                    numeric
+                        .generalized_bins()
+                        .into_iter()
+                        .map(|b| ((b.0, b.1), b.2)),
+                    MODEL_COUNTS,
+                    dp.k_threshold,
+                    rng,
+                )
+                .into_iter()
+                .map(|((low, high), count)| (low, high, count))
+                .collect();
+            numeric.count = numeric.bins.iter().map(|b| b.2).sum();
+
+            // the range of the remaining bins
+            match (numeric.bins.first(), numeric.bins.last()) {
+                (Some(first), Some(last)) => {
+                    numeric.min = first.0;
+                    numeric.max = last.1;
+                }
+                _ => self.numeric = None,
+            }
+        }
+
+        if let Some(model) = self.date_time.as_mut() {
+            model.generalize();
+            model.count = dp.noisy_count(model.count, MODEL_COUNTS, rng);
+            model.offsets = dp
+                .release(
+                    std::mem::take(&mut model.offsets),
+                    MODEL_COUNTS,
+                    dp.k_threshold,
+                    rng,
+                )
+                .into_iter()
+                .collect();
+        }
+
+        if let Some(model) = self.semantic.as_mut() {
+            model.count = dp.noisy_count(model.count, MODEL_COUNTS, rng);
+            model.components = dp
+                .release(
+                    std::mem::take(&mut model.components),
+                    MODEL_COUNTS,
+                    dp.k_threshold,
+                    rng,
+                )
+                .into_iter()
+                .collect();
+        }
+    }
+
+    /// Constructs a new Profile from an exported JSON file. This is used when restoring from "archive"
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The full path of the export file , excluding the file extension, (e.g.: "./test/data/custom-names").</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///        let mut profile = Profile::from_file("./tests/samples/sample-00-profile");
+    ///
+    ///     profile.pre_generate();
+    ///
+    ///     println!("The generated name is {:?}", profile.generate());
+    /// }
+    /// ```
+    pub fn from_file(path: &'static str) -> Profile {
+        Self::try_from_file(path).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Constructs a new Profile from an exported JSON file, returning an error instead of panicking if the file
+    /// can't be opened, read or parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The full path of the export file , excluding the file extension, (e.g.: "./test/data/custom-names").</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     assert!(Profile::try_from_file("./tests/samples/sample-00-profile").is_ok());
+    ///     assert!(Profile::try_from_file("./tests/samples/bad-path").is_err());
+    /// }
+    /// ```
+    pub fn try_from_file(path: &str) -> TdgResult<Profile> {
+        let file_name = format!("{}.json", &path);
+
+        // open the archive file
+        let mut file = match File::open(&file_name) {
+            Err(e) => {
+                error!("Could not open file {:?}", &path.to_string());
+                return Err(TdgError::FileOpen {
+                    path: path.to_string(),
+                    source: e,
+                });
+            }
+            Ok(f) => {
+                info!("Successfully opened file {:?}", &path.to_string());
+                f
+            }
+        };
+
+        //read the archive file
+        let mut serialized = String::new();
+        match file.read_to_string(&mut serialized) {
+            Err(e) => {
+                error!(
+                    "Could not read file {:?} because of {:?}",
+                    &path.to_string(),
+                    e.to_string()
+                );
+                return Err(TdgError::FileRead {
+                    path: path.to_string(),
+                    source: e,
+                });
+            }
+            Ok(s) => {
+                info!("Successfully read file {:?}", &path.to_string());
+                s
+            }
+        };
+
+        Self::try_from_serialized(&serialized).map_err(|e| match e {
+            TdgError::Json { source, .. } => TdgError::Json {
+                path: Some(file_name),
+                source,
+            },
+            other => other,
+        })
+    }
+
+    /// Constructs a new Profile from a serialized (JSON) string of the Profile object. This is used when restoring from "archive"
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///        let serialized = "{\"patterns\":{\"VC\":1},\"pattern_total\":1,\"pattern_keys\":[\"VC\"],\"pattern_vals\":[1],\"pattern_percentages\":[],\"pattern_ranks\":[],\"sizes\":{\"2\":1},\"size_total\":1,\"size_ranks\":[],\"processors\":4,\"facts\":[[{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0}],[{\"key\":\"K\",\"prior_key\":\"O\",\"next_key\":null,\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1}],[],[]]}";
+    ///        let mut profile = Profile::from_serialized(&serialized);
+    ///
+    ///     profile.pre_generate();
+    ///
+    ///     println!("The generated name is {:?}", profile.generate());
+    /// }
+    /// ```
+    pub fn from_serialized(serialized: &str) -> Profile {
+        Self::try_from_serialized(serialized).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Constructs a new Profile from a serialized (JSON) string of the Profile object, returning an error instead of panicking
+    /// if the string isn't a valid Profile.
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     assert!(Profile::try_from_serialized("{\"patterns\":").is_err());
+    /// }
+    /// ```
+    pub fn try_from_serialized(serialized: &str) -> TdgResult<Profile> {
+        Ok(serde_json::from_str(serialized)?)
+    }
+
+    /// This function converts an data point (&str) to a pattern and adds it to the profile
+    ///
+    /// # Arguments
+    ///
+    /// * `entity: String` - The textual str of the value to analyze.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile =  Profile::new();
+    ///        profile.analyze("One");
+    ///        profile.analyze("Two");
+    ///        profile.analyze("Three");
+    ///        profile.analyze("Four");
+    ///
+    ///        assert_eq!(profile.patterns.len(), 4);
+    /// }
+    /// ```
+    pub fn analyze(&mut self, entity: &str) {
+        let _t = self.try_analyze(entity).map_err(|e| {
+            error!(
+                "Warning: Couldn't apply the pattern and facts for the entity {}!",
+                entity
+            );
+            e.to_string()
+        });
+    }
+
+    /// This function converts an data point (&str) to a pattern and adds it to the profile,
+    /// returning an error if the pattern and facts couldn't be applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity: String` - The textual str of the value to analyze.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile =  Profile::new();
+    ///
+    ///        assert!(profile.try_analyze("One").is_ok());
+    /// }
+    /// ```
+    pub fn try_analyze(&mut self, entity: &str) -> TdgResult<()> {
+        let rslt = PatternDefinition::with_classes(&self.char_classes).analyze(entity);
+        self.apply_facts(rslt.0, rslt.1)
+            .map(|_| ())
+            .map_err(|message| TdgError::Analyze { message })
+    }
+
+    /// This function applies the pattern and list of Facts  to the profile
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern: String` - The string the represents the pattern of the entity that was analyzed.</br>
+    /// * `facts: Vec<Fact>` - A Vector containing the Facts based on the analysis (one for each char in the entity).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::{Fact, PatternDefinition};
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile =  Profile::new();
+    ///        let results = PatternDefinition::new().analyze("Word");
+    ///
+    ///        assert_eq!(profile.apply_facts(results.0, results.1).unwrap(), 1);
+    /// }
+    /// ```
+    #[inline]
+    pub fn apply_facts(&mut self, pattern: String, facts: Vec<Fact>) -> Result<i32, String> {
+        self.record_facts(pattern, &facts);
+        self.update_pattern_totals();
+
+        Ok(1)
+    }
+
+    /// This function applies the patterns and lists of Facts of many analyzed entities to the profile,
+    /// and only updates the pattern totals once (instead of once per entity as `apply_facts()` does)
+    ///
+    /// # Arguments
+    ///
+    /// * `results: Vec<(String, Vec<Fact>)>` - The pattern and Facts of each entity that was analyzed.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::PatternDefinition;
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile =  Profile::new();
+    ///     let mut pttrn_def = PatternDefinition::new();
+    ///     let results = vec![pttrn_def.analyze("Word"), pttrn_def.analyze("Wide")];
+    ///
+    ///     assert_eq!(profile.apply_facts_all(results).unwrap(), 2);
+    ///     assert_eq!(profile.pattern_total, 2);
+    /// }
+    /// ```
+    pub fn apply_facts_all(&mut self, results: Vec<(String, Vec<Fact>)>) -> Result<i32, String> {
+        let cnt = results.len() as i32;
+
+        for (pattern, facts) in results {
+            self.record_facts(pattern, &facts);
+        }
+        self.update_pattern_totals();
+
+        Ok(cnt)
+    }
+
+    #[inline]
+    fn record_facts(&mut self, pattern: String, facts: &[Fact]) {
+        // the entity is made of the keys of its Facts
+        let entity: String = facts.iter().map(|f| f.key).collect();
+
+        // count the missing values, so that they are generated at the same rate instead of as patterns
+        if self.is_missing(&entity) {
+            self.missing
+                .get_or_insert_with(MissingValues::default)
+                .learn(&entity);
+            return;
+        }
+
+        // keep the keyed hash of the value, so that it isn't generated in privacy mode
+        if let Some(guard) = self.privacy.as_mut() {
+            guard.learn(&entity);
+        }
+
+        // learn the transitions between the chars
+        if let Some(chain) = self.markov.as_mut() {
+            chain.learn(facts);
+        }
+
+        // index the facts so they can be found without scanning while generating data
+        self.facts.insert_all(facts);
+
+        // learn the format and the range of the dates, as long as every value is a date of the same format
+        if self.patterns.is_empty() {
+            self.date_time = DateTimeModel::detect_with(&entity, self.epoch_seconds);
+        } else if let Some(model) = self.date_time.as_mut() {
+            if !model.learn(&entity) {
+                self.date_time = None;
+            }
+        }
+
+        // detect the semantic type (e.g.: email), as long as every value is of the same semantic type
+        if self.patterns.is_empty() {
+            self.semantic = SemanticModel::detect(&entity);
+        } else if let Some(model) = self.semantic.as_mut() {
+            if !model.learn(&entity) {
+                self.semantic = None;
+            }
+        }
+
+        // infer the type of the values from the entity
+        let data_type = DataType::of(&entity);
+        self.data_type = DataType::merge(self.data_type, data_type);
+
+        // learn the distribution of the numbers
+        if let DataType::Integer | DataType::Decimal = data_type {
+            self.numeric
+                .get_or_insert_with(NumericModel::default)
+                .learn(&entity);
+        }
+
+        // analyze sizes
+        AddAssign::add_assign(
+            self.sizes
+                .entry(pattern.chars().count() as u32)
+                .or_insert(0),
+            1,
+        );
+
+        // store the pattern
+        AddAssign::add_assign(self.patterns.entry(pattern).or_insert(0), 1);
+    }
+
+    fn update_pattern_totals(&mut self) {
+        // store the total number of patterns generated so far
+        self.pattern_total = self.patterns.values().sum::<u32>();
+        self.size_total = self.sizes.values().sum::<u32>();
+
+        self.pattern_keys = self.patterns.keys().cloned().collect();
+        self.pattern_vals = self.patterns.values().cloned().collect();
+    }
+
+    /// This function calculates the patterns to use by the chance they will occur (as cumulative percentage) in decreasing order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile =  Profile::new();
+    ///
+    ///        profile.analyze("Smith, John");
+    ///        profile.analyze("O'Brian, Henny");
+    ///        profile.analyze("Dale, Danny");
+    ///        profile.analyze("Rickets, Ronnae");
+    ///        profile.analyze("Richard, Richie");
+    ///        profile.analyze("Roberts, Blake");
+    ///        profile.analyze("Conways, Sephen");
+    ///
+    ///        profile.pre_generate();
+    ///        let test = [("CvccvccpSCvccvv".to_string(), 28.57142857142857 as f64), ("CcvccpSCvcc".to_string(), 42.857142857142854 as f64), ("CvccvccpSCvccvc".to_string(), 57.14285714285714 as f64), ("CvcvcccpSCcvcv".to_string(), 71.42857142857142 as f64), ("CvcvpSCvccc".to_string(), 85.7142857142857 as f64), ("V@CcvvcpSCvccc".to_string(), 99.99999999999997 as f64)];
+    ///
+    ///        assert_eq!(profile.pattern_ranks, test);
+    /// }
+    /// ```
+    #[inline]
+    pub fn cum_patternmap(&mut self) {
+        // Reference: https://users.rust-lang.org/t/cannot-infer-an-appropriate-lifetime-for-autoref/13360/3
+
+        debug!("calculating the cumulative percentage of occurences for data point patterns...");
+
+        // calculate the percentage by patterns
+        // -> {"CcvccpSCvcc": 14.285714285714285, "CvccvccpSCvccvc": 14.285714285714285, "CvccvccpSCvccvv": 28.57142857142857, "CvcvcccpSCcvcv": 14.285714285714285, "CvcvpSCvccc": 14.285714285714285, "V~CcvvcpSCvccc": 14.285714285714285}
+        let n = self.patterns.len();
+
+        // see issue: https://github.com/dsietz/test-data-generation/issues/88
+        self.pattern_percentages.clear();
+
+        for m in 0..n {
+            self.pattern_percentages.push((
+                self.pattern_keys[m].clone(),
+                (self.pattern_vals[m] as f64 / self.pattern_total as f64) * 100.0,
+            ));
+        }
+
+        // sort the ranks by percentages in decreasing order
+        // -> [("CvccvccpSCvccvv", 28.57142857142857), ("CcvccpSCvcc", 14.285714285714285), ("CvccvccpSCvccvc", 14.285714285714285), ("CvcvcccpSCcvcv", 14.285714285714285), ("CvcvpSCvccc", 14.285714285714285), ("V~CcvvcpSCvccc", 14.285714285714285)]
+        self.pattern_percentages
+            .sort_by(|&(_, a), &(_, b)| b.partial_cmp(&a).unwrap());
+
+        // calculate the cumulative sum of the pattern rankings
+        // -> [("CvccvccpSCvccvv", 28.57142857142857), ("CcvccpSCvcc", 42.857142857142854), ("CvccvccpSCvccvc", 57.14285714285714), ("CvcvcccpSCcvcv", 71.42857142857142), ("CvcvpSCvccc", 85.7142857142857), ("V~CcvvcpSCvccc", 99.99999999999997)]
+        let mut rank: f64 = 0.00;
+
+        // see issue: https://github.com/dsietz/test-data-generation/issues/88
+        self.pattern_ranks.clear();
+
+        for pttrn in self.pattern_percentages.iter() {
+            let tmp = pttrn.1 + rank;
+            self.pattern_ranks.push((pttrn.0.clone(), tmp));
+            rank = tmp;
+        }
+    }
+
+    /// This function calculates the sizes to use by the chance they will occur (as cumulative percentage) in decreasing order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile =  Profile::new();
+    ///        profile.analyze("One");
+    ///        profile.analyze("Two");
+    ///        profile.analyze("Three");
+    ///        profile.analyze("Four");
+    ///        profile.analyze("Five");
+    ///        profile.analyze("Six");
+    ///
+    ///     profile.cum_sizemap();
+    ///
+    ///        print!("The size ranks are {:?}", profile.size_ranks);
+    ///     // The size ranks are [(3, 50), (4, 83.33333333333333), (5, 100)]
+    /// }
+    /// ```
+    #[inline]
+    pub fn cum_sizemap(&mut self) {
+        debug!("calculating the cumulative percentage of occurences for data point sizes...");
+        // calculate the percentage by sizes
+        // -> {11: 28.57142857142857, 14: 14.285714285714285, 15: 57.14285714285714}
+        let mut size_ranks = SizeRankMap::new();
+
+        for key in self.sizes.keys() {
+            size_ranks.insert(
+                *key,
+                (*self.sizes.get(key).unwrap() as f64 / self.size_total as f64) * 100.0,
+            );
+        }
+
+        // sort the ranks by percentages in decreasing order
+        // -> [(15, 57.14285714285714), (11, 28.57142857142857), (14, 14.285714285714285)]
+        let mut sizes = size_ranks.iter().collect::<Vec<_>>();
+        sizes.sort_by(|&(_, a), &(_, b)| b.partial_cmp(a).unwrap());
+
+        // calculate the cumulative sum of the size rankings
+        // -> [(15, 57.14285714285714), (11, 85.71428571428571), (14, 100)]
+        self.size_ranks = sizes
+            .iter()
+            .scan((0_u32, 0.00_f64), |state, &(&k, &v)| {
+                *state = (k, state.1 + v);
+                Some(*state)
+            })
+            .collect::<Vec<(_, _)>>();
+    }
+
+    /// This function generates realistic test data based on the sampel data that was analyzed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile =  Profile::new();
+    ///
+    ///        profile.analyze("One");
+    ///        profile.analyze("Two");
+    ///        profile.analyze("Three");
+    ///        profile.analyze("Four");
+    ///        profile.analyze("Five");
+    ///
+    ///     profile.pre_generate();
+    ///
+    ///        print!("The test data {:?} was generated.", profile.generate());
+    /// }
+    /// ```
+    #[inline]
+    pub fn generate(&mut self) -> String {
+        self.try_generate().unwrap_or_else(|e| {
+            error!("{}", e);
+            panic!("{}", e)
+        })
+    }
+
+    /// This function generates realistic test data based on the sampel data that was analyzed,
+    /// returning an error if the profile has no patterns (or facts) to generate the data from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile =  Profile::new();
+    ///     assert!(profile.try_generate().is_err());
+    ///
+    ///        profile.analyze("One");
+    ///     profile.pre_generate();
+    ///
+    ///        assert_eq!(profile.try_generate().unwrap(), "One");
+    /// }
+    /// ```
+    pub fn try_generate(&mut self) -> TdgResult<String> {
+        // 0. generate a missing value at the rate the missing values were analyzed
+        if let Some(missing) = self.missing.as_ref() {
+            let rng = Self::init_rng(&mut self.rng, self.seed);
+            if let Some(value) = missing.sample(self.pattern_total, rng) {
+                return Ok(value);
+            }
+        }
+
+        // missing values aren't required to be unique
+        self.try_accepted(Self::try_generate_present)
+    }
+
+    // generates a (present) value from the models or the patterns of the Profile
+    fn try_generate_present(&mut self) -> TdgResult<String> {
+        // generate the values of semantic, date and numeric fields from their models instead of their patterns
+        if let Some(value) = self.generate_typed() {
+            return Ok(value);
+        }
+
+        // generate the value from a synthesized pattern at the rate of the novelty
+        if let Some(pattern) = self.synthesize_pattern() {
+            let length = pattern.chars().count();
+            return self.try_generate_stretched(&pattern, length, true);
+        }
+
+        // 1. get a random number
+        let s: f64 = random_percentage!(self.rng());
+
+        // 2. find the first pattern that falls within the percentage chance of occurring
+        // NOTE: The following 2 lines has been commented out because this doesn't need to
+        //       happen since the patterns are already ranks by percent chance of occurring
+        //       and therefore sizes (lengths) as well since the patterns include the full
+        //       length of the entitiy analyzed.
+        //let size = self.size_ranks.iter().find(|&&x|&x.1 >= &s).unwrap().0;
+        //let pattern = self.pattern_ranks.iter().find(|x|&x.1 >= &s && x.0.len() == size as usize).unwrap().clone();
+        let pattern = match self.pattern_ranks.iter().find(|x| x.1 >= s) {
+            Some(p) => p.clone(),
+            // the cumulative percentage may fall just short of 100 due to rounding
+            None => match self.pattern_ranks.last() {
+                Some(p) => p.clone(),
+                None => return Err(TdgError::EmptyProfile),
+            },
+        };
+
+        // lastly, generate the test data using facts that adhere to the pattern
+        self.try_generate_from_pattern(pattern.0)
+    }
+
+    /// This function generates realistic test data of a target length, (see try_generate_with_length).
+    ///
+    /// # Arguments
+    ///
+    /// * `min: Option<u32>` - The minimum length of the generated data, (None for no minimum).</br>
+    /// * `max: Option<u32>` - The maximum length of the generated data, (None for no maximum).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile =  Profile::new();
+    ///        profile.analyze("AB-1234");
+    ///        profile.analyze("CD-5678");
+    ///     profile.pre_generate();
+    ///
+    ///        assert_eq!(profile.generate_with_length(Some(9), Some(9)).len(), 9);
+    /// }
+    /// ```
+    #[inline]
+    pub fn generate_with_length(&mut self, min: Option<u32>, max: Option<u32>) -> String {
+        self.try_generate_with_length(min, max).unwrap_or_else(|e| {
+            error!("{}", e);
+            panic!("{}", e)
+        })
+    }
+
+    /// This function generates realistic test data of a target length, (e.g.: for fixed-width fields such as account numbers),
+    /// returning an error if the profile has no patterns (or facts) to generate the data from or the range of the length isn't valid.
+    /// The target length is sampled from the analyzed sizes (see size_ranks) that are within the range, (or from the range itself
+    /// if none of the analyzed sizes are), and the data is generated from an analyzed pattern of that length.
+    /// If no pattern of that length was analyzed, a pattern of the closest length is stretched (or shrunk) to the length.
+    /// Unlike try_generate(), the data is always generated from the patterns (and is never a missing value).
+    ///
+    /// # Arguments
+    ///
+    /// * `min: Option<u32>` - The minimum length of the generated data, (None for no minimum).</br>
+    /// * `max: Option<u32>` - The maximum length of the generated data, (None for no maximum).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
+    ///     let mut profile =  Profile::new();
+    ///        profile.analyze("12345678");
+    ///        profile.analyze("87654321");
+    ///        profile.analyze("1234");
+    ///     profile.pre_generate();
+    ///
+    ///     assert_eq!(profile.try_generate_with_length(Some(5), None).unwrap().len(), 8);
+    ///     assert_eq!(profile.try_generate_with_length(Some(10), Some(10)).unwrap().len(), 10);
+    ///     assert!(profile.try_generate_with_length(Some(5), Some(4)).is_err());
+    /// }
+    /// ```
+    pub fn try_generate_with_length(
+        &mut self,
+        min: Option<u32>,
+        max: Option<u32>,
+    ) -> TdgResult<String> {
+        let lower = min.unwrap_or(1).max(1);
+        if let Some(upper) = max {
+            if upper < lower {
+                return Err(TdgError::InvalidSetting {
+                    setting: "length".to_string(),
+                    message: format!(
+                        "the maximum length {} is less than the minimum length {}",
+                        upper, lower
+                    ),
+                });
+            }
+        }
+        if self.pattern_ranks.is_empty() {
+            return Err(TdgError::EmptyProfile);
+        }
+
+        self.try_accepted(|profile| {
+            let length = profile.sample_length(lower, max);
+            let pattern = profile.select_pattern(length);
+
+            profile.try_generate_stretched(&pattern, length as usize, false)
+        })
+    }
+
+    /// This function returns an InvalidSetting error if the Profile is in privacy mode and the key of its hashes hasn't been
+    /// supplied, (see set_privacy_key()).
+    pub(crate) fn check_privacy_key(&self) -> TdgResult<()> {
+        if self.privacy.as_ref().is_some_and(|p| p.key().is_none()) {
+            return Err(TdgError::InvalidSetting {
+                setting: "privacy_key".to_string(),
+                message: format!(
+                    "the key of the hashes of the analyzed values of the field {:?} must be supplied, (see Profile::set_privacy_key())",
+                    self.id.as_deref().unwrap_or_default()
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// This function generates a value with the generator, generating it again (up to the budget of retries) if the Profile
+    /// is in uniqueness mode and the value was already generated (see set_unique()), or the Profile is in privacy mode
+    /// and the value is (or is too close to) an analyzed value (see set_privacy()).
+    /// Returns a UniqueExhausted (or PrivacyExhausted) error if no value was accepted within the budget, or an InvalidSetting
+    /// error if the key of the privacy mode hasn't been supplied (see set_privacy_key()).
+    pub(crate) fn try_accepted<F>(&mut self, mut generate: F) -> TdgResult<String>
+    where
+        F: FnMut(&mut Profile) -> TdgResult<String>,
+    {
+        self.check_privacy_key()?;
+
+        let retries = match (self.unique.as_ref(), self.privacy.as_ref()) {
+            (None, None) => return generate(self),
+            (unique, privacy) => unique
+                .map_or(0, |u| u.retries)
+                .max(privacy.map_or(0, |p| p.retries)),
+        };
+
+        let mut private = true;
+        for _ in 0..=retries {
+            let value = generate(self)?;
+
+            private = self.privacy.as_ref().is_none_or(|p| p.allows(&value));
+            if private && self.unique.as_mut().is_none_or(|u| u.insert(&value)) {
+                return Ok(value);
+            }
+        }
+
+        match private {
+            true => Err(TdgError::UniqueExhausted {
+                field: self.id.clone(),
+                generated: self.unique.as_ref().map_or(0, |u| u.len()),
+                capacity: self.capacity(),
+            }),
+            false => Err(TdgError::PrivacyExhausted {
+                field: self.id.clone(),
+                retries,
+            }),
+        }
+    }
+
+    // samples a length (within the range) by the chance the analyzed sizes occur
+    fn sample_length(&mut self, lower: u32, upper: Option<u32>) -> u32 {
+        let in_range = |size: u32| size >= lower && upper.is_none_or(|u| size <= u);
+
+        // the size ranks are cumulative, so the chance of a size is the difference to the prior rank
+        let mut prior = 0.0;
+        let sizes: Vec<(u32, f64)> = self
+            .size_ranks
+            .iter()
+            .map(|&(size, rank)| {
+                let chance = rank - prior;
+                prior = rank;
+                (size, chance)
+            })
+            .filter(|&(size, _)| in_range(size))
+            .collect();
+
+        let rng = self.rng();
+        let total: f64 = sizes.iter().map(|s| s.1).sum();
+        if total <= 0.0 {
+            return rng.u32(lower..=upper.unwrap_or(lower));
+        }
+
+        let mut pick = rng.f64() * total;
+        for &(size, chance) in sizes.iter() {
+            if pick < chance {
+                return size;
+            }
+            pick -= chance;
+        }
+
+        sizes[sizes.len() - 1].0
+    }
+
+    // selects a pattern of the length (or of the closest length) by the chance the patterns occur
+    fn select_pattern(&mut self, length: u32) -> String {
+        let distance = |p: &str| (p.chars().count() as i64 - length as i64).abs();
+        let closest = self
+            .pattern_percentages
+            .iter()
+            .map(|p| distance(&p.0))
+            .min()
+            .unwrap_or(0);
+        let patterns: Vec<(String, f64)> = self
+            .pattern_percentages
+            .iter()
+            .filter(|p| distance(&p.0) == closest)
+            .cloned()
+            .collect();
+
+        let total: f64 = patterns.iter().map(|p| p.1).sum();
+        let mut pick = self.rng().f64() * total;
+        for (pattern, chance) in patterns.iter() {
+            if pick < *chance {
+                return pattern.clone();
             }
-            Ok(s) => {
-                info!("Successfully read file {:?}", &path.to_string());
-                s
+            pick -= chance;
+        }
+
+        patterns[patterns.len() - 1].0.clone()
+    }
+
+    // synthesizes a pattern (preferably one that wasn't analyzed) at the rate of the novelty, None if the value should be
+    // generated from an analyzed pattern
+    fn synthesize_pattern(&mut self) -> Option<String> {
+        let novelty = self.novelty.filter(|n| *n > 0.0)?;
+        if self.rng().f64() >= novelty {
+            return None;
+        }
+
+        let patterns = &self.patterns;
+        let chain = self.pattern_chain.get_or_insert_with(|| {
+            let mut chain = PatternChain::new();
+            for (pattern, count) in patterns.iter() {
+                chain.learn(pattern, *count);
             }
-        };
+            chain
+        });
+        let rng = Self::init_rng(&mut self.rng, self.seed);
+
+        let mut synthesized = None;
+        for _ in 0..SYNTHESIS_ATTEMPTS {
+            match chain.synthesize(novelty, rng) {
+                Some(pattern) if !patterns.contains_key(&pattern) => return Some(pattern),
+                Some(pattern) => synthesized = Some(pattern),
+                None => {}
+            }
+        }
 
-        //serde_json::from_str(&serialized).unwrap()
-        Self::from_serialized(&serialized)
+        synthesized
     }
 
-    /// Constructs a new Profile from a serialized (JSON) string of the Profile object. This is used when restoring from "archive"
+    /// This function generates a missing value (an empty value or a null sentinel token), weighted by the number of times
+    /// each missing value was analyzed. Returns an empty value if no missing values were analyzed.
     ///
-    /// #Example
+    /// # Example
     ///
     /// ```rust
     /// extern crate test_data_generation;
@@ -356,23 +1886,23 @@ impl Profile {
     /// use test_data_generation::Profile;
     ///
     /// fn main() {
-    ///        let serialized = "{\"patterns\":{\"VC\":1},\"pattern_total\":1,\"pattern_keys\":[\"VC\"],\"pattern_vals\":[1],\"pattern_percentages\":[],\"pattern_ranks\":[],\"sizes\":{\"2\":1},\"size_total\":1,\"size_ranks\":[],\"processors\":4,\"facts\":[[{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0}],[{\"key\":\"K\",\"prior_key\":\"O\",\"next_key\":null,\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1}],[],[]]}";
-    ///        let mut profile = Profile::from_serialized(&serialized);
-    ///
-    ///     profile.pre_generate();
+    ///     let mut profile =  Profile::new();
+    ///     assert_eq!(profile.generate_missing(), "");
     ///
-    ///     println!("The generated name is {:?}", profile.generate());
+    ///        profile.analyze("N/A");
+    ///        assert_eq!(profile.generate_missing(), "N/A");
     /// }
     /// ```
-    pub fn from_serialized(serialized: &str) -> Profile {
-        serde_json::from_str(serialized).unwrap()
+    pub fn generate_missing(&mut self) -> String {
+        match self.missing.as_ref() {
+            Some(missing) => missing
+                .sample(0, Self::init_rng(&mut self.rng, self.seed))
+                .unwrap_or_default(),
+            None => String::new(),
+        }
     }
 
-    /// This function converts an data point (&str) to a pattern and adds it to the profile
-    ///
-    /// # Arguments
-    ///
-    /// * `entity: String` - The textual str of the value to analyze.</br>
+    /// This function returns the ratio (0.0 to 1.0) of the analyzed values that were missing, (i.e.: empty values or null sentinel tokens).
     ///
     /// # Example
     ///
@@ -383,142 +1913,75 @@ impl Profile {
     ///
     /// fn main() {
     ///     let mut profile =  Profile::new();
-    ///        profile.analyze("One");
-    ///        profile.analyze("Two");
-    ///        profile.analyze("Three");
-    ///        profile.analyze("Four");
+    ///        profile.analyze("Aaron");
+    ///        profile.analyze("");
+    ///        profile.analyze("NULL");
+    ///        profile.analyze("Beth");
     ///
-    ///        assert_eq!(profile.patterns.len(), 4);
+    ///     assert_eq!(profile.missing_rate(), 0.5);
     /// }
     /// ```
-    pub fn analyze(&mut self, entity: &str) {
-        let rslt = PatternDefinition::new().analyze(entity);
-        let _t = self.apply_facts(rslt.0, rslt.1).map_err(|e| {
-            error!(
-                "Warning: Couldn't apply the pattern and facts for the entity {}!",
-                entity
-            );
-            e.to_string()
-        });
+    pub fn missing_rate(&self) -> f64 {
+        match self.missing.as_ref() {
+            Some(missing) => missing.rate(self.pattern_total),
+            None => 0.0,
+        }
     }
 
-    /// This function applies the pattern and list of Facts  to the profile
+    /// This function sets the tokens that represent a null value, (e.g.: for a field whose real values include a default token),
+    /// which are counted as missing values instead of being analyzed as patterns. The tokens are compared case insensitively
+    /// after trimming whitespace, and empty values are always missing.
+    /// NOTE: Set the tokens before analyzing, since the values that were already analyzed aren't analyzed again.
     ///
     /// # Arguments
     ///
-    /// * `pattern: String` - The string the represents the pattern of the entity that was analyzed.</br>
-    /// * `facts: Vec<Fact>` - A Vector containing the Facts based on the analysis (one for each char in the entity).</br>
+    /// * `sentinels: Option<Vec<String>>` - The tokens that represent a null value, or None for the default tokens (see engine::missing::NULL_SENTINELS).</br>
     ///
     /// # Example
     ///
     /// ```rust
     /// extern crate test_data_generation;
     ///
-    /// use test_data_generation::engine::{Fact, PatternDefinition};
     /// use test_data_generation::Profile;
     ///
     /// fn main() {
     ///     let mut profile =  Profile::new();
-    ///        let results = PatternDefinition::new().analyze("Word");
+    ///     profile.set_null_sentinels(Some(vec!["-".to_string()]));
+    ///        profile.analyze("NULL");
+    ///        profile.analyze("-");
     ///
-    ///        assert_eq!(profile.apply_facts(results.0, results.1).unwrap(), 1);
+    ///     assert_eq!(profile.missing_rate(), 0.5);
+    ///     assert!(profile.patterns.contains_key("CVCC"));
     /// }
     /// ```
-    #[inline]
-    pub fn apply_facts(&mut self, pattern: String, facts: Vec<Fact>) -> Result<i32, String> {
-        // balance the storing of facts across all the vectors that can be processed in parallel
-        let mut i = 0;
-        for f in facts.into_iter() {
-            if i == self.processors {
-                i = 0;
-            }
+    pub fn set_null_sentinels(&mut self, sentinels: Option<Vec<String>>) {
+        self.null_sentinels = sentinels;
+    }
 
-            self.facts[i as usize].push(f);
-            i += 1;
+    /// This function returns the tokens that represent a null value, (see set_null_sentinels()).
+    pub fn get_null_sentinels(&self) -> Vec<String> {
+        match self.null_sentinels.as_ref() {
+            Some(sentinels) => sentinels.clone(),
+            None => NULL_SENTINELS.iter().map(|s| s.to_string()).collect(),
         }
-
-        // store the pattern
-        AddAssign::add_assign(self.patterns.entry(pattern.to_string()).or_insert(0), 1);
-
-        // store the total number of patterns generated so far
-        self.pattern_total = self.patterns.values().sum::<u32>();
-
-        // analyze sizes
-        AddAssign::add_assign(self.sizes.entry(pattern.len() as u32).or_insert(0), 1);
-        self.size_total = self.sizes.values().sum::<u32>();
-
-        self.pattern_keys = self.patterns.keys().cloned().collect();
-        self.pattern_vals = self.patterns.values().cloned().collect();
-
-        Ok(1)
     }
 
-    /// This function calculates the patterns to use by the chance they will occur (as cumulative percentage) in decreasing order
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// extern crate test_data_generation;
-    ///
-    /// use test_data_generation::Profile;
-    ///
-    /// fn main() {
-    ///     let mut profile =  Profile::new();
-    ///
-    ///        profile.analyze("Smith, John");
-    ///        profile.analyze("O'Brian, Henny");
-    ///        profile.analyze("Dale, Danny");
-    ///        profile.analyze("Rickets, Ronnae");
-    ///        profile.analyze("Richard, Richie");
-    ///        profile.analyze("Roberts, Blake");
-    ///        profile.analyze("Conways, Sephen");
+    /// This function returns true if the value is empty or one of the tokens that represent a null value, (see set_null_sentinels()).
     ///
-    ///        profile.pre_generate();
-    ///        let test = [("CvccvccpSCvccvv".to_string(), 28.57142857142857 as f64), ("CcvccpSCvcc".to_string(), 42.857142857142854 as f64), ("CvccvccpSCvccvc".to_string(), 57.14285714285714 as f64), ("CvcvcccpSCcvcv".to_string(), 71.42857142857142 as f64), ("CvcvpSCvccc".to_string(), 85.7142857142857 as f64), ("V@CcvvcpSCvccc".to_string(), 99.99999999999997 as f64)];
+    /// # Arguments
     ///
-    ///        assert_eq!(profile.pattern_ranks, test);
-    /// }
-    /// ```
-    #[inline]
-    pub fn cum_patternmap(&mut self) {
-        // Reference: https://users.rust-lang.org/t/cannot-infer-an-appropriate-lifetime-for-autoref/13360/3
-
-        debug!("calculating the cumulative percentage of occurences for data point patterns...");
-
-        // calculate the percentage by patterns
-        // -> {"CcvccpSCvcc": 14.285714285714285, "CvccvccpSCvccvc": 14.285714285714285, "CvccvccpSCvccvv": 28.57142857142857, "CvcvcccpSCcvcv": 14.285714285714285, "CvcvpSCvccc": 14.285714285714285, "V~CcvvcpSCvccc": 14.285714285714285}
-        let n = self.patterns.len();
-
-        // see issue: https://github.com/dsietz/test-data-generation/issues/88
-        self.pattern_percentages.clear();
-
-        for m in 0..n {
-            self.pattern_percentages.push((
-                self.pattern_keys[m].clone(),
-                (self.pattern_vals[m] as f64 / self.pattern_total as f64) * 100.0,
-            ));
-        }
-
-        // sort the ranks by percentages in decreasing order
-        // -> [("CvccvccpSCvccvv", 28.57142857142857), ("CcvccpSCvcc", 14.285714285714285), ("CvccvccpSCvccvc", 14.285714285714285), ("CvcvcccpSCcvcv", 14.285714285714285), ("CvcvpSCvccc", 14.285714285714285), ("V~CcvvcpSCvccc", 14.285714285714285)]
-        self.pattern_percentages
-            .sort_by(|&(_, a), &(_, b)| b.partial_cmp(&a).unwrap());
-
-        // calculate the cumulative sum of the pattern rankings
-        // -> [("CvccvccpSCvccvv", 28.57142857142857), ("CcvccpSCvcc", 42.857142857142854), ("CvccvccpSCvccvc", 57.14285714285714), ("CvcvcccpSCcvcv", 71.42857142857142), ("CvcvpSCvccc", 85.7142857142857), ("V~CcvvcpSCvccc", 99.99999999999997)]
-        let mut rank: f64 = 0.00;
-
-        // see issue: https://github.com/dsietz/test-data-generation/issues/88
-        self.pattern_ranks.clear();
-
-        for pttrn in self.pattern_percentages.iter() {
-            let tmp = pttrn.1 + rank;
-            self.pattern_ranks.push((pttrn.0.clone(), tmp));
-            rank = tmp;
+    /// * `value: &str` - The value to check.</br>
+    pub fn is_missing(&self, value: &str) -> bool {
+        match self.null_sentinels.as_ref() {
+            Some(sentinels) => MissingValues::is_missing_in(value, sentinels),
+            None => MissingValues::is_missing(value),
         }
     }
 
-    /// This function calculates the sizes to use by the chance they will occur (as cumulative percentage) in decreasing order
+    /// This function generates a value of a field of a semantic type (see SemanticModel), a date field or a numeric field
+    /// from the detector of the semantic type, the format and range of the analyzed dates (see DateTimeModel)
+    /// or the distribution of the analyzed numbers (see NumericModel), instead of from the patterns.
+    /// Returns None if the field isn't of a semantic type, a date or numeric field.
     ///
     /// # Example
     ///
@@ -529,50 +1992,42 @@ impl Profile {
     ///
     /// fn main() {
     ///     let mut profile =  Profile::new();
-    ///        profile.analyze("One");
-    ///        profile.analyze("Two");
-    ///        profile.analyze("Three");
-    ///        profile.analyze("Four");
-    ///        profile.analyze("Five");
-    ///        profile.analyze("Six");
+    ///        profile.analyze("02/28/2016");
+    ///        profile.analyze("03/01/2016");
     ///
-    ///     profile.cum_sizemap();
+    ///     let generated = profile.generate_typed().unwrap();
+    ///     assert!(["02/28/2016", "02/29/2016", "03/01/2016"].contains(&generated.as_str()));
     ///
-    ///        print!("The size ranks are {:?}", profile.size_ranks);
-    ///     // The size ranks are [(3, 50), (4, 83.33333333333333), (5, 100)]
+    ///        profile.analyze("Aaron");
+    ///     assert_eq!(profile.generate_typed(), None);
     /// }
     /// ```
-    #[inline]
-    pub fn cum_sizemap(&mut self) {
-        debug!("calculating the cumulative percentage of occurences for data point sizes...");
-        // calculate the percentage by sizes
-        // -> {11: 28.57142857142857, 14: 14.285714285714285, 15: 57.14285714285714}
-        let mut size_ranks = SizeRankMap::new();
+    pub fn generate_typed(&mut self) -> Option<String> {
+        let rng = Self::init_rng(&mut self.rng, self.seed);
 
-        for key in self.sizes.keys() {
-            size_ranks.insert(
-                *key,
-                (*self.sizes.get(key).unwrap() as f64 / self.size_total as f64) * 100.0,
-            );
+        if let Some(value) = self.semantic.as_ref().and_then(|m| m.try_sample(rng)) {
+            return Some(value);
         }
 
-        // sort the ranks by percentages in decreasing order
-        // -> [(15, 57.14285714285714), (11, 28.57142857142857), (14, 14.285714285714285)]
-        let mut sizes = size_ranks.iter().collect::<Vec<_>>();
-        sizes.sort_by(|&(_, a), &(_, b)| b.partial_cmp(a).unwrap());
+        if let Some(model) = self.date_time.as_ref() {
+            return Some(model.sample(self.date_shift.unwrap_or(0), rng));
+        }
 
-        // calculate the cumulative sum of the size rankings
-        // -> [(15, 57.14285714285714), (11, 85.71428571428571), (14, 100)]
-        self.size_ranks = sizes
-            .iter()
-            .scan((0_u32, 0.00_f64), |state, &(&k, &v)| {
-                *state = (k, state.1 + v);
-                Some(*state)
-            })
-            .collect::<Vec<(_, _)>>();
+        match (self.data_type, self.numeric.as_ref()) {
+            (Some(DataType::Integer) | Some(DataType::Decimal), Some(numeric)) => {
+                Some(numeric.sample(rng))
+            }
+            _ => None,
+        }
     }
 
-    /// This function generates realistic test data based on the sampel data that was analyzed.
+    /// This function generates realistic test data based on the sample data that was analyzed.
+    /// _NOTE:_ The random number generator of the Profile can't be advanced through a shared reference, so the data is generated
+    /// with an unseeded generator. Use try_generate_from_pattern() to generate data that can be reproduced with the seed of the Profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern: String` - The pattern to reference when generating the test data.</br>
     ///
     /// # Example
     ///
@@ -584,41 +2039,30 @@ impl Profile {
     /// fn main() {
     ///     let mut profile =  Profile::new();
     ///
-    ///        profile.analyze("One");
-    ///        profile.analyze("Two");
-    ///        profile.analyze("Three");
-    ///        profile.analyze("Four");
-    ///        profile.analyze("Five");
+    ///        profile.analyze("01/13/2017");
+    ///        profile.analyze("11/24/2017");
+    ///        profile.analyze("08/05/2017");
     ///
     ///     profile.pre_generate();
     ///
-    ///        print!("The test data {:?} was generated.", profile.generate());
+    ///      let generated = profile.generate_from_pattern("##p##p####".to_string());
+    ///
+    ///     assert_eq!(generated.len(), 10);
     /// }
     /// ```
     #[inline]
-    pub fn generate(&mut self) -> String {
-        // 1. get a random number
-        let s: f64 = random_percentage!();
-
-        // 2. find the first pattern that falls within the percentage chance of occurring
-        // NOTE: The following 2 lines has been commented out because this doesn't need to
-        //       happen since the patterns are already ranks by percent chance of occurring
-        //       and therefore sizes (lengths) as well since the patterns include the full
-        //       length of the entitiy analyzed.
-        //let size = self.size_ranks.iter().find(|&&x|&x.1 >= &s).unwrap().0;
-        //let pattern = self.pattern_ranks.iter().find(|x|&x.1 >= &s && x.0.len() == size as usize).unwrap().clone();
-        let pattern = self
-            .pattern_ranks
-            .iter()
-            .find(|x| x.1 >= s)
-            .unwrap()
-            .clone();
+    pub fn generate_from_pattern(&self, pattern: String) -> String {
+        let length = pattern.chars().count();
 
-        // lastly, generate the test data using facts that adhere to the pattern
-        self.generate_from_pattern(pattern.0)
+        self.stretch(&pattern, length, false, &mut fastrand::Rng::new())
+            .unwrap_or_else(|e| {
+                error!("{}", e);
+                panic!("{}", e)
+            })
     }
 
-    /// This function generates realistic test data based on the sample data that was analyzed.
+    /// This function generates realistic test data based on the sample data that was analyzed,
+    /// returning an error if there are no facts for one of the symbols in the pattern.
     ///
     /// # Arguments
     ///
@@ -635,98 +2079,99 @@ impl Profile {
     ///     let mut profile =  Profile::new();
     ///
     ///        profile.analyze("01/13/2017");
-    ///        profile.analyze("11/24/2017");
-    ///        profile.analyze("08/05/2017");
-    ///
     ///     profile.pre_generate();
     ///
-    ///      let generated = profile.generate_from_pattern("##p##p####".to_string());
-    ///
-    ///     assert_eq!(generated.len(), 10);
+    ///     assert!(profile.try_generate_from_pattern("##p##p####".to_string()).is_ok());
+    ///     assert!(profile.try_generate_from_pattern("Cvcc".to_string()).is_err());
     /// }
     /// ```
-    #[inline]
-    pub fn generate_from_pattern(&self, pattern: String) -> String {
+    pub fn try_generate_from_pattern(&mut self, pattern: String) -> TdgResult<String> {
+        let length = pattern.chars().count();
+        self.try_generate_stretched(&pattern, length, false)
+    }
+
+    // generates the data of a length from a pattern, where each char of the data is generated from the facts of the
+    // symbol of the pattern at the same relative position, (so a shorter or longer pattern can be stretched to the length).
+    // When any_position is true, a char whose symbol wasn't analyzed at its position is generated from the facts of the symbol
+    // at any position, (e.g.: for a synthesized pattern).
+    fn try_generate_stretched(
+        &mut self,
+        pattern: &str,
+        length: usize,
+        any_position: bool,
+    ) -> TdgResult<String> {
+        Self::init_rng(&mut self.rng, self.seed);
+        let mut rng = self.rng.take().unwrap_or_default();
+        let generated = self.stretch(pattern, length, any_position, &mut rng);
+        self.rng = Some(rng);
+
+        generated
+    }
+
+    // generates the data of a length from a pattern with a random number generator, (see try_generate_stretched())
+    fn stretch(
+        &self,
+        pattern: &str,
+        length: usize,
+        any_position: bool,
+        rng: &mut fastrand::Rng,
+    ) -> TdgResult<String> {
         let pattern_chars = pattern.chars().collect::<Vec<char>>();
         let mut generated = String::new();
-        let prev_char = ' ';
-
-        // iterate through the chars in the pattern string
-        for (idx, ch) in pattern_chars.iter().enumerate() {
-            match crossbeam::scope(|scope| {
-                let c = ch;
-                let starts = if idx == 0 { 1 } else { 0 };
-                let ends = if idx == pattern_chars.len() - 1 { 1 } else { 0 };
-                let mut fact_options = vec![];
-                let prior_char = prev_char;
-
-                // iterate through the processors (vec) that hold the lists (vec) of facts
-                for v in &self.facts {
-                    let selected_facts = scope.spawn(move |_| {
-                        let mut facts = vec![];
-
-                        // iterate through the list of facts
-                        for value in v {
-                            if value.starts_with == starts
-                                && value.ends_with == ends
-                                && value.pattern_placeholder == *c
-                                && value.index_offset == idx as u32
-                            {
-                                facts.push(value.key);
-
-                                // if the value.key's prior char matches the prior generated char, then weight the value.key
-                                // to increase the chance of it being used when generated
-                                if value.prior_key.unwrap_or(' ') == prior_char {
-                                    facts.push(value.key);
-                                    facts.push(value.key);
-                                }
-
-                                // if the value.key's index_offset matches the current index, then weight the value.key
-                                // to increase the chance of it being used when generated
-                                if value.index_offset == idx as u32 {
-                                    facts.push(value.key);
-                                    facts.push(value.key);
-                                }
-                            }
-                        }
-
-                        facts
-                    });
-
-                    //append the selected_facts to the fact_options
-                    //fact_options.extend_from_slice(&selected_facts.join());
-                    match selected_facts.join() {
-                        Ok(sf) => fact_options.extend_from_slice(&sf),
-                        Err(err) => {
-                            error!("{:?}", err);
-                            panic!("{:?}", err);
-                        }
-                    }
-                }
-
-                //select a fact to use as the generated char
-                let rnd_start = 0;
-                let rnd_end = fact_options.len() - 1;
-
-                if rnd_start >= rnd_end {
-                    //generated.push(fact_options[0 as usize]);
-                    fact_options[0_usize]
-                } else {
-                    let x: u32 = random_between!(rnd_start, rnd_end);
-                    //prev_char = fact_options[x as usize];
-                    //generated.push(prev_char);
-                    fact_options[x as usize]
+        let mut history: Vec<char> = Vec::with_capacity(length);
+
+        for pos in 0..length {
+            // the position in the pattern that the char is generated from
+            let idx = match (length, pattern_chars.len()) {
+                (1, _) | (_, 0) | (_, 1) => 0,
+                (l, n) => ((pos * (n - 1)) as f64 / (l - 1) as f64).round() as usize,
+            };
+            let ch = match pattern_chars.get(idx) {
+                Some(ch) => ch,
+                None => {
+                    return Err(TdgError::NoFacts {
+                        pattern: pattern.to_string(),
+                        index: idx,
+                    })
                 }
-            }) {
-                Ok(c) => generated.push(c),
-                Err(err) => {
-                    error!("{:?}", err);
-                    panic!("{:?}", err);
+            };
+
+            // use the n-gram model (if enabled) to choose the char based on the previously generated chars
+            if let Some(chain) = self.markov.as_ref() {
+                if let Some(c) = chain.next_char(*ch, &history, rng) {
+                    generated.push(c);
+                    history.push(c);
+                    continue;
                 }
             }
+
+            let starts = if idx == 0 { 1 } else { 0 };
+            let ends = if idx == pattern_chars.len() - 1 { 1 } else { 0 };
+            let prior_char = history.last().copied().unwrap_or(' ');
+
+            // select a fact to use as the generated char
+            let selected = match self
+                .facts
+                .select(*ch, idx as u32, starts, ends, prior_char, rng)
+            {
+                None if any_position => self.facts.select_any(*ch, prior_char, rng),
+                selected => selected,
+            };
+            let c = match selected {
+                Some(c) => c,
+                None => {
+                    return Err(TdgError::NoFacts {
+                        pattern: pattern.to_string(),
+                        index: idx,
+                    })
+                }
+            };
+
+            generated.push(c);
+            history.push(c);
         }
 
-        generated
+        Ok(generated)
     }
 
     /// This function learns by measuring how realistic the test data it generates to the sample data that was provided.
@@ -831,27 +2276,6 @@ impl Profile {
         realistic_test!(control, experiment)
     }
 
-    /// This function is called from within the implementated structure and returns a list processors (Vec) with empty lists (Vec) for their Facts.
-    /// Each processor shares the load of generating the data based on the Facts it has been assigned to manage.
-    ///
-    /// # Arguments
-    ///
-    /// * `p: u8` - A number that sets the number of processors to start up to manage the Facts.</br>
-    ///         Increasing the number of processors will speed up the generator be ditributing the workload.
-    ///         The recommended number of processors is 1 per 10K data points (e.g.: profiling 20K names should be handled by 2 processors)</br>
-    ///         NOTE: The default number of processors is 4.
-    ///
-    #[inline]
-    fn new_facts(p: u8) -> Vec<Vec<Fact>> {
-        let mut vec_main = Vec::new();
-
-        for _ in 0..p {
-            vec_main.push(Vec::new());
-        }
-
-        vec_main
-    }
-
     /// This function prepares the size a pattern accumulated percentages order by percentage increasing
     ///
     /// # Example
@@ -880,6 +2304,8 @@ impl Profile {
         info!("Preparing the profile for data generation...");
         self.cum_sizemap();
         self.cum_patternmap();
+        // the patterns may have changed, so the model of the pattern symbols is rebuilt when it is used
+        self.pattern_chain = None;
         info!("Profile: preparing generator...");
     }
 
@@ -943,26 +2369,60 @@ impl Profile {
     /// use test_data_generation::Profile;
     ///
     /// fn main() {
-    ///     // analyze the dataset
+    ///     // analyze the dataset
+    ///        let mut profile =  Profile::new();
+    ///     profile.analyze("Smith, John");
+    ///        profile.analyze("O'Brian, Henny");
+    ///        profile.analyze("Dale, Danny");
+    ///        profile.analyze("Rickets, Ronney");
+    ///
+    ///        profile.pre_generate();
+    ///
+    ///     assert_eq!(profile.save("./tests/samples/sample-00-profile").unwrap(), true);
+    /// }
+    ///
+    pub fn save(&mut self, path: &'static str) -> Result<bool, io::Error> {
+        match self.try_save(path) {
+            Ok(_) => Ok(true),
+            Err(TdgError::FileCreate { source, .. }) | Err(TdgError::FileWrite { source, .. }) => {
+                Err(source)
+            }
+            Err(e) => Err(io::Error::other(e)),
+        }
+    }
+
+    /// This function saves (exports) the Profile to a JSON file, returning an error that includes the path if it couldn't be saved.
+    ///
+    /// # Arguments
+    ///
+    /// * `path: &str` - The full path of the export file , excluding the file extension, (e.g.: "./test/data/custom-names").</br>
+    ///
+    /// #Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::Profile;
+    ///
+    /// fn main() {
     ///        let mut profile =  Profile::new();
     ///     profile.analyze("Smith, John");
-    ///        profile.analyze("O'Brian, Henny");
-    ///        profile.analyze("Dale, Danny");
-    ///        profile.analyze("Rickets, Ronney");
-    ///
     ///        profile.pre_generate();
     ///
-    ///     assert_eq!(profile.save("./tests/samples/sample-00-profile").unwrap(), true);
+    ///     assert!(profile.try_save("./badpath/sample-00-profile").is_err());
     /// }
     ///
-    pub fn save(&mut self, path: &'static str) -> Result<bool, io::Error> {
-        let dsp_json = serde_json::to_string(&self).unwrap();
+    pub fn try_save(&mut self, path: &str) -> TdgResult<()> {
+        let dsp_json = serde_json::to_string(&self)?;
 
         // Create the archive file
         let mut file = match File::create(format!("{}.json", &path)) {
             Err(e) => {
                 error!("Could not create file {:?}", &path.to_string());
-                return Err(e);
+                return Err(TdgError::FileCreate {
+                    path: path.to_string(),
+                    source: e,
+                });
             }
             Ok(f) => {
                 info!("Successfully exported to {:?}", &path.to_string());
@@ -974,14 +2434,17 @@ impl Profile {
         match file.write_all(dsp_json.as_bytes()) {
             Err(e) => {
                 error!("Could not write to file {}", &path.to_string());
-                return Err(e);
+                return Err(TdgError::FileWrite {
+                    path: path.to_string(),
+                    source: e,
+                });
             }
             Ok(_) => {
                 info!("Successfully exported to {}", &path.to_string());
             }
         };
 
-        Ok(true)
+        Ok(())
     }
 
     /// This function converts the Profile to a serialize JSON string.
@@ -1001,7 +2464,7 @@ impl Profile {
     ///        data_profile.analyze("OK");
     ///
     ///     println!("{}", data_profile.serialize());
-    ///     // {"patterns":{"VC":1},"pattern_total":1,"pattern_keys":["VC"],"pattern_vals":[1],"pattern_percentages":[],"pattern_ranks":[],"sizes":{"2":1},"size_total":1,"size_ranks":[],"processors":4,"facts":[[{"key":"O","prior_key":null,"next_key":"K","pattern_placeholder":"V","starts_with":1,"ends_with":0,"index_offset":0}],[{"key":"K","prior_key":"O","next_key":null,"pattern_placeholder":"C","starts_with":0,"ends_with":1,"index_offset":1}],[],[]]}
+    ///     // {"id":null,"patterns":{"VC":1},"pattern_total":1,"pattern_keys":["VC"],"pattern_vals":[1],"pattern_percentages":[],"pattern_ranks":[],"sizes":{"2":1},"size_total":1,"size_ranks":[],"processors":4,"facts":[{"key":"K","prior_key":"O","pattern_placeholder":"C","starts_with":0,"ends_with":1,"index_offset":1,"count":1},{"key":"O","prior_key":null,"pattern_placeholder":"V","starts_with":1,"ends_with":0,"index_offset":0,"count":1}],"data_type":"string"}
     /// }
     ///
     pub fn serialize(&mut self) -> String {
@@ -1012,14 +2475,18 @@ impl Profile {
 #[macro_use]
 pub mod macros;
 pub mod configs;
+pub mod csv_dialect;
 pub mod data_sample_parser;
 pub mod engine;
+pub mod errors;
 pub mod shared;
+pub mod sql;
 
 // Unit Tests
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeSet;
 
     #[test]
     fn apply_facts() {
@@ -1029,6 +2496,27 @@ mod tests {
         assert_eq!(profile.apply_facts(results.0, results.1).unwrap(), 1);
     }
 
+    #[test]
+    fn apply_facts_all() {
+        let words = ["Word", "Wide", "Wander", "Word"];
+        let mut pttrn_def = PatternDefinition::new();
+        let mut one_by_one = Profile::new();
+        let mut all = Profile::new();
+
+        for word in words.iter() {
+            let results = pttrn_def.analyze(word);
+            one_by_one.apply_facts(results.0, results.1).unwrap();
+        }
+        let results = words.iter().map(|w| pttrn_def.analyze(w)).collect();
+
+        assert_eq!(all.apply_facts_all(results).unwrap(), 4);
+        assert_eq!(all.pattern_total, 4);
+        assert_eq!(all.pattern_keys, one_by_one.pattern_keys);
+        assert_eq!(all.pattern_vals, one_by_one.pattern_vals);
+        assert_eq!(all.sizes, one_by_one.sizes);
+        assert_eq!(all.facts.total(), one_by_one.facts.total());
+    }
+
     #[test]
     fn levenshtein_test() {
         let mut profil = Profile::new();
@@ -1112,6 +2600,60 @@ mod tests {
         assert!(profile.generate().len() > 0);
     }
 
+    #[test]
+    fn try_new_profile_from_file_bad_path() {
+        match Profile::try_from_file("./tests/samples/bad-path") {
+            Err(TdgError::FileOpen { path, .. }) => assert_eq!(path, "./tests/samples/bad-path"),
+            other => panic!("Expected a FileOpen error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn try_new_profile_from_file_bad_data() {
+        match Profile::try_from_file("./tests/samples/not-readable") {
+            Err(TdgError::Json { path, .. }) => {
+                assert_eq!(path, Some("./tests/samples/not-readable.json".to_string()))
+            }
+            other => panic!("Expected a Json error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn try_generate_empty_profile() {
+        let mut profile = Profile::new();
+        profile.pre_generate();
+
+        match profile.try_generate() {
+            Err(TdgError::EmptyProfile) => {}
+            other => panic!("Expected an EmptyProfile error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn try_generate_from_pattern_no_facts() {
+        let mut profile = Profile::new();
+        profile.analyze("OK");
+        profile.pre_generate();
+
+        match profile.try_generate_from_pattern("V#".to_string()) {
+            Err(TdgError::NoFacts { index, .. }) => assert_eq!(index, 1),
+            other => panic!("Expected a NoFacts error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn try_save_profile_bad_path() {
+        let mut profile = Profile::new();
+        profile.analyze("OK");
+
+        match profile.try_save("./badpath/sample-00-profile") {
+            Err(TdgError::FileCreate { path, .. }) => {
+                assert_eq!(path, "./badpath/sample-00-profile")
+            }
+            other => panic!("Expected a FileCreate error, but got {:?}", other),
+        }
+    }
+
     #[test]
     fn new_profile_from_serialized() {
         let serialized = "{\"patterns\":{\"VC\":1},\"pattern_total\":1,\"pattern_keys\":[\"VC\"],\"pattern_vals\":[1],\"pattern_percentages\":[],\"pattern_ranks\":[],\"sizes\":{\"2\":1},\"size_total\":1,\"size_ranks\":[],\"processors\":4,\"facts\":[[{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0}],[{\"key\":\"K\",\"prior_key\":\"O\",\"next_key\":null,\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1}],[],[]]}";
@@ -1128,6 +2670,144 @@ mod tests {
         assert_eq!(profile.processors, 10);
     }
 
+    #[test]
+    // ensure Profiles with the same seed generate the same test data
+    fn new_profile_with_seed() {
+        let mut profile_a = Profile::new_with_seed(1234);
+        let mut profile_b = Profile::new_with_seed(1234);
+
+        for name in [
+            "Smith, John",
+            "O'Brian, Henny",
+            "Dale, Danny",
+            "Rickets, Ronney",
+        ]
+        .iter()
+        {
+            profile_a.analyze(name);
+            profile_b.analyze(name);
+        }
+
+        profile_a.pre_generate();
+        profile_b.pre_generate();
+
+        for _ in 0..20 {
+            assert_eq!(profile_a.generate(), profile_b.generate());
+        }
+    }
+
+    #[test]
+    // ensure a seeded Profile generates the same test data after being restored from an archive
+    fn profile_seed_from_serialized() {
+        let mut profile = Profile::new();
+        profile.analyze("Smith, John");
+        profile.analyze("O'Brian, Henny");
+        profile.analyze("Dale, Danny");
+        profile.analyze("Rickets, Ronney");
+        profile.pre_generate();
+        profile.set_seed(42);
+
+        let mut restored = Profile::from_serialized(&profile.serialize());
+        let expected: Vec<String> = (0..20).map(|_| profile.generate()).collect();
+        let actual: Vec<String> = (0..20).map(|_| restored.generate()).collect();
+
+        assert_eq!(restored.seed, Some(42));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    // ensure the Markov chain is rebuilt from the Facts that were analyzed before it was enabled
+    fn profile_set_markov_order_rebuilds() {
+        // only the transitions of order 1 can be recovered from the indexed facts
+        let mut before = Profile::new_with_processors(3);
+        before.analyze("Jonathon");
+        before.analyze("A");
+        before.analyze("Johnathan");
+        before.set_markov_order(1).unwrap();
+
+        let mut after = Profile::new_with_processors(3);
+        after.set_markov_order(1).unwrap();
+        after.analyze("Jonathon");
+        after.analyze("A");
+        after.analyze("Johnathan");
+
+        assert_eq!(before.markov, after.markov);
+    }
+
+    #[test]
+    // ensure the indexed facts survive a round trip through the archive
+    fn profile_indexed_facts_from_serialized() {
+        let mut profile = Profile::new_with_seed(5);
+        profile.analyze("Anna");
+        profile.analyze("Anna");
+        profile.analyze("Otto");
+
+        let mut restored = Profile::from_serialized(&profile.serialize());
+        assert_eq!(restored.facts, profile.facts);
+        assert_eq!(restored.facts.total(), 12);
+
+        restored.pre_generate();
+        assert_eq!(restored.generate().len(), 4);
+    }
+
+    #[test]
+    // ensure the Profile keeps the diacritics of the analyzed data
+    fn profile_generate_with_diacritics() {
+        let mut profile = Profile::new_with_seed(11);
+        profile.analyze("José");
+        profile.analyze("Müller");
+        profile.pre_generate();
+
+        assert_eq!(profile.patterns.get("Cvcv"), Some(&1));
+        assert!(profile
+            .generate_from_pattern("Cvcv".to_string())
+            .ends_with('é'));
+        // the second char is either the 'o' of "José" or the 'ü' of "Müller"
+        let second = profile
+            .generate_from_pattern("Cvccvc".to_string())
+            .chars()
+            .nth(1);
+        assert!(second == Some('o') || second == Some('ü'));
+    }
+
+    #[test]
+    fn profile_set_markov_order_invalid() {
+        let mut profile = Profile::new();
+
+        match profile.set_markov_order(0) {
+            Err(TdgError::InvalidSetting { setting, .. }) => assert_eq!(setting, "markov_order"),
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+        assert!(profile.markov.is_none());
+    }
+
+    #[test]
+    // ensure a Profile using the Markov chain only generates chars that follow the previous chars in the sample data
+    fn profile_generate_with_markov_order() {
+        let mut profile = Profile::new_with_seed(99);
+        profile.set_markov_order(4).unwrap();
+        profile.analyze("Abcd");
+        profile.analyze("Abce");
+        profile.pre_generate();
+
+        for _ in 0..10 {
+            let generated = profile.generate();
+            assert!(generated == "Abcd" || generated == "Abce");
+        }
+    }
+
+    #[test]
+    // ensure the order of the Markov chain is stored in the archive
+    fn profile_markov_order_from_serialized() {
+        let mut profile = Profile::new();
+        profile.set_markov_order(3).unwrap();
+        profile.analyze("OK");
+
+        let restored = Profile::from_serialized(&profile.serialize());
+
+        assert_eq!(restored.markov.unwrap().order, 3);
+    }
+
     #[test]
     // ensure Profile is analyzing all the sample data points
     fn profile_analyze() {
@@ -1272,6 +2952,570 @@ mod tests {
         profil.analyze("OK");
 
         let serialized = profil.serialize();
-        assert_eq!(serialized, "{\"id\":null,\"patterns\":{\"VC\":1},\"pattern_total\":1,\"pattern_keys\":[\"VC\"],\"pattern_vals\":[1],\"pattern_percentages\":[],\"pattern_ranks\":[],\"sizes\":{\"2\":1},\"size_total\":1,\"size_ranks\":[],\"processors\":4,\"facts\":[[{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0}],[{\"key\":\"K\",\"prior_key\":\"O\",\"next_key\":null,\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1}],[],[]]}");
+        assert_eq!(serialized, "{\"id\":null,\"patterns\":{\"VC\":1},\"pattern_total\":1,\"pattern_keys\":[\"VC\"],\"pattern_vals\":[1],\"pattern_percentages\":[],\"pattern_ranks\":[],\"sizes\":{\"2\":1},\"size_total\":1,\"size_ranks\":[],\"processors\":4,\"facts\":[{\"key\":\"K\",\"prior_key\":\"O\",\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1,\"count\":1},{\"key\":\"O\",\"prior_key\":null,\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0,\"count\":1}],\"data_type\":\"string\"}");
+    }
+
+    #[test]
+    // ensure the missing values are counted instead of analyzed as patterns, and generated at the same rate
+    fn profile_missing_values() {
+        let mut profile = Profile::new_with_seed(5);
+        for value in ["12", "", "NULL", "7", "", "31", "n/a", "4"].iter() {
+            profile.analyze(value);
+        }
+        profile.pre_generate();
+
+        assert_eq!(profile.pattern_total, 4);
+        assert_eq!(profile.data_type, Some(DataType::Integer));
+        assert_eq!(profile.missing_rate(), 0.5);
+
+        let mut missing = 0;
+        for _ in 0..10000 {
+            let generated = profile.try_generate().unwrap();
+            if MissingValues::is_missing(&generated) {
+                assert!(["", "NULL", "n/a"].contains(&generated.as_str()));
+                missing += 1;
+            }
+        }
+        assert!((4500..5500).contains(&missing));
+    }
+
+    #[test]
+    // ensure a Profile that only analyzed missing values generates missing values instead of an error
+    fn profile_only_missing_values() {
+        let mut profile = Profile::new();
+        profile.analyze("");
+        profile.analyze("");
+        profile.pre_generate();
+
+        assert_eq!(profile.try_generate().unwrap(), "");
+        assert_eq!(profile.data_type, None);
+    }
+
+    #[test]
+    // ensure the numbers are generated from the distribution of the analyzed numbers instead of digit by digit
+    fn profile_numeric_distribution() {
+        let mut profile = Profile::new_with_seed(17);
+        for amount in 0..500 {
+            profile.analyze(&format!("{}.{:02}", 95 + amount % 10, amount % 100));
+        }
+        profile.analyze("NULL");
+        profile.pre_generate();
+
+        let numeric = profile.numeric.clone().unwrap();
+        assert_eq!(numeric.min, 95.0);
+        assert_eq!(numeric.max, 104.99);
+        assert_eq!(numeric.precision, 2);
+
+        for _ in 0..1000 {
+            let generated = profile.try_generate().unwrap();
+            if generated == "NULL" {
+                continue;
+            }
+            let amount: f64 = generated.parse().unwrap();
+            assert!((95.0..=104.99).contains(&amount));
+            assert_eq!(generated.split_once('.').unwrap().1.len(), 2);
+        }
+    }
+
+    #[test]
+    // ensure dates are generated in the analyzed format and range, and are valid dates, (the patterns allow 19/39/2016)
+    fn profile_date_time() {
+        let mut profile = Profile::new_with_seed(18);
+        for date in ["01/14/2016", "12/31/2016", "09/17/2016", "N/A"].iter() {
+            profile.analyze(date);
+        }
+        profile.pre_generate();
+
+        let model = profile.date_time.clone().unwrap();
+        assert_eq!(model.format(), "%m/%d/%Y");
+        assert_eq!(model.count, 3);
+
+        for _ in 0..1000 {
+            let generated = profile.try_generate().unwrap();
+            if generated == "N/A" {
+                continue;
+            }
+            let date = chrono::NaiveDate::parse_from_str(&generated, "%m/%d/%Y").unwrap();
+            assert_eq!(generated.len(), 10);
+            assert!(generated.ends_with("/2016"));
+            assert!(date >= chrono::NaiveDate::from_ymd_opt(2016, 1, 14).unwrap());
+        }
+
+        // a value that isn't a date of the same format discards the model
+        profile.analyze("2016-01-14");
+        assert_eq!(profile.date_time, None);
+    }
+
+    #[test]
+    // ensure the values of a semantic type are structurally valid, (the patterns don't ensure a single @)
+    fn profile_semantic_type() {
+        let mut profile = Profile::new_with_seed(19);
+        for email in [
+            "aaron@example.com",
+            "beth.aaby@example.com",
+            "carl@test.org",
+            "",
+        ]
+        .iter()
+        {
+            profile.analyze(email);
+        }
+        profile.pre_generate();
+
+        assert_eq!(profile.semantic.as_ref().unwrap().detector, "email");
+        for _ in 0..500 {
+            let generated = profile.try_generate().unwrap();
+            if generated.is_empty() {
+                continue;
+            }
+            assert_eq!(generated.matches('@').count(), 1);
+            assert!(generated.ends_with("@example.com") || generated.ends_with("@test.org"));
+        }
+
+        profile.analyze("n.a.");
+        assert_eq!(profile.semantic, None);
+    }
+
+    #[test]
+    // ensure the custom classes of chars are used to analyze and generate, and are exported with the profile
+    fn profile_char_classes() {
+        let mut profile = Profile::new_with_seed(20);
+        profile
+            .add_char_class(CharClass::chars('h', "0123456789abcdef", 0).unwrap())
+            .unwrap();
+        // a class without a regex or chars
+        let undefined: CharClass = serde_json::from_str("{\"symbol\":\"x\"}").unwrap();
+        assert!(profile.add_char_class(undefined).is_err());
+        assert_eq!(profile.char_classes.len(), 1);
+
+        profile.analyze("#0f8fad");
+        profile.analyze("#c0ffee");
+        profile.pre_generate();
+        assert_eq!(profile.patterns.keys().collect::<Vec<_>>(), vec!["phhhhhh"]);
+
+        for _ in 0..50 {
+            let generated = profile.try_generate().unwrap();
+            assert!(generated.starts_with('#'));
+            assert!(generated[1..].chars().all(|c| c.is_ascii_hexdigit()));
+        }
+
+        let mut restored = Profile::from_serialized(&serde_json::to_string(&profile).unwrap());
+        restored.analyze("#beef00");
+        assert_eq!(restored.patterns.get("phhhhhh"), Some(&3));
+    }
+
+    #[test]
+    // ensure the values are unique until the value space is exhausted, and missing values may repeat
+    fn profile_unique() {
+        let mut profile = Profile::new_with_seed(23);
+        profile.id = Some("code".to_string());
+        profile.set_unique(true);
+        for value in ["B1", "C2", "", "D3"].iter() {
+            profile.analyze(value);
+        }
+        profile.pre_generate();
+        assert_eq!(profile.capacity(), 9.0);
+
+        let mut generated = BTreeSet::new();
+        let mut missing = 0;
+        loop {
+            match profile.try_generate() {
+                Ok(value) if value.is_empty() => missing += 1,
+                Ok(value) => assert!(generated.insert(value)),
+                Err(TdgError::UniqueExhausted {
+                    field,
+                    generated: count,
+                    capacity,
+                }) => {
+                    assert_eq!(field.as_deref(), Some("code"));
+                    assert_eq!(count, generated.len());
+                    assert_eq!(capacity, 9.0);
+                    break;
+                }
+                Err(e) => panic!("Expected a UniqueExhausted error, but got {:?}", e),
+            }
+        }
+        assert_eq!(generated.len(), 9);
+        assert!(missing > 0);
+
+        // enabling the mode again forgets the generated values
+        profile.set_unique(true);
+        assert!(profile.try_generate_with_length(Some(2), Some(2)).is_ok());
+
+        let restored = Profile::from_serialized(&serde_json::to_string(&profile).unwrap());
+        assert_eq!(restored.unique.unwrap().retries, 100);
+    }
+
+    #[test]
+    // ensure the analyzed values (and the values close to them) aren't generated in privacy mode
+    fn profile_privacy() {
+        let names = [
+            "Smith, John",
+            "O'Brian, Henny",
+            "Dale, Danny",
+            "Rickets, Ronnae",
+            "Richard, Richie",
+            "Roberts, Blake",
+            "Conways, Sephen",
+        ];
+        let mut profile = Profile::new_with_seed(24);
+        profile.set_privacy(Some(3));
+        for name in names.iter() {
+            profile.analyze(name);
+        }
+        profile.pre_generate();
+
+        for _ in 0..100 {
+            let generated = profile.try_generate().unwrap();
+            for name in names.iter() {
+                assert!(levenshtein_distance!(name, &generated) >= 3);
+            }
+        }
+
+        let serialized = serde_json::to_string(&profile).unwrap();
+        assert!(names.iter().all(|name| !serialized.contains(name)));
+        let mut restored = Profile::from_serialized(&serialized);
+        restored.pre_generate();
+        // the key of the hashes isn't exported
+        assert!(restored.privacy_key().is_none());
+        match restored.try_generate() {
+            Err(TdgError::InvalidSetting { setting, .. }) => assert_eq!(setting, "privacy_key"),
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+
+        restored
+            .set_privacy_key(profile.privacy_key().unwrap())
+            .unwrap();
+        let guard = restored.privacy.unwrap();
+        assert_eq!(guard.len(), names.len());
+        assert!(names.iter().all(|name| !guard.allows(name)));
+    }
+
+    #[test]
+    // ensure a profile that can only generate its analyzed value returns an error in privacy mode
+    fn profile_privacy_exhausted() {
+        let mut profile = Profile::new_with_id("surname".to_string());
+        profile.set_privacy(Some(0));
+        profile.analyze("O'Brien");
+        profile.analyze("");
+        profile.pre_generate();
+        assert_eq!(profile.capacity(), 0.0);
+
+        match profile.try_generate_with_length(None, None) {
+            Err(TdgError::PrivacyExhausted { field, retries }) => {
+                assert_eq!(field.as_deref(), Some("surname"));
+                assert_eq!(retries, 100);
+            }
+            other => panic!("Expected a PrivacyExhausted error, but got {:?}", other),
+        }
+        // missing values are always allowed
+        assert_eq!(profile.generate_missing(), "");
+    }
+
+    #[test]
+    // ensure the order of an archived Markov chain can be lowered, but not raised since the entities aren't kept
+    fn profile_set_markov_order_after_analyze() {
+        let mut profile = Profile::new();
+        profile.set_markov_order(3).unwrap();
+        profile.analyze("Jonathon");
+        profile.analyze("Johnathan");
+        let mut restored = Profile::from_serialized(&profile.serialize());
+        restored.set_markov_order(2).unwrap();
+
+        let mut expected = Profile::new();
+        expected.set_markov_order(2).unwrap();
+        expected.analyze("Jonathon");
+        expected.analyze("Johnathan");
+        assert_eq!(restored.markov, expected.markov);
+
+        match restored.set_markov_order(4) {
+            Err(TdgError::InvalidSetting { setting, .. }) => assert_eq!(setting, "markov_order"),
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+        assert_eq!(restored.markov.unwrap().order, 2);
+    }
+
+    #[test]
+    // ensure the rare patterns are suppressed and the values in privacy mode aren't exported with the noisy profile
+    fn profile_privatize() {
+        let mut profile = Profile::new();
+        profile.set_privacy(Some(0));
+        for _ in 0..20 {
+            profile.analyze("Smith");
+        }
+        profile.analyze("O'Brien");
+        profile.pre_generate();
+
+        // with a huge budget, the noise is negligible
+        let dp = DifferentialPrivacy::new(1e9, 5).unwrap();
+        let mut shared = profile.privatize(&dp).unwrap();
+        assert_eq!(shared.patterns.get("Ccvcc"), Some(&20));
+        assert_eq!(shared.patterns.len(), 1);
+        assert_eq!(shared.sizes.len(), 1);
+        assert_eq!(shared.pattern_total, 20);
+        // the Facts of the single O'Brien are suppressed
+        assert_eq!(shared.facts.total(), 5 * 20);
+        assert!(shared.privacy.is_none());
+        assert_eq!(shared.pattern_ranks, vec![("Ccvcc".to_string(), 100.0)]);
+        assert_eq!(shared.generate().len(), 5);
+
+        // the analyzed profile is unchanged
+        assert_eq!(profile.patterns.len(), 2);
+        assert!(profile.privacy.is_some());
+
+        let invalid = DifferentialPrivacy {
+            epsilon: f64::NAN,
+            k_threshold: 5,
+        };
+        match profile.privatize(&invalid) {
+            Err(TdgError::InvalidSetting { setting, .. }) => assert_eq!(setting, "epsilon"),
+            other => panic!(
+                "Expected an InvalidSetting error, but got {:?}",
+                other.map(|p| p.patterns)
+            ),
+        }
+    }
+
+    #[test]
+    // ensure noise is added to the counts and the rare bins of the numeric model are suppressed
+    fn profile_privatize_noise() {
+        let mut profile = Profile::new();
+        for _ in 0..1000 {
+            profile.analyze("42");
+        }
+        profile.analyze("99");
+
+        let dp = DifferentialPrivacy::new(0.1, 5).unwrap();
+        let counts: Vec<Option<u32>> = (0..5)
+            .map(|_| profile.privatize(&dp).unwrap().patterns.get("##").copied())
+            .collect();
+        assert!(counts.iter().any(|c| *c != Some(1001)));
+
+        let shared = profile
+            .privatize(&DifferentialPrivacy::new(1e9, 5).unwrap())
+            .unwrap();
+        let numeric = shared.numeric.unwrap();
+        // the bins are generalized, so the analyzed numbers aren't exported as bounds
+        assert_eq!(numeric.bins, vec![(40.0, 50.0, 1000)]);
+        assert_eq!((numeric.min, numeric.max), (40.0, 50.0));
+        assert_eq!(numeric.count, 1000);
+    }
+
+    #[test]
+    // ensure the earliest and the latest analyzed dates aren't exported with the noisy profile
+    fn profile_privatize_dates() {
+        let mut profile = Profile::new();
+        for day in 10..=25 {
+            profile.analyze(&format!("2017-03-{}", day));
+        }
+
+        let shared = profile
+            .privatize(&DifferentialPrivacy::new(1e9, 5).unwrap())
+            .unwrap();
+        let model = shared.date_time.unwrap();
+        assert_eq!(
+            model.range(),
+            ("2017-03-01".to_string(), "2017-03-31".to_string())
+        );
+        assert_eq!(
+            profile.date_time.unwrap().range(),
+            ("2017-03-10".to_string(), "2017-03-25".to_string())
+        );
+    }
+
+    #[test]
+    // ensure the capacity of the value space is estimated from the models of the values
+    fn profile_capacity() {
+        let mut dates = Profile::new();
+        dates.analyze("2017-01-01");
+        dates.analyze("2017-12-31");
+        assert_eq!(dates.capacity(), 365.0);
+
+        let mut numbers = Profile::new();
+        numbers.analyze("1.25");
+        numbers.analyze("1.75");
+        numbers.analyze("9");
+        assert_eq!(numbers.capacity(), 3.0);
+
+        let mut emails = Profile::new();
+        emails.analyze("jdoe@example.com");
+        assert_eq!(emails.capacity(), f64::INFINITY);
+
+        // the n-gram model can choose the chars of a symbol at any position
+        let mut names = Profile::new();
+        names.analyze("Ab");
+        names.analyze("Ecd");
+        assert_eq!(names.capacity(), 4.0);
+        names.set_markov_order(1).unwrap();
+        assert_eq!(names.capacity(), 24.0);
+        names.set_novelty(0.1).unwrap();
+        assert_eq!(names.capacity(), f64::INFINITY);
+    }
+
+    #[test]
+    // ensure values are generated from unseen patterns at the rate of the novelty
+    fn profile_novelty() {
+        let mut profile = Profile::new_with_seed(22);
+        for value in ["Bert", "Berta", "Becall", "Bart"].iter() {
+            profile.analyze(value);
+        }
+        profile.pre_generate();
+        let pttrn_def = PatternDefinition::new();
+
+        profile.set_novelty(0.0).unwrap();
+        for _ in 0..100 {
+            let generated = profile.try_generate().unwrap();
+            assert!(profile
+                .patterns
+                .contains_key(&pttrn_def.symbolize(&generated)));
+        }
+
+        profile.set_novelty(1.0).unwrap();
+        let mut unseen = 0;
+        for _ in 0..100 {
+            let generated = profile.try_generate().unwrap();
+            assert!(generated.starts_with('B'));
+            if !profile
+                .patterns
+                .contains_key(&pttrn_def.symbolize(&generated))
+            {
+                unseen += 1;
+            }
+        }
+        assert!(unseen > 50);
+
+        let restored = Profile::from_serialized(&serde_json::to_string(&profile).unwrap());
+        assert_eq!(restored.novelty, Some(1.0));
+    }
+
+    #[test]
+    // ensure the novelty must be between 0 and 1
+    fn profile_novelty_invalid() {
+        let mut profile = Profile::new();
+
+        for novelty in [-0.1, 1.1, f64::NAN].iter() {
+            match profile.set_novelty(*novelty) {
+                Err(TdgError::InvalidSetting { setting, .. }) => assert_eq!(setting, "novelty"),
+                other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+            }
+        }
+        assert!(profile.novelty.is_none());
+    }
+
+    #[test]
+    // ensure the target length is sampled from the analyzed sizes within the range
+    fn profile_generate_with_length() {
+        let mut profile = Profile::new_with_seed(21);
+        for value in ["AC-1001", "AC-2002", "AC-3003", "AC-40045", "AC-500", ""].iter() {
+            profile.analyze(value);
+        }
+        profile.pre_generate();
+        assert_eq!(profile.sizes.get(&8), Some(&1));
+
+        let mut lengths = BTreeMap::new();
+        for _ in 0..200 {
+            let generated = profile.try_generate_with_length(None, None).unwrap();
+            assert!(generated.starts_with("AC"));
+            *lengths.entry(generated.len()).or_insert(0) += 1;
+        }
+        assert_eq!(lengths.keys().copied().collect::<Vec<_>>(), vec![6, 7, 8]);
+        assert!(lengths[&7] > lengths[&6] && lengths[&7] > lengths[&8]);
+
+        for _ in 0..20 {
+            let generated = profile.try_generate_with_length(Some(8), None).unwrap();
+            assert_eq!(generated.len(), 8);
+            assert!(generated[3..].chars().all(|c| c.is_ascii_digit()));
+        }
+
+        match profile.try_generate_with_length(Some(0), Some(0)) {
+            Err(TdgError::InvalidSetting { setting, .. }) => assert_eq!(setting, "length"),
+            other => panic!("Expected an InvalidSetting error, but got {:?}", other),
+        }
+        assert!(Profile::new()
+            .try_generate_with_length(None, Some(5))
+            .is_err());
+    }
+
+    #[test]
+    // ensure a pattern is synthesized for a length that wasn't analyzed
+    fn profile_generate_with_length_synthesized() {
+        let mut profile = Profile::new_with_seed(12);
+        profile.analyze("0012345678");
+        profile.analyze("0098765432");
+        profile.pre_generate();
+
+        for length in [1, 4, 10, 12, 16].iter() {
+            let generated = profile
+                .try_generate_with_length(Some(*length), Some(*length))
+                .unwrap();
+            assert_eq!(generated.chars().count(), *length as usize);
+            assert!(generated.starts_with('0'));
+            assert!(generated.chars().all(|c| c.is_ascii_digit()));
+        }
+
+        let generated = profile
+            .try_generate_with_length(Some(11), Some(14))
+            .unwrap();
+        assert!((11..=14).contains(&generated.len()));
+    }
+
+    #[test]
+    // ensure epoch seconds are generated as dates rather than as numbers
+    fn profile_date_time_epoch_seconds() {
+        let mut profile = Profile::new_with_seed(2);
+        profile.set_date_shift(1);
+        profile.set_epoch_seconds(true);
+        profile.analyze("1483488000");
+        profile.analyze("1483574399");
+        profile.pre_generate();
+
+        assert_eq!(profile.data_type, Some(DataType::Integer));
+        let epoch: i64 = profile.try_generate().unwrap().parse().unwrap();
+        assert!((1483574400..=1483660799).contains(&epoch));
+    }
+
+    #[test]
+    // ensure numbers of 9 or 10 digits (e.g.: ids) are generated as numbers unless epoch seconds are enabled
+    fn profile_epoch_seconds_opt_in() {
+        let mut profile = Profile::new_with_seed(18);
+        for id in ["1000000001", "1000000002", "1999999999"].iter() {
+            profile.analyze(id);
+        }
+        profile.pre_generate();
+
+        assert!(profile.date_time.is_none());
+        assert!(profile.numeric.is_some());
+        let restored = Profile::from_serialized(&profile.serialize());
+        assert!(!restored.epoch_seconds);
+    }
+
+    #[test]
+    // ensure a field with numbers and text is generated from its patterns
+    fn profile_numeric_mixed_with_text() {
+        let mut profile = Profile::new_with_seed(3);
+        profile.analyze("12");
+        profile.analyze("A7");
+        profile.pre_generate();
+
+        assert_eq!(profile.data_type, Some(DataType::String));
+        assert_eq!(profile.numeric.as_ref().unwrap().count, 1);
+        for _ in 0..20 {
+            assert_eq!(profile.try_generate().unwrap().len(), 2);
+        }
+    }
+
+    #[test]
+    // ensure the type of the values is inferred while analyzing
+    fn profile_data_type() {
+        let mut profile = Profile::new();
+        assert_eq!(profile.data_type, None);
+
+        profile.analyze("42");
+        assert_eq!(profile.data_type, Some(DataType::Integer));
+        profile.analyze("");
+        profile.analyze("-7.25");
+        assert_eq!(profile.data_type, Some(DataType::Decimal));
+        profile.analyze("unknown");
+        assert_eq!(profile.data_type, Some(DataType::String));
     }
 }
@@ -26,6 +26,10 @@ macro_rules! levenshtein_distance {
 /// This macro generates a random number between 0 and 100.
 /// Returns a f64.
 ///
+/// # Arguments
+///
+/// * `rng: &mut fastrand::Rng` - (optional) The random number generator to draw from. When omitted, a new unseeded generator is used.</br>
+///
 /// # Example
 ///
 /// ```rust
@@ -33,15 +37,23 @@ macro_rules! levenshtein_distance {
 /// # fn main() {
 ///     let rnd: f64 = random_percentage!();
 ///     println!("Your random number is {}", rnd);
+///
+///     // use a seeded generator to get reproducible numbers
+///     let mut rng = fastrand::Rng::with_seed(42);
+///     let seeded: f64 = random_percentage!(rng);
+///     assert_eq!(seeded, random_percentage!(fastrand::Rng::with_seed(42)));
 /// # }
 /// ```
 #[macro_export]
 macro_rules! random_percentage {
-    ( $( $x:expr ),* ) => {{
+    () => {{
         use fastrand; //DevSkim: ignore DS148264
 
         let mut rng = fastrand::Rng::new(); //DevSkim: ignore DS148264
-        let nbr: f64 = rng.f64() * 100_f64; //DevSkim: ignore DS148264
+        random_percentage!(rng)
+    }};
+    ( $rng:expr ) => {{
+        let nbr: f64 = $rng.f64() * 100_f64; //DevSkim: ignore DS148264
 
         nbr
     }};
@@ -54,6 +66,7 @@ macro_rules! random_percentage {
 ///
 /// * `a: u32` - The lowest number of the range to use for the random number.</br>
 /// * `b: u32` - The highest number of the range to use for the random number.</br>
+/// * `rng: &mut fastrand::Rng` - (optional) The random number generator to draw from. When omitted, a new unseeded generator is used.</br>
 ///
 /// # Example
 ///
@@ -62,6 +75,11 @@ macro_rules! random_percentage {
 /// # fn main() {
 ///     let rnd: u32 = random_between!(0, 100);
 ///     println!("Your random number is {}", rnd);
+///
+///     // use a seeded generator to get reproducible numbers
+///     let mut rng = fastrand::Rng::with_seed(42);
+///     let seeded: u32 = random_between!(0, 100, rng);
+///     assert_eq!(seeded, random_between!(0, 100, fastrand::Rng::with_seed(42)));
 /// # }
 /// ```
 #[macro_export]
@@ -70,7 +88,10 @@ macro_rules! random_between {
         use fastrand; //DevSkim: ignore DS148264
 
         let mut rng = fastrand::Rng::new(); //DevSkim: ignore DS148264
-        let nbr: u32 = rng.u32($a as u32..$b as u32); //DevSkim: ignore DS148264
+        random_between!($a, $b, rng)
+    }};
+    ($a:expr, $b:expr, $rng:expr) => {{
+        let nbr: u32 = $rng.u32($a as u32..$b as u32); //DevSkim: ignore DS148264
 
         nbr
     }};
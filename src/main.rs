@@ -0,0 +1,290 @@
+//! The `tdg` binary provides the Test Data Generation tools from the command line, so that the library can be used from shell scripts.
+//!
+//! # Tools
+//!
+//! data-sampler: analyzes a csv file of sample data and saves the Data Sample Parser as an archive file (json)
+//! (.json and .ndjson/.jsonl input files are analyzed as JSON records)
+//!
+//! > `tdg --tool data-sampler --input ./tests/samples/sample-01.csv --output ./tests/samples/sample-01-dsp.json`
+//!
+//! > `tdg --tool data-sampler --input ./tests/samples/sample-events.ndjson --output ./tests/samples/sample-events-dsp.json`
+//!
+//! data-generator: generates a csv file of test data from a saved Data Sample Parser
+//!
+//! > `tdg --tool data-generator --input ./tests/samples/sample-01-dsp.json --output ./generated-01.csv --rows 1000`
+//!
+//! > `tdg --tool data-generator --input ./tests/samples/sample-01-dsp.json --output - --rows 1000 | psql -c "COPY people FROM STDIN CSV HEADER"`
+//!
+//! The fields in privacy mode (`privacy-min-distance` in the configuration file) are analyzed and generated with a secret key
+//! that isn't saved in the archive, so specify the same key to both tools (`--privacy-key` or `privacy-key` in the configuration file)
+//!
+//! > `tdg --tool data-sampler --config ./tdg-privacy.yaml --input ./people.csv --output ./people-dsp.json --privacy-key "$TDG_KEY"`
+//!
+//! > `tdg --tool data-generator --input ./people-dsp.json --output ./generated.csv --privacy-key "$TDG_KEY"`
+//!
+//! profile-inspector: prints a summary of the Profiles in a saved Data Sample Parser (or a single saved Profile)
+//!
+//! > `tdg --tool profile-inspector --input ./tests/samples/sample-01-dsp.json`
+#[macro_use]
+extern crate log;
+extern crate clap;
+extern crate log4rs;
+extern crate test_data_generation;
+
+mod params;
+
+use log::LevelFilter;
+use log4rs::append::console::{ConsoleAppender, Target};
+use log4rs::config::{Appender, Config, Root};
+use params::Params;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::process;
+use test_data_generation::data_sample_parser::DataSampleParser;
+use test_data_generation::errors::{TdgError, TdgResult};
+use test_data_generation::Profile;
+
+/// The number of patterns to list when inspecting a Profile
+const TOP_PATTERNS: usize = 5;
+
+fn main() {
+    let params = Params::new();
+    init_logging(&params);
+
+    let rslt = match params.get_tool() {
+        "data-sampler" => data_sampler(&params),
+        "data-generator" => data_generator(&params),
+        _ => profile_inspector(&params),
+    };
+
+    if let Err(e) = rslt {
+        error!("{}", e);
+        eprintln!("tdg: {}", e);
+        process::exit(1);
+    }
+}
+
+// logs to the console when verbose, otherwise uses the log4rs configuration file (if there is one)
+fn init_logging(params: &Params) {
+    let level = match params.get_verbose() {
+        "debug" => LevelFilter::Debug,
+        "info" => LevelFilter::Info,
+        _ => LevelFilter::Off,
+    };
+
+    let rslt = if level != LevelFilter::Off {
+        let stderr = ConsoleAppender::builder().target(Target::Stderr).build();
+        Config::builder()
+            .appender(Appender::builder().build("stderr", Box::new(stderr)))
+            .build(Root::builder().appender("stderr").build(level))
+            .map_err(|e| e.to_string())
+            .and_then(|cfg| {
+                log4rs::init_config(cfg)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            })
+    } else if Path::new(params.get_log_file()).exists() {
+        log4rs::init_file(params.get_log_file(), Default::default()).map_err(|e| e.to_string())
+    } else {
+        Ok(())
+    };
+
+    if let Err(e) = rslt {
+        eprintln!("tdg: logging couldn't be enabled because of {}", e);
+    }
+}
+
+// the library adds the .json extension to the path of the archive files
+fn archive_path(path: &str) -> &str {
+    path.strip_suffix(".json").unwrap_or(path)
+}
+
+fn new_data_sample_parser(params: &Params) -> TdgResult<DataSampleParser> {
+    let mut dsp = if params.has_config_file() || Path::new(params.get_config_file()).exists() {
+        DataSampleParser::try_new_with(params.get_config_file())?
+    } else {
+        DataSampleParser::new()
+    };
+
+    if let Some(seed) = params.get_seed() {
+        dsp.set_seed(seed);
+    }
+
+    if let Some(key) = params.get_privacy_key() {
+        dsp.set_privacy_key(key)?;
+    }
+
+    Ok(dsp)
+}
+
+fn data_sampler(params: &Params) -> TdgResult<()> {
+    let output = archive_path(params.get_output().unwrap());
+    let mut dsp = new_data_sample_parser(params)?;
+
+    info!("Analyzing {} ...", params.get_input());
+    match params.get_input() {
+        // stream the csv data from stdin, (e.g.: piped from another command)
+        "-" => dsp.try_analyze_csv_reader(io::stdin().lock(), params.get_delimiter())?,
+        input => match Path::new(input).extension().and_then(|e| e.to_str()) {
+            Some("json") => dsp.try_analyze_json_reader(open_input(input)?)?,
+            Some("ndjson") | Some("jsonl") => dsp.try_analyze_ndjson_reader(open_input(input)?)?,
+            _ => dsp.try_analyze_csv_file(input, params.get_delimiter())?,
+        },
+    }
+
+    // the keys of the privacy mode aren't exported, so the archive can only generate data with a key that is supplied again
+    let headers = dsp.extract_headers();
+    let in_privacy = headers
+        .iter()
+        .any(|f| dsp.get_profile(f).is_some_and(|p| p.privacy.is_some()));
+    if in_privacy && dsp.privacy_key().is_none() {
+        let msg = "the fields in privacy mode were analyzed with random keys, so the data-generator can't use the archive, (specify --privacy-key)";
+        warn!("{}", msg);
+        eprintln!("tdg: {}", msg);
+    }
+
+    info!("Saving the Data Sample Parser to {}.json ...", output);
+    dsp.try_save(output)
+}
+
+fn open_input(path: &str) -> TdgResult<File> {
+    File::open(path).map_err(|e| TdgError::FileOpen {
+        path: path.to_string(),
+        source: e,
+    })
+}
+
+fn data_generator(params: &Params) -> TdgResult<()> {
+    let mut dsp = DataSampleParser::try_from_file(archive_path(params.get_input()))?;
+
+    if let Some(seed) = params.get_seed() {
+        dsp.set_seed(seed);
+    }
+
+    if let Some(key) = params.get_privacy_key() {
+        dsp.set_privacy_key(key)?;
+    }
+
+    info!(
+        "Generating {} rows to {} ...",
+        params.get_rows(),
+        params.get_output().unwrap()
+    );
+    match params.get_output().unwrap() {
+        // stream the csv data to stdout, (e.g.: piped to another command)
+        "-" => dsp.try_generate_csv_writer(
+            params.get_rows(),
+            io::stdout().lock(),
+            params.get_delimiter(),
+        ),
+        output => dsp.try_generate_csv(params.get_rows(), output, params.get_delimiter()),
+    }
+}
+
+fn profile_inspector(params: &Params) -> TdgResult<()> {
+    let input = archive_path(params.get_input());
+
+    match DataSampleParser::try_from_file(input) {
+        Ok(mut dsp) => {
+            for field in dsp.extract_headers() {
+                if let Some(profile) = dsp.get_profile(&field) {
+                    println!("{}", describe_profile(&field, profile));
+                }
+            }
+            Ok(())
+        }
+        // the archive isn't a Data Sample Parser, so try to load it as a single Profile
+        Err(TdgError::MissingField { .. }) | Err(TdgError::Json { .. }) => {
+            let profile = Profile::try_from_file(input)?;
+            let id = profile.id.clone().unwrap_or_else(|| "profile".to_string());
+            println!("{}", describe_profile(&id, &profile));
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn describe_profile(name: &str, profile: &Profile) -> String {
+    let mut patterns: Vec<(&String, &u32)> = profile.patterns.iter().collect();
+    patterns.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+    let mut desc = format!("{}\n", name);
+    desc.push_str(&format!("  entities analyzed: {}\n", profile.pattern_total));
+    desc.push_str(&format!(
+        "  missing values: {:.1}%\n",
+        profile.missing_rate() * 100.0
+    ));
+    desc.push_str(&format!(
+        "  distinct patterns: {}\n",
+        profile.patterns.len()
+    ));
+    desc.push_str(&format!(
+        "  sizes: {}\n",
+        match (profile.sizes.keys().next(), profile.sizes.keys().last()) {
+            (Some(min), Some(max)) => format!("{} to {}", min, max),
+            _ => "none".to_string(),
+        }
+    ));
+    desc.push_str(&format!(
+        "  facts: {} ({} distinct)\n",
+        profile.facts.total(),
+        profile.facts.len()
+    ));
+    if let Some(data_type) = profile.data_type {
+        desc.push_str(&format!("  data type: {:?}\n", data_type));
+    }
+    if let Some(semantic) = profile.semantic.as_ref() {
+        desc.push_str(&format!("  semantic type: {}\n", semantic.detector));
+    }
+    if let Some(date_time) = profile.date_time.as_ref() {
+        let (min, max) = date_time.range();
+        desc.push_str(&format!(
+            "  dates: {} to {} ({})\n",
+            min,
+            max,
+            date_time.format()
+        ));
+    }
+    if let Some(numeric) = profile.numeric.as_ref() {
+        desc.push_str(&format!(
+            "  numbers: {} to {} (median {}, {} decimals)\n",
+            numeric.min,
+            numeric.max,
+            numeric.quantile(0.5),
+            numeric.precision
+        ));
+    }
+    if let Some(seed) = profile.seed {
+        desc.push_str(&format!("  seed: {}\n", seed));
+    }
+    if let Some(chain) = profile.markov.as_ref() {
+        desc.push_str(&format!("  markov order: {}\n", chain.order));
+    }
+    if let Some(novelty) = profile.novelty {
+        desc.push_str(&format!("  novelty: {}\n", novelty));
+    }
+    if let Some(guard) = profile.privacy.as_ref() {
+        desc.push_str(&format!(
+            "  privacy: {} analyzed values excluded (min distance {})\n",
+            guard.len(),
+            guard.min_distance
+        ));
+    }
+    if profile.unique.is_some() {
+        desc.push_str(&format!(
+            "  unique: yes (capacity {})\n",
+            profile.capacity()
+        ));
+    }
+    desc.push_str("  top patterns:");
+    for (pattern, count) in patterns.iter().take(TOP_PATTERNS) {
+        desc.push_str(&format!(
+            "\n    {} ({:.2}%)",
+            pattern,
+            **count as f64 / profile.pattern_total as f64 * 100_f64
+        ));
+    }
+
+    desc
+}
@@ -1,68 +1,262 @@
-use clap::{Arg, App, ArgMatches};
-
-/// A parameters manager object
-/// 
-
-
-pub struct Params<'a>{
-    opts: ArgMatches<'a>,
-}
-
-impl<'a> Params<'a> {
-    //constructor
-    pub fn new() -> Params<'a> {
-        Params{
-            opts: App::new("Test Data Generation")
-                          .version("1.0")
-                          .author("dsietz")
-                          .about("Made just for you!")
-                          .arg(Arg::with_name("tool")
-                               .short("t")
-                               .long("tool")
-                               .possible_values(&["data-sampler","data-generator"])
-                               .required(true)
-                               .default_value("data-sampler")
-                               .help("define the tool to use (options: data-sampler, data-generator)"))
-                          .arg(Arg::with_name("config")
-                               .short("c")
-                               .long("config")
-                               .value_name("FILE")
-                               .help("Specifies the location of the Test Data Generation configuration file (default tdg.yaml)")
-                               .takes_value(true)
-                               .default_value("tdg.yaml"))
-                          .arg(Arg::with_name("log")
-                               .short("l")
-                               .long("log")
-                               .value_name("FILE")
-                               .help("Specifies the location of the log4rs logging configuration file (default log4rs.yaml)")
-                               .takes_value(true)
-                               .default_value("log4rs.yaml"))     
-                          .arg(Arg::with_name("verbose")
-                               .short("v")
-                               .long("verbose")
-                               .possible_values(&["off","info","debug"])
-                               .default_value("off")
-                               .help("explain what is being done (options: off, info, debug)"))
-                          .get_matches(),
-        }
-    }
-    
-    // get() functions
-    pub fn get_config_file(&self) -> &str{
-        &self.opts.value_of("config").unwrap()
-    }
-    
-    pub fn get_log_file(&self) -> &str{
-        &self.opts.value_of("log").unwrap()
-    }
-    
-    pub fn get_tool(&self) -> &str{
-        &self.opts.value_of("tool").unwrap()
-    }
-    
-    pub fn get_verbose(&self) -> &str{
-        &self.opts.value_of("verbose").unwrap()
-    }
-    
-    //set() functions
-}
+use clap::{App, Arg, ArgMatches};
+use test_data_generation::configs::to_privacy_key;
+
+/// A parameters manager object
+///
+pub struct Params<'a> {
+    opts: ArgMatches<'a>,
+}
+
+impl<'a> Params<'a> {
+    //constructor
+    pub fn new() -> Params<'a> {
+        Params {
+            opts: Self::app().get_matches(),
+        }
+    }
+
+    // constructor that parses the specified arguments (the first argument is the name of the binary)
+    #[cfg(test)]
+    pub fn from_args<I, T>(args: I) -> Result<Params<'a>, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
+        Ok(Params {
+            opts: Self::app().get_matches_from_safe(args)?,
+        })
+    }
+
+    fn app() -> App<'a, 'a> {
+        App::new("Test Data Generation")
+            .version(env!("CARGO_PKG_VERSION"))
+            .author("dsietz")
+            .about("Made just for you!")
+            .arg(Arg::with_name("tool")
+                .short("t")
+                .long("tool")
+                .possible_values(&["data-sampler", "data-generator", "profile-inspector"])
+                .required(true)
+                .default_value("data-sampler")
+                .help("define the tool to use (options: data-sampler, data-generator, profile-inspector)"))
+            .arg(Arg::with_name("config")
+                .short("c")
+                .long("config")
+                .value_name("FILE")
+                .help("Specifies the location of the Test Data Generation configuration file (default tdg.yaml)")
+                .takes_value(true)
+                .default_value("tdg.yaml"))
+            .arg(Arg::with_name("log")
+                .short("l")
+                .long("log")
+                .value_name("FILE")
+                .help("Specifies the location of the log4rs logging configuration file (default log4rs.yaml)")
+                .takes_value(true)
+                .default_value("log4rs.yaml"))
+            .arg(Arg::with_name("verbose")
+                .short("v")
+                .long("verbose")
+                .possible_values(&["off", "info", "debug"])
+                .default_value("off")
+                .help("explain what is being done (options: off, info, debug)"))
+            .arg(Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(true)
+                .help("Specifies the csv file to analyze, or - for stdin (data-sampler) or the archive file (json) to generate data from or inspect (data-generator, profile-inspector)"))
+            .arg(Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .value_name("FILE")
+                .takes_value(true)
+                .required_ifs(&[("tool", "data-sampler"), ("tool", "data-generator")])
+                .help("Specifies the archive file (json) to save (data-sampler) or the csv file to generate, or - for stdout (data-generator)"))
+            .arg(Arg::with_name("rows")
+                .short("r")
+                .long("rows")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .default_value("100")
+                .validator(|v| v.parse::<u32>().map(|_| ()).map_err(|e| e.to_string()))
+                .help("Specifies the number of rows to generate (data-generator)"))
+            .arg(Arg::with_name("delimiter")
+                .short("d")
+                .long("delimiter")
+                .value_name("CHAR")
+                .takes_value(true)
+                .validator(|v| match v.len() {
+                    1 => Ok(()),
+                    _ => Err("the delimiter must be a single (ascii) character".to_string()),
+                })
+                .help("Specifies the delimiter of the csv file to analyze or generate (default ,)"))
+            .arg(Arg::with_name("seed")
+                .short("s")
+                .long("seed")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(|v| v.parse::<u64>().map(|_| ()).map_err(|e| e.to_string()))
+                .help("Specifies the seed of the random number generator so that the generated data can be reproduced"))
+            .arg(Arg::with_name("privacy-key")
+                .short("k")
+                .long("privacy-key")
+                .value_name("HEX")
+                .takes_value(true)
+                .validator(|v| to_privacy_key("privacy-key", &Some(v)).map(|_| ()).map_err(|e| e.to_string()))
+                .help("Specifies the secret key (hexadecimal) of the hashes of the privacy mode, so that the archive of the data-sampler can be used by the data-generator (data-sampler, data-generator)"))
+    }
+
+    // get() functions
+    pub fn get_config_file(&self) -> &str {
+        self.opts.value_of("config").unwrap()
+    }
+
+    // returns true if the configuration file was specified (instead of using the default)
+    pub fn has_config_file(&self) -> bool {
+        self.opts.occurrences_of("config") > 0
+    }
+
+    pub fn get_log_file(&self) -> &str {
+        self.opts.value_of("log").unwrap()
+    }
+
+    pub fn get_tool(&self) -> &str {
+        self.opts.value_of("tool").unwrap()
+    }
+
+    pub fn get_verbose(&self) -> &str {
+        self.opts.value_of("verbose").unwrap()
+    }
+
+    pub fn get_input(&self) -> &str {
+        self.opts.value_of("input").unwrap()
+    }
+
+    pub fn get_output(&self) -> Option<&str> {
+        self.opts.value_of("output")
+    }
+
+    pub fn get_rows(&self) -> u32 {
+        self.opts.value_of("rows").unwrap().parse::<u32>().unwrap()
+    }
+
+    pub fn get_delimiter(&self) -> Option<u8> {
+        self.opts.value_of("delimiter").map(|d| d.as_bytes()[0])
+    }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.opts
+            .value_of("seed")
+            .map(|s| s.parse::<u64>().unwrap())
+    }
+
+    pub fn get_privacy_key(&self) -> Option<u128> {
+        self.opts.value_of("privacy-key").map(|k| {
+            to_privacy_key("privacy-key", &Some(k.to_string()))
+                .unwrap()
+                .unwrap()
+        })
+    }
+
+    //set() functions
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_data_sampler() {
+        let params = Params::from_args(vec![
+            "tdg",
+            "-i",
+            "./tests/samples/sample-01.csv",
+            "-o",
+            "./tests/samples/sample-01-dsp",
+        ])
+        .unwrap();
+
+        assert_eq!(params.get_tool(), "data-sampler");
+        assert_eq!(params.get_input(), "./tests/samples/sample-01.csv");
+        assert_eq!(params.get_output(), Some("./tests/samples/sample-01-dsp"));
+        assert_eq!(params.get_config_file(), "tdg.yaml");
+        assert!(!params.has_config_file());
+        assert_eq!(params.get_log_file(), "log4rs.yaml");
+        assert_eq!(params.get_verbose(), "off");
+        assert_eq!(params.get_delimiter(), None);
+        assert_eq!(params.get_seed(), None);
+        assert_eq!(params.get_privacy_key(), None);
+    }
+
+    #[test]
+    fn params_data_generator() {
+        let params = Params::from_args(vec![
+            "tdg",
+            "--tool",
+            "data-generator",
+            "--input",
+            "./tests/samples/sample-01-dsp.json",
+            "--output",
+            "./tests/samples/generated-01.csv",
+            "--rows",
+            "25",
+            "--delimiter",
+            ";",
+            "--seed",
+            "42",
+            "--privacy-key",
+            "0x5eed",
+        ])
+        .unwrap();
+
+        assert_eq!(params.get_tool(), "data-generator");
+        assert_eq!(params.get_rows(), 25);
+        assert_eq!(params.get_delimiter(), Some(b';'));
+        assert_eq!(params.get_seed(), Some(42));
+        assert_eq!(params.get_privacy_key(), Some(0x5eed));
+    }
+
+    #[test]
+    fn params_missing_output() {
+        assert!(Params::from_args(vec![
+            "tdg",
+            "-t",
+            "data-generator",
+            "-i",
+            "./tests/samples/sample-01-dsp.json"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn params_profile_inspector() {
+        let params = Params::from_args(vec![
+            "tdg",
+            "-t",
+            "profile-inspector",
+            "-i",
+            "./tests/samples/sample-01-dsp.json",
+        ])
+        .unwrap();
+
+        assert_eq!(params.get_tool(), "profile-inspector");
+        assert_eq!(params.get_output(), None);
+    }
+
+    #[test]
+    fn params_bad_privacy_key() {
+        assert!(
+            Params::from_args(vec!["tdg", "-i", "sample.csv", "-o", "dsp", "-k", "secret"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn params_bad_rows() {
+        assert!(
+            Params::from_args(vec!["tdg", "-i", "sample.csv", "-o", "dsp", "-r", "ten"]).is_err()
+        );
+    }
+}
@@ -19,6 +19,7 @@ use std::mem;
 ///        let static_str =  shared::string_to_static_str(my_string);
 /// }
 /// ```
+#[allow(clippy::missing_transmute_annotations)]
 pub fn string_to_static_str(s: String) -> &'static str {
     unsafe {
         let ret = mem::transmute(&s as &str);
@@ -86,6 +87,7 @@ pub trait CsvManipulator {
                 columns.extend(new_columns);
             }
 
+            #[allow(clippy::needless_range_loop)]
             for c in 0..record.len() {
                 field = record.get(c).unwrap();
                 columns[c].push(field.to_owned());
@@ -0,0 +1,452 @@
+//! The `sql` module provides the options and the formatting of the SQL scripts that seed a database with generated test data,
+//! (see DataSampleParser::try_generate_sql()).
+//!
+//! A script can create the table (with column types that are inferred from the analyzed values) and insert the records
+//! using batched `INSERT` statements or a `COPY ... FROM STDIN` block (PostgreSQL only).
+//! Identifiers are always double quoted and string literals are single quoted, (the quotes are escaped by doubling them).
+//!
+//! # Examples
+//!
+//!
+//! Generate a PostgreSQL script that uses COPY ...
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::data_sample_parser::DataSampleParser;
+//! use test_data_generation::sql::{SqlDialect, SqlOptions, SqlStatement};
+//!
+//! fn main() {
+//!     let mut dsp = DataSampleParser::new();
+//!     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv"), None).unwrap();
+//!
+//!     let mut options = SqlOptions::new("people");
+//!     options.dialect = SqlDialect::Postgres;
+//!     options.statement = SqlStatement::Copy;
+//!
+//!     let mut script = Vec::new();
+//!     dsp.try_generate_sql_writer(10, &mut script, &options).unwrap();
+//!
+//!     let script = String::from_utf8(script).unwrap();
+//!     assert!(script.contains("COPY \"people\" (\"firstname\", \"lastname\") FROM STDIN;"));
+//! }
+//! ```
+
+use crate::engine::data_type::DataType;
+use crate::errors::{TdgError, TdgResult};
+use serde_json::Value;
+
+/// The number of records of an INSERT statement if the options don't specify it
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// Represents the SQL dialect of the database to seed
+pub enum SqlDialect {
+    /// PostgreSQL
+    Postgres,
+    /// SQLite
+    Sqlite,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// Represents the kind of statement that inserts the records
+pub enum SqlStatement {
+    /// batched INSERT INTO ... VALUES statements
+    Insert,
+    /// a COPY ... FROM STDIN block, (PostgreSQL only)
+    Copy,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+/// Represents the options of a generated SQL script
+pub struct SqlOptions {
+    /// the name of the table to insert the records into
+    pub table: String,
+    /// the SQL dialect of the database, (default postgres)
+    pub dialect: SqlDialect,
+    /// the kind of statement that inserts the records, (default insert)
+    pub statement: SqlStatement,
+    /// true to start the script with a CREATE TABLE IF NOT EXISTS statement, (default true)
+    pub create_table: bool,
+    /// the number of records of each INSERT statement, (default 100)
+    pub batch_size: usize,
+}
+
+impl SqlOptions {
+    /// Constructs a new SqlOptions for the table that uses the default options
+    ///
+    /// # Arguments
+    ///
+    /// * `table: &str` - The name of the table to insert the records into.</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::sql::{SqlDialect, SqlOptions};
+    ///
+    /// fn main() {
+    ///     let options = SqlOptions::new("people");
+    ///     assert_eq!(options.dialect, SqlDialect::Postgres);
+    /// }
+    /// ```
+    pub fn new(table: &str) -> SqlOptions {
+        SqlOptions {
+            table: table.to_string(),
+            dialect: SqlDialect::Postgres,
+            statement: SqlStatement::Insert,
+            create_table: true,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// This function returns an error if the options can't be used to generate a script.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::sql::{SqlDialect, SqlOptions, SqlStatement};
+    ///
+    /// fn main() {
+    ///     let mut options = SqlOptions::new("people");
+    ///     options.dialect = SqlDialect::Sqlite;
+    ///     options.statement = SqlStatement::Copy;
+    ///
+    ///     // SQLite doesn't support COPY
+    ///     assert!(options.validate().is_err());
+    /// }
+    /// ```
+    pub fn validate(&self) -> TdgResult<()> {
+        let invalid = |setting: &str, message: &str| {
+            Err(TdgError::InvalidSetting {
+                setting: setting.to_string(),
+                message: message.to_string(),
+            })
+        };
+
+        if self.table.is_empty() {
+            return invalid("table", "the name of the table can't be empty");
+        }
+        if self.batch_size == 0 {
+            return invalid("batch-size", "the number of records must be at least 1");
+        }
+        if self.statement == SqlStatement::Copy && self.dialect != SqlDialect::Postgres {
+            return invalid(
+                "statement",
+                "COPY is only supported by the postgres dialect",
+            );
+        }
+
+        Ok(())
+    }
+
+    /// This function returns the CREATE TABLE IF NOT EXISTS statement of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns: &[String]` - The names of the columns.</br>
+    /// * `data_types: &[Option<DataType>]` - The type of the values of each column, (None is a text column).</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::engine::data_type::DataType;
+    /// use test_data_generation::sql::SqlOptions;
+    ///
+    /// fn main() {
+    ///     let options = SqlOptions::new("people");
+    ///     let columns = vec!["name".to_string(), "age".to_string()];
+    ///
+    ///     assert_eq!(
+    ///         options.create_table_statement(&columns, &[None, Some(DataType::Integer)]),
+    ///         "CREATE TABLE IF NOT EXISTS \"people\" (\n  \"name\" TEXT,\n  \"age\" BIGINT\n);\n"
+    ///     );
+    /// }
+    /// ```
+    pub fn create_table_statement(
+        &self,
+        columns: &[String],
+        data_types: &[Option<DataType>],
+    ) -> String {
+        let definitions: Vec<String> = columns
+            .iter()
+            .zip(data_types.iter())
+            .map(|(column, data_type)| {
+                format!(
+                    "  {} {}",
+                    quote_identifier(column),
+                    self.column_type(*data_type)
+                )
+            })
+            .collect();
+
+        format!(
+            "CREATE TABLE IF NOT EXISTS {} (\n{}\n);\n",
+            quote_identifier(&self.table),
+            definitions.join(",\n")
+        )
+    }
+
+    /// This function returns the INSERT statement of a batch of records.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns: &[String]` - The names of the columns.</br>
+    /// * `records: &[Vec<Value>]` - The typed values of the records, (see DataType::typed_value()).</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    /// extern crate serde_json;
+    ///
+    /// use serde_json::Value;
+    /// use test_data_generation::sql::SqlOptions;
+    ///
+    /// fn main() {
+    ///     let options = SqlOptions::new("people");
+    ///     let columns = vec!["name".to_string(), "age".to_string()];
+    ///     let records = vec![vec![Value::from("O'Brian"), Value::from(42)]];
+    ///
+    ///     assert_eq!(
+    ///         options.insert_statement(&columns, &records),
+    ///         "INSERT INTO \"people\" (\"name\", \"age\") VALUES\n  ('O''Brian', 42);\n"
+    ///     );
+    /// }
+    /// ```
+    pub fn insert_statement(&self, columns: &[String], records: &[Vec<Value>]) -> String {
+        let rows: Vec<String> = records
+            .iter()
+            .map(|record| {
+                let values: Vec<String> = record.iter().map(|v| self.literal(v)).collect();
+                format!("  ({})", values.join(", "))
+            })
+            .collect();
+
+        format!(
+            "INSERT INTO {} ({}) VALUES\n{};\n",
+            quote_identifier(&self.table),
+            Self::column_list(columns),
+            rows.join(",\n")
+        )
+    }
+
+    /// This function returns the COPY ... FROM STDIN statement that starts the block of records.
+    pub fn copy_statement(&self, columns: &[String]) -> String {
+        format!(
+            "COPY {} ({}) FROM STDIN;\n",
+            quote_identifier(&self.table),
+            Self::column_list(columns)
+        )
+    }
+
+    /// This function returns a record of a COPY block, (text format: tab separated values with \N for null).
+    ///
+    /// # Arguments
+    ///
+    /// * `record: &[Value]` - The typed values of the record, (see DataType::typed_value()).</br>
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate test_data_generation;
+    /// extern crate serde_json;
+    ///
+    /// use serde_json::Value;
+    /// use test_data_generation::sql::SqlOptions;
+    ///
+    /// fn main() {
+    ///     let options = SqlOptions::new("people");
+    ///     let record = vec![Value::from("Tab\there"), Value::Null, Value::Bool(true)];
+    ///
+    ///     assert_eq!(options.copy_record(&record), "Tab\\there\t\\N\tt\n");
+    /// }
+    /// ```
+    pub fn copy_record(&self, record: &[Value]) -> String {
+        let values: Vec<String> = record
+            .iter()
+            .map(|value| match value {
+                Value::Null => "\\N".to_string(),
+                Value::Bool(b) => (if *b { "t" } else { "f" }).to_string(),
+                Value::String(s) => s
+                    .replace('\\', "\\\\")
+                    .replace('\t', "\\t")
+                    .replace('\n', "\\n")
+                    .replace('\r', "\\r"),
+                other => other.to_string(),
+            })
+            .collect();
+
+        format!("{}\n", values.join("\t"))
+    }
+
+    /// This function returns the line that ends a COPY block.
+    pub fn copy_end(&self) -> &'static str {
+        "\\.\n"
+    }
+
+    /// This function returns the column type of the dialect for the type of the values.
+    fn column_type(&self, data_type: Option<DataType>) -> &'static str {
+        match (self.dialect, data_type) {
+            (SqlDialect::Postgres, Some(DataType::Boolean)) => "BOOLEAN",
+            (SqlDialect::Postgres, Some(DataType::Integer)) => "BIGINT",
+            (SqlDialect::Postgres, Some(DataType::Decimal)) => "NUMERIC",
+            (SqlDialect::Sqlite, Some(DataType::Boolean)) => "INTEGER",
+            (SqlDialect::Sqlite, Some(DataType::Integer)) => "INTEGER",
+            (SqlDialect::Sqlite, Some(DataType::Decimal)) => "REAL",
+            (_, _) => "TEXT",
+        }
+    }
+
+    /// This function returns the SQL literal of a typed value.
+    fn literal(&self, value: &Value) -> String {
+        match (value, self.dialect) {
+            (Value::Null, _) => "NULL".to_string(),
+            (Value::Bool(b), SqlDialect::Postgres) => {
+                (if *b { "TRUE" } else { "FALSE" }).to_string()
+            }
+            (Value::Bool(b), SqlDialect::Sqlite) => (if *b { "1" } else { "0" }).to_string(),
+            (Value::Number(n), _) => n.to_string(),
+            (Value::String(s), _) => quote_literal(s),
+            (other, _) => quote_literal(&other.to_string()),
+        }
+    }
+
+    fn column_list(columns: &[String]) -> String {
+        columns
+            .iter()
+            .map(|c| quote_identifier(c))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+/// This function double quotes an identifier (e.g.: a table or column name), so that any name can be used safely.
+///
+/// # Arguments
+///
+/// * `name: &str` - The name of the table or column.</br>
+///
+/// # Example
+///
+/// ```
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::sql::quote_identifier;
+///
+/// fn main() {
+///     assert_eq!(quote_identifier("first \"name\""), "\"first \"\"name\"\"\"");
+/// }
+/// ```
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// This function single quotes a string literal, so that any text can be used safely.
+///
+/// # Arguments
+///
+/// * `value: &str` - The text.</br>
+///
+/// # Example
+///
+/// ```
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::sql::quote_literal;
+///
+/// fn main() {
+///     assert_eq!(quote_literal("O'Brian"), "'O''Brian'");
+/// }
+/// ```
+pub fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Vec<String> {
+        vec![
+            "name".to_string(),
+            "active".to_string(),
+            "score".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_sql_options_validate() {
+        let mut options = SqlOptions::new("people");
+        assert!(options.validate().is_ok());
+
+        options.batch_size = 0;
+        assert!(options.validate().is_err());
+
+        options = SqlOptions::new("");
+        assert!(options.validate().is_err());
+    }
+
+    #[test]
+    fn test_sql_create_table_sqlite() {
+        let mut options = SqlOptions::new("my \"table\"");
+        options.dialect = SqlDialect::Sqlite;
+        let types = [
+            Some(DataType::String),
+            Some(DataType::Boolean),
+            Some(DataType::Decimal),
+        ];
+
+        assert_eq!(
+            options.create_table_statement(&columns(), &types),
+            "CREATE TABLE IF NOT EXISTS \"my \"\"table\"\"\" (\n  \"name\" TEXT,\n  \"active\" INTEGER,\n  \"score\" REAL\n);\n"
+        );
+    }
+
+    #[test]
+    fn test_sql_insert_statement_dialects() {
+        let records = vec![
+            vec![Value::from("a"), Value::Bool(true), Value::from(1.5)],
+            vec![Value::from("b"), Value::Null, Value::from(2)],
+        ];
+        let mut options = SqlOptions::new("people");
+
+        assert_eq!(
+            options.insert_statement(&columns(), &records),
+            "INSERT INTO \"people\" (\"name\", \"active\", \"score\") VALUES\n  ('a', TRUE, 1.5),\n  ('b', NULL, 2);\n"
+        );
+
+        options.dialect = SqlDialect::Sqlite;
+        assert!(options
+            .insert_statement(&columns(), &records)
+            .contains("('a', 1, 1.5)"));
+    }
+
+    #[test]
+    fn test_sql_copy_block() {
+        let options = SqlOptions::new("people");
+
+        assert_eq!(
+            options.copy_statement(&columns()),
+            "COPY \"people\" (\"name\", \"active\", \"score\") FROM STDIN;\n"
+        );
+        assert_eq!(
+            options.copy_record(&[
+                Value::from("back\\slash\nline"),
+                Value::Bool(false),
+                Value::from(3)
+            ]),
+            "back\\\\slash\\nline\tf\t3\n"
+        );
+        assert_eq!(options.copy_end(), "\\.\n");
+    }
+}
@@ -0,0 +1,188 @@
+#![cfg(feature = "cli")]
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+
+    fn tdg(args: &[&str]) -> std::process::Output {
+        Command::new(env!("CARGO_BIN_EXE_tdg"))
+            .args(args)
+            .output()
+            .unwrap()
+    }
+
+    #[test]
+    // ensure the data-sampler and data-generator tools can be chained from a shell script
+    fn cli_sample_and_generate() {
+        let dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+        let dsp = dir.join("cli-sample-01-dsp.json");
+        let csv = dir.join("cli-generated-01.csv");
+
+        let sampled = tdg(&[
+            "--tool",
+            "data-sampler",
+            "--input",
+            "./tests/samples/sample-01.csv",
+            "--output",
+            dsp.to_str().unwrap(),
+            "--seed",
+            "7",
+        ]);
+        assert!(sampled.status.success());
+        assert!(dsp.exists());
+
+        let generated = tdg(&[
+            "--tool",
+            "data-generator",
+            "--input",
+            dsp.to_str().unwrap(),
+            "--output",
+            csv.to_str().unwrap(),
+            "--rows",
+            "10",
+        ]);
+        assert!(generated.status.success());
+
+        let data = fs::read_to_string(&csv).unwrap();
+        assert_eq!(data.lines().count(), 11);
+        assert_eq!(data.lines().next(), Some("firstname,lastname"));
+    }
+
+    #[test]
+    // ensure the data-sampler tool can stream the csv data from stdin
+    fn cli_sample_from_stdin() {
+        let dsp = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli-stdin-dsp.json");
+
+        let sampled = Command::new(env!("CARGO_BIN_EXE_tdg"))
+            .args(["-i", "-", "-o", dsp.to_str().unwrap()])
+            .stdin(fs::File::open("./tests/samples/sample-01.csv").unwrap())
+            .output()
+            .unwrap();
+
+        assert!(sampled.status.success());
+        assert!(fs::read_to_string(&dsp).unwrap().contains("firstname"));
+    }
+
+    #[test]
+    // ensure the data-sampler tool analyzes ndjson files as nested JSON records
+    fn cli_sample_ndjson() {
+        let dsp = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli-events-dsp.json");
+
+        let sampled = tdg(&[
+            "-i",
+            "./tests/samples/sample-events.ndjson",
+            "-o",
+            dsp.to_str().unwrap(),
+        ]);
+
+        assert!(sampled.status.success());
+        assert!(fs::read_to_string(&dsp).unwrap().contains("items[].sku"));
+    }
+
+    #[test]
+    // ensure the data-generator tool can stream the generated csv data to stdout
+    fn cli_generate_to_stdout() {
+        let generated = tdg(&[
+            "-t",
+            "data-generator",
+            "-i",
+            "./tests/samples/sample-00-dsp.json",
+            "-o",
+            "-",
+            "-r",
+            "5",
+            "-d",
+            "|",
+        ]);
+        assert!(generated.status.success());
+
+        let data = String::from_utf8(generated.stdout).unwrap();
+        assert_eq!(data.lines().count(), 6);
+        assert!(data.starts_with("status\nOK\n"));
+    }
+
+    #[test]
+    // ensure the archive of fields in privacy mode can only be used by the data-generator with the key of the data-sampler
+    fn cli_privacy_key_round_trip() {
+        let dsp = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli-privacy-dsp.json");
+
+        // the key is read from the configuration file
+        let sampled = tdg(&[
+            "-c",
+            "./tests/config/tdg-privacy.yaml",
+            "-i",
+            "./tests/samples/sample-01.csv",
+            "-o",
+            dsp.to_str().unwrap(),
+        ]);
+        assert!(sampled.status.success());
+        assert!(sampled.stderr.is_empty());
+
+        let generate = |key: Option<&str>| {
+            let mut args = vec![
+                "-t",
+                "data-generator",
+                "-i",
+                dsp.to_str().unwrap(),
+                "-o",
+                "-",
+            ];
+            if let Some(k) = key {
+                args.extend(["--privacy-key", k]);
+            }
+            tdg(&args)
+        };
+
+        // nothing is written (not even the header) without the key
+        let failed = generate(None);
+        assert!(!failed.status.success());
+        assert!(failed.stdout.is_empty());
+        assert!(String::from_utf8(failed.stderr)
+            .unwrap()
+            .contains("privacy_key"));
+
+        assert!(!generate(Some("0x5eee")).status.success());
+
+        let generated = generate(Some("0x5eed"));
+        assert!(generated.status.success());
+        let data = String::from_utf8(generated.stdout).unwrap();
+        assert_eq!(data.lines().count(), 101);
+        assert_eq!(data.lines().next(), Some("firstname,lastname"));
+    }
+
+    #[test]
+    // ensure the profile-inspector tool prints a summary of each profile
+    fn cli_inspect() {
+        let inspected = tdg(&[
+            "-t",
+            "profile-inspector",
+            "-i",
+            "./tests/samples/sample-0.2.1-dsp.json",
+        ]);
+        assert!(inspected.status.success());
+
+        let summary = String::from_utf8(inspected.stdout).unwrap();
+        assert!(summary.starts_with("status\n"));
+        assert!(summary.contains("top patterns:"));
+    }
+
+    #[test]
+    // ensure errors are reported with a non-zero exit code
+    fn cli_bad_input() {
+        let failed = tdg(&[
+            "-t",
+            "data-generator",
+            "-i",
+            "./tests/samples/bad-path.json",
+            "-o",
+            "./tests/samples/never-generated.csv",
+        ]);
+
+        assert!(!failed.status.success());
+        assert!(String::from_utf8(failed.stderr)
+            .unwrap()
+            .starts_with("tdg: Could not open file"));
+    }
+}